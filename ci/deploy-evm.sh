@@ -31,3 +31,9 @@ neon-cli --url $SOLANA_URL --evm_loader $EVM_LOADER \
   --keypair evm_loader-keypair.json \
   --solana_key_for_config 86KRyxmrm4QnZXdrnf4NpHDNM3hdduUyS3gXgPhZjGuY \
   --loglevel debug init-environment --send-trx --keys-dir keys/
+
+OPERATORS=$(for keypair in /opt/operator-keypairs/*.json; do solana-keygen pubkey $keypair; done)
+echo "Registering operators: " $OPERATORS
+neon-cli --url $SOLANA_URL --evm_loader $EVM_LOADER \
+  --keypair evm_loader-keypair.json \
+  --loglevel debug operator-registry add $OPERATORS
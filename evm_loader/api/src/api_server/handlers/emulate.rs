@@ -1,7 +1,7 @@
 #![allow(clippy::future_not_send)]
 
 use actix_request_identifier::RequestId;
-use actix_web::{http::StatusCode, post, web::Json, Responder};
+use actix_web::{post, web::Json, Responder};
 use neon_lib::tracing::tracers::TracerTypeEnum;
 use std::convert::Into;
 use tracing::info;
@@ -25,7 +25,7 @@ pub async fn emulate(
 
     let rpc = match state.build_rpc(slot, index).await {
         Ok(rpc) => rpc,
-        Err(e) => return process_error(StatusCode::BAD_REQUEST, &e),
+        Err(e) => return process_error(&e),
     };
 
     process_result(
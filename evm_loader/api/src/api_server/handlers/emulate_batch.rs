@@ -0,0 +1,37 @@
+#![allow(clippy::future_not_send)]
+
+use actix_request_identifier::RequestId;
+use actix_web::{post, web::Json, Responder};
+use std::convert::Into;
+use tracing::info;
+
+use crate::api_server::handlers::process_error;
+use crate::{
+    commands::emulate_batch as EmulateBatchCommand, types::EmulateBatchApiRequest, NeonApiState,
+};
+
+use super::process_result;
+
+#[tracing::instrument(skip_all, fields(id = request_id.as_str()))]
+#[post("/emulate_batch")]
+pub async fn emulate_batch(
+    state: NeonApiState,
+    request_id: RequestId,
+    Json(emulate_request): Json<EmulateBatchApiRequest>,
+) -> impl Responder {
+    info!("emulate_batch_request={:?}", emulate_request);
+
+    let slot = emulate_request.slot;
+    let index = emulate_request.tx_index_in_block;
+
+    let rpc = match state.build_rpc(slot, index).await {
+        Ok(rpc) => rpc,
+        Err(e) => return process_error(&e),
+    };
+
+    process_result(
+        &EmulateBatchCommand::execute(&rpc, state.config.evm_loader, emulate_request.body)
+            .await
+            .map_err(Into::into),
+    )
+}
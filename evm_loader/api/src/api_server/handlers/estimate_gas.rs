@@ -0,0 +1,37 @@
+#![allow(clippy::future_not_send)]
+
+use actix_request_identifier::RequestId;
+use actix_web::{post, web::Json, Responder};
+use std::convert::Into;
+use tracing::info;
+
+use crate::api_server::handlers::process_error;
+use crate::{
+    commands::estimate_gas as EstimateGasCommand, types::EstimateGasApiRequest, NeonApiState,
+};
+
+use super::process_result;
+
+#[tracing::instrument(skip_all, fields(id = request_id.as_str()))]
+#[post("/estimate_gas")]
+pub async fn estimate_gas(
+    state: NeonApiState,
+    request_id: RequestId,
+    Json(estimate_request): Json<EstimateGasApiRequest>,
+) -> impl Responder {
+    info!("estimate_gas_request={:?}", estimate_request);
+
+    let slot = estimate_request.slot;
+    let index = estimate_request.tx_index_in_block;
+
+    let rpc = match state.build_rpc(slot, index).await {
+        Ok(rpc) => rpc,
+        Err(e) => return process_error(&e),
+    };
+
+    process_result(
+        &EstimateGasCommand::execute(&rpc, state.config.evm_loader, estimate_request.body)
+            .await
+            .map_err(Into::into),
+    )
+}
@@ -5,7 +5,7 @@ use crate::commands::get_balance as GetBalanceCommand;
 use crate::{types::GetBalanceRequest, NeonApiState};
 use actix_request_identifier::RequestId;
 use actix_web::web::Json;
-use actix_web::{http::StatusCode, post, Responder};
+use actix_web::{post, Responder};
 use std::convert::Into;
 use tracing::log::info;
 
@@ -22,7 +22,7 @@ pub async fn get_balance(
 
     let rpc = match state.build_rpc(get_balance_request.slot, None).await {
         Ok(rpc) => rpc,
-        Err(e) => return process_error(StatusCode::BAD_REQUEST, &e),
+        Err(e) => return process_error(&e),
     };
 
     process_result(
@@ -4,7 +4,7 @@ use crate::api_server::handlers::process_error;
 use crate::NeonApiState;
 use actix_request_identifier::RequestId;
 use actix_web::routes;
-use actix_web::{http::StatusCode, Responder};
+use actix_web::Responder;
 use std::convert::Into;
 
 use crate::commands::get_config as GetConfigCommand;
@@ -18,7 +18,7 @@ use super::process_result;
 pub async fn get_config(state: NeonApiState, request_id: RequestId) -> impl Responder {
     let rpc = match state.build_rpc(None, None).await {
         Ok(rpc) => rpc,
-        Err(e) => return process_error(StatusCode::BAD_REQUEST, &e),
+        Err(e) => return process_error(&e),
     };
 
     process_result(
@@ -5,7 +5,7 @@ use crate::{types::GetStorageAtRequest, NeonApiState};
 use actix_request_identifier::RequestId;
 use actix_web::post;
 use actix_web::web::Json;
-use actix_web::{http::StatusCode, Responder};
+use actix_web::Responder;
 use std::convert::Into;
 use tracing::info;
 
@@ -24,7 +24,7 @@ pub async fn get_storage_at(
 
     let rpc = match state.build_rpc(get_storage_at_request.slot, None).await {
         Ok(rpc) => rpc,
-        Err(e) => return process_error(StatusCode::BAD_REQUEST, &e),
+        Err(e) => return process_error(&e),
     };
 
     process_result(
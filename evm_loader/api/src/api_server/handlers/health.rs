@@ -0,0 +1,55 @@
+#![allow(clippy::future_not_send)]
+
+use crate::api_server::handlers::process_error;
+use crate::NeonApiState;
+use actix_web::get;
+use actix_web::http::StatusCode;
+use actix_web::web::Json;
+use actix_web::Responder;
+use serde_json::json;
+
+use crate::commands::health as HealthCommand;
+
+/// Liveness check: responds while the service is running, dependencies are checked by `/ready`
+#[get("/health")]
+pub async fn health() -> impl Responder {
+    (
+        Json(json!({
+            "result": "success",
+            "value": "alive",
+        })),
+        StatusCode::OK,
+    )
+}
+
+/// Readiness check: reports the state of the dependencies,
+/// responds with 503 while any of them is unavailable
+#[tracing::instrument(skip_all)]
+#[get("/ready")]
+pub async fn ready(state: NeonApiState) -> impl Responder {
+    let health = match HealthCommand::execute(
+        &state.rpc_client,
+        &state.tracer_db,
+        state.config.evm_loader,
+        state.config.health_max_slot_lag,
+    )
+    .await
+    {
+        Ok(health) => health,
+        Err(e) => return process_error(&e),
+    };
+
+    let (result, status_code) = if health.ready {
+        ("success", StatusCode::OK)
+    } else {
+        ("error", StatusCode::SERVICE_UNAVAILABLE)
+    };
+
+    (
+        Json(json!({
+            "result": result,
+            "value": health,
+        })),
+        status_code,
+    )
+}
@@ -0,0 +1,11 @@
+use actix_web::get;
+use actix_web::HttpResponse;
+use actix_web::Responder;
+use neon_lib::metrics;
+
+#[get("/metrics")]
+pub async fn metrics_route() -> impl Responder {
+    HttpResponse::Ok()
+        .content_type(metrics::CONTENT_TYPE)
+        .body(metrics::gather())
+}
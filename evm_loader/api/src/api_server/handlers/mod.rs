@@ -11,11 +11,15 @@ use tracing::error;
 
 pub mod build_info;
 pub mod emulate;
+pub mod emulate_batch;
+pub mod estimate_gas;
 pub mod get_balance;
 pub mod get_config;
 pub mod get_contract;
 pub mod get_holder;
 pub mod get_storage_at;
+pub mod health;
+pub mod metrics;
 pub mod simulate_solana;
 pub mod trace;
 
@@ -57,18 +61,27 @@ fn process_result<T: Serialize>(
             })),
             StatusCode::OK,
         ),
-        Err(e) => process_error(StatusCode::INTERNAL_SERVER_ERROR, &e.0),
+        Err(e) => process_error(&e.0),
     }
 }
 
-fn process_error(status_code: StatusCode, e: &NeonError) -> (Json<Value>, StatusCode) {
+fn process_error(e: &NeonError) -> (Json<Value>, StatusCode) {
     error!("NeonError: {e}");
-    (
-        Json(json!({
-            "result": "error",
-            "error": e.to_string(),
-            "error_code": e.error_code(),
-        })),
-        status_code,
-    )
+    neon_lib::metrics::observe_error(e.error_code());
+
+    let kind = e.kind();
+    let status_code =
+        StatusCode::from_u16(kind.http_status()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+
+    let mut body = json!({
+        "result": "error",
+        "error": e.to_string(),
+        "error_code": e.error_code(),
+        "error_kind": kind,
+    });
+    if let Some(data) = e.data() {
+        body["data"] = data;
+    }
+
+    (Json(body), status_code)
 }
@@ -5,6 +5,7 @@ mod api_server;
 #[allow(clippy::module_name_repetitions)]
 mod build_info;
 
+use actix_web::dev::Service;
 use actix_web::web;
 use actix_web::App;
 use actix_web::HttpServer;
@@ -13,20 +14,25 @@ use neon_lib::abi::state::State;
 pub use neon_lib::commands;
 pub use neon_lib::config;
 pub use neon_lib::errors;
+use neon_lib::metrics;
 pub use neon_lib::types;
 use tracing_appender::non_blocking::NonBlockingBuilder;
 
 use actix_request_identifier::RequestIdentifier;
 use actix_web::web::Data;
-use std::{env, net::SocketAddr, str::FromStr};
+use std::{env, net::SocketAddr, str::FromStr, time::Instant};
 
 use crate::api_server::handlers::build_info::build_info_route;
 use crate::api_server::handlers::emulate::emulate;
+use crate::api_server::handlers::emulate_batch::emulate_batch;
+use crate::api_server::handlers::estimate_gas::estimate_gas;
 use crate::api_server::handlers::get_balance::get_balance;
 use crate::api_server::handlers::get_config::get_config;
 use crate::api_server::handlers::get_contract::get_contract;
 use crate::api_server::handlers::get_holder::get_holder_account_data;
 use crate::api_server::handlers::get_storage_at::get_storage_at;
+use crate::api_server::handlers::health::{health, ready};
+use crate::api_server::handlers::metrics::metrics_route;
 use crate::api_server::handlers::simulate_solana::simulate_solana;
 use crate::api_server::handlers::trace::trace;
 use crate::build_info::get_build_info;
@@ -67,20 +73,38 @@ async fn main() -> NeonApiResult<()> {
     let addr = SocketAddr::from_str(listener_addr.as_str())?;
     tracing::info!("listening on {}", addr);
     HttpServer::new(move || {
-        App::new().service(
-            web::scope("/api")
-                .app_data(state.clone())
-                .service(build_info_route)
-                .service(emulate)
-                .service(get_balance)
-                .service(get_contract)
-                .service(get_storage_at)
-                .service(get_config)
-                .service(get_holder_account_data)
-                .service(trace)
-                .service(simulate_solana)
-                .wrap(RequestIdentifier::with_uuid()),
-        )
+        App::new()
+            .app_data(state.clone())
+            .service(metrics_route)
+            .service(health)
+            .service(ready)
+            .service(
+                web::scope("/api")
+                    .service(build_info_route)
+                    .service(emulate)
+                    .service(emulate_batch)
+                    .service(estimate_gas)
+                    .service(get_balance)
+                    .service(get_contract)
+                    .service(get_storage_at)
+                    .service(get_config)
+                    .service(get_holder_account_data)
+                    .service(trace)
+                    .service(simulate_solana)
+                    .wrap(RequestIdentifier::with_uuid())
+                    .wrap_fn(|request, service| {
+                        let method = request
+                            .match_pattern()
+                            .unwrap_or_else(|| "unknown".to_owned());
+                        let time_start = Instant::now();
+                        let response = service.call(request);
+                        async move {
+                            let response = response.await;
+                            metrics::observe_request(&method, time_start.elapsed());
+                            response
+                        }
+                    }),
+            )
     })
     .bind(addr)
     .unwrap()
@@ -10,11 +10,12 @@ mod program_options;
 
 use neon_lib::{
     commands::{
-        collect_treasury, emulate, get_balance, get_config, get_contract, get_holder, get_neon_elf,
-        get_storage_at, init_environment, trace,
+        collect_treasury, emulate, emulate_batch, estimate_gas, get_balance, get_config,
+        get_contract, get_holder, get_neon_elf, get_storage_at, holder, init_environment,
+        operator_balance, operator_registry, send_neon_transaction, stuck_transactions, trace,
     },
     rpc::CloneRpcClient,
-    types::{BalanceAddress, EmulateRequest},
+    types::BalanceAddress,
     Config,
 };
 
@@ -23,8 +24,9 @@ use std::io::Read;
 
 use ethnum::U256;
 use log::debug;
+use serde::de::DeserializeOwned;
 use serde_json::json;
-use solana_clap_utils::input_parsers::{pubkey_of, value_of};
+use solana_clap_utils::input_parsers::{pubkey_of, pubkeys_of, value_of};
 use tokio::time::Instant;
 
 use crate::build_info::get_build_info;
@@ -46,15 +48,31 @@ async fn run(options: &ArgMatches<'_>) -> NeonCliResult {
         ("emulate", Some(_)) => {
             let rpc = build_rpc(options, config).await?;
 
-            let request = read_tx_from_stdin()?;
+            let request = read_json_from_stdin()?;
             emulate::execute(&rpc, config.evm_loader, request, None::<TracerTypeEnum>)
                 .await
                 .map(|(result, _)| json!(result))
         }
+        ("emulate-batch", Some(_)) => {
+            let rpc = build_rpc(options, config).await?;
+
+            let request = read_json_from_stdin()?;
+            emulate_batch::execute(&rpc, config.evm_loader, request)
+                .await
+                .map(|result| json!(result))
+        }
+        ("estimate-gas", Some(_)) => {
+            let rpc = build_rpc(options, config).await?;
+
+            let request = read_json_from_stdin()?;
+            estimate_gas::execute(&rpc, config.evm_loader, request)
+                .await
+                .map(|result| json!(result))
+        }
         ("trace", Some(_)) => {
             let rpc = build_rpc(options, config).await?;
 
-            let request = read_tx_from_stdin()?;
+            let request = read_json_from_stdin()?;
             trace::trace_transaction(&rpc, config.evm_loader, request)
                 .await
                 .map(|trace| json!(trace))
@@ -107,6 +125,230 @@ async fn run(options: &ArgMatches<'_>) -> NeonCliResult {
                 .await
                 .map(|result| json!(result))
         }
+        ("send-transaction", Some(params)) => {
+            let rpc_client = CloneRpcClient::new_from_config(config);
+            let signer = build_signer(config)?;
+
+            let raw_transaction = params.value_of("raw_transaction").unwrap();
+            let raw_transaction = hex::decode(
+                raw_transaction
+                    .strip_prefix("0x")
+                    .unwrap_or(raw_transaction),
+            )
+            .expect("raw_transaction parse error");
+
+            let send_config = send_neon_transaction::SendTransactionConfig {
+                raw_transaction,
+                operator_address: address_of(params, "operator_address"),
+                holder_seed: params.value_of("holder_seed").map(String::from),
+                steps_per_iteration: value_of(params, "steps_per_iteration"),
+                max_iterations: value_of(params, "max_iterations"),
+                iterative: params.is_present("iterative"),
+            };
+
+            send_neon_transaction::execute(config, &rpc_client, &*signer, send_config)
+                .await
+                .map(|result| json!(result))
+        }
+        ("treasury-report", Some(_)) => {
+            let rpc_client = CloneRpcClient::new_from_config(config);
+
+            collect_treasury::report(config, &rpc_client)
+                .await
+                .map(|result| json!(result))
+        }
+        ("operator-registry", Some(params)) => {
+            let rpc_client = CloneRpcClient::new_from_config(config);
+
+            match params.subcommand() {
+                ("status", Some(params)) => {
+                    let operator = pubkey_of(params, "operator").unwrap();
+                    operator_registry::status(config, &rpc_client, operator)
+                        .await
+                        .map(|result| json!(result))
+                }
+                ("list", Some(_)) => operator_registry::list(config, &rpc_client)
+                    .await
+                    .map(|result| json!(result)),
+                (command, Some(params)) => {
+                    let signer = build_signer(config)?;
+                    let operators = pubkeys_of(params, "operator").unwrap();
+
+                    match command {
+                        "add" => {
+                            operator_registry::add(config, &rpc_client, &*signer, &operators).await
+                        }
+                        "remove" => {
+                            operator_registry::remove(config, &rpc_client, &*signer, &operators)
+                                .await
+                        }
+                        "suspend" | "resume" => {
+                            let suspended = command == "suspend";
+                            operator_registry::set_suspended(
+                                config,
+                                &rpc_client,
+                                &*signer,
+                                &operators,
+                                suspended,
+                            )
+                            .await
+                        }
+                        _ => unreachable!(),
+                    }
+                    .map(|result| json!(result))
+                }
+                _ => unreachable!(),
+            }
+        }
+        ("holder", Some(params)) => {
+            let rpc_client = CloneRpcClient::new_from_config(config);
+
+            match params.subcommand() {
+                ("show", Some(params)) => {
+                    let account = pubkey_of(params, "account").unwrap();
+
+                    get_holder::execute(&rpc_client, &config.evm_loader, account)
+                        .await
+                        .map(|result| json!(result))
+                }
+                ("list", Some(params))
+                    if !params.is_present("cancel") && !params.is_present("delete") =>
+                {
+                    let operator = pubkey_of(params, "operator")
+                        .map_or_else(|| build_signer(config).map(|signer| signer.pubkey()), Ok)?;
+
+                    holder::list(config, &rpc_client, &operator)
+                        .await
+                        .map(|holders| json!({ "holders": holders }))
+                }
+                ("list", Some(params)) => {
+                    let signer = build_signer(config)?;
+                    let cleanup = holder::HolderCleanupConfig {
+                        cancel: params.is_present("cancel"),
+                        delete: params.is_present("delete"),
+                        operator_address: address_of(params, "operator_address"),
+                    };
+
+                    holder::cleanup(config, &rpc_client, &*signer, &cleanup)
+                        .await
+                        .map(|result| json!(result))
+                }
+                (command, Some(params)) => {
+                    let signer = build_signer(config)?;
+                    let seed = params
+                        .value_of("seed")
+                        .unwrap_or(holder::DEFAULT_HOLDER_SEED);
+
+                    match command {
+                        "create" => {
+                            let size = value_of(params, "size");
+                            holder::create(config, &rpc_client, &*signer, seed, size).await
+                        }
+                        "write" => {
+                            let raw_transaction = params.value_of("raw_transaction").unwrap();
+                            let raw_transaction = hex::decode(
+                                raw_transaction
+                                    .strip_prefix("0x")
+                                    .unwrap_or(raw_transaction),
+                            )
+                            .expect("raw_transaction parse error");
+
+                            holder::write(config, &rpc_client, &*signer, seed, &raw_transaction)
+                                .await
+                        }
+                        "delete" => holder::delete(config, &rpc_client, &*signer, seed).await,
+                        _ => unreachable!(),
+                    }
+                    .map(|result| json!(result))
+                }
+                _ => unreachable!(),
+            }
+        }
+        ("operator-balance", Some(params)) => {
+            let rpc_client = CloneRpcClient::new_from_config(config);
+
+            match params.subcommand() {
+                ("list", Some(params)) => {
+                    let operators = pubkeys_of(params, "operator").map_or_else(
+                        || build_signer(config).map(|signer| vec![signer.pubkey()]),
+                        Ok,
+                    )?;
+                    operator_balance::list(config, &rpc_client, &operators)
+                        .await
+                        .map(|result| json!(result))
+                }
+                ("show", Some(params)) => {
+                    let operator = pubkey_of(params, "operator")
+                        .map_or_else(|| build_signer(config).map(|signer| signer.pubkey()), Ok)?;
+                    let address = address_of(params, "address").unwrap();
+                    let chain_id = value_of(params, "chain_id").unwrap();
+
+                    operator_balance::show(config, &rpc_client, operator, address, chain_id)
+                        .await
+                        .map(|result| json!(result))
+                }
+                (command, Some(params)) => {
+                    let signer = build_signer(config)?;
+                    let address = address_of(params, "address").unwrap();
+                    let chain_id = value_of(params, "chain_id").unwrap();
+
+                    match command {
+                        "create" => {
+                            operator_balance::create(
+                                config,
+                                &rpc_client,
+                                &*signer,
+                                address,
+                                chain_id,
+                            )
+                            .await
+                        }
+                        "withdraw" => {
+                            operator_balance::withdraw(
+                                config,
+                                &rpc_client,
+                                &*signer,
+                                address,
+                                chain_id,
+                            )
+                            .await
+                        }
+                        "delete" => {
+                            operator_balance::delete(
+                                config,
+                                &rpc_client,
+                                &*signer,
+                                address,
+                                chain_id,
+                            )
+                            .await
+                        }
+                        _ => unreachable!(),
+                    }
+                    .map(|result| json!(result))
+                }
+                _ => unreachable!(),
+            }
+        }
+        ("stuck-transactions", Some(params)) => {
+            let rpc_client = CloneRpcClient::new_from_config(config);
+            let cancel = params.is_present("cancel");
+            let signer = if cancel {
+                Some(build_signer(config)?)
+            } else {
+                None
+            };
+
+            let stuck_config = stuck_transactions::StuckTransactionsConfig {
+                threshold: value_of(params, "threshold").unwrap(),
+                cancel,
+                operator_address: address_of(params, "operator_address"),
+            };
+
+            stuck_transactions::execute(config, &rpc_client, signer.as_deref(), &stuck_config)
+                .await
+                .map(|result| json!(result))
+        }
         ("init-environment", Some(params)) => {
             let rpc_client = CloneRpcClient::new_from_config(config);
             let signer = build_signer(config)?;
@@ -214,7 +456,7 @@ async fn main() {
     };
 }
 
-fn read_tx_from_stdin() -> Result<EmulateRequest, NeonError> {
+fn read_json_from_stdin<T: DeserializeOwned>() -> Result<T, NeonError> {
     let mut stdin_buffer = String::new();
     std::io::stdin().read_to_string(&mut stdin_buffer)?;
 
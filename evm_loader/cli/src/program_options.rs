@@ -29,6 +29,58 @@ where
         .map_err(|e| e.to_string())
 }
 
+// Return an error if string cannot be parsed as hex data with an optional 0x prefix
+fn is_valid_hex<T>(string: T) -> Result<(), String>
+where
+    T: AsRef<str>,
+{
+    let value = string.as_ref();
+    hex::decode(value.strip_prefix("0x").unwrap_or(value))
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+fn operators_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("operator")
+        .index(1)
+        .value_name("OPERATOR")
+        .takes_value(true)
+        .multiple(true)
+        .required(true)
+        .validator(is_valid_pubkey)
+        .help("Operator public keys")
+}
+
+fn balance_args<'a, 'b>(subcommand: App<'a, 'b>) -> App<'a, 'b> {
+    subcommand
+        .arg(
+            Arg::with_name("address")
+                .index(1)
+                .value_name("ADDRESS")
+                .takes_value(true)
+                .required(true)
+                .validator(is_valid_address)
+                .help("Ethereum address of the operator balance"),
+        )
+        .arg(
+            Arg::with_name("chain_id")
+                .index(2)
+                .value_name("CHAIN_ID")
+                .takes_value(true)
+                .required(true)
+                .validator(is_amount::<u64, _>)
+                .help("Network chain_id"),
+        )
+}
+
+fn holder_seed_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("seed")
+        .long("seed")
+        .value_name("SEED")
+        .takes_value(true)
+        .help("Seed of the holder account [default: holder]")
+}
+
 fn is_amount<T, U>(amount: U) -> Result<(), String>
 where
     T: std::str::FromStr,
@@ -159,6 +211,14 @@ pub fn parse<'a>() -> ArgMatches<'a> {
             SubCommand::with_name("emulate")
             .about("Emulation transaction. Parameters can be provided via STDIN as a JSON object.")
         )
+        .subcommand(
+            SubCommand::with_name("emulate-batch")
+            .about("Emulation of dependent transactions one after another. Parameters can be provided via STDIN as a JSON object.")
+        )
+        .subcommand(
+            SubCommand::with_name("estimate-gas")
+            .about("Search of the minimal gas limit of transaction. Parameters can be provided via STDIN as a JSON object.")
+        )
         .subcommand(
             SubCommand::with_name("trace")
             .about("Emulation transaction to collecting traces. Parameters can be provided via STDIN as a JSON object.")
@@ -202,7 +262,6 @@ pub fn parse<'a>() -> ArgMatches<'a> {
         )
         .subcommand(
             SubCommand::with_name("get-holder-account-data")
-                .alias("holder")
                 .about("Get values stored in a Holder acount")
                 .arg(
                     Arg::with_name("account")
@@ -234,6 +293,254 @@ pub fn parse<'a>() -> ArgMatches<'a> {
             SubCommand::with_name("collect-treasury")
                 .about("Collect lamports from auxiliary treasury accounts to the main treasury balance")
         )
+        .subcommand(
+            SubCommand::with_name("treasury-report")
+                .about("Show balances of the main treasury and auxiliary treasury accounts")
+        )
+        .subcommand(
+            SubCommand::with_name("operator-registry")
+                .about("Manage the registry of operators authorized to execute transactions")
+                .setting(AppSettings::SubcommandRequiredElseHelp)
+                .subcommand(
+                    SubCommand::with_name("add")
+                        .about("Register operators (signer must be the program upgrade authority)")
+                        .arg(operators_arg())
+                )
+                .subcommand(
+                    SubCommand::with_name("remove")
+                        .about("Remove operators from the registry (signer must be the program upgrade authority)")
+                        .arg(operators_arg())
+                )
+                .subcommand(
+                    SubCommand::with_name("suspend")
+                        .about("Suspend operators (signer must be the program upgrade authority)")
+                        .arg(operators_arg())
+                )
+                .subcommand(
+                    SubCommand::with_name("resume")
+                        .about("Resume suspended operators (signer must be the program upgrade authority)")
+                        .arg(operators_arg())
+                )
+                .subcommand(
+                    SubCommand::with_name("status")
+                        .about("Show registry status of the operator")
+                        .arg(
+                            Arg::with_name("operator")
+                                .index(1)
+                                .value_name("OPERATOR")
+                                .takes_value(true)
+                                .required(true)
+                                .validator(is_valid_pubkey)
+                                .help("Operator public key"),
+                        )
+                )
+                .subcommand(
+                    SubCommand::with_name("list")
+                        .about("List registered operators")
+                )
+        )
+        .subcommand(
+            SubCommand::with_name("send-transaction")
+                .about("Execute a signed Neon transaction and print its receipt")
+                .arg(
+                    Arg::with_name("raw_transaction")
+                        .index(1)
+                        .value_name("RAW_TRANSACTION")
+                        .takes_value(true)
+                        .required(true)
+                        .validator(is_valid_hex)
+                        .help("Signed transaction in RLP encoding (hex)"),
+                )
+                .arg(
+                    Arg::with_name("operator_address")
+                        .long("operator-address")
+                        .value_name("ADDRESS")
+                        .takes_value(true)
+                        .validator(is_valid_address)
+                        .help("Address of the operator balance account"),
+                )
+                .arg(
+                    Arg::with_name("holder_seed")
+                        .long("holder-seed")
+                        .value_name("SEED")
+                        .takes_value(true)
+                        .help("Seed of the operator holder account [default: holder]"),
+                )
+                .arg(
+                    Arg::with_name("steps_per_iteration")
+                        .long("steps-per-iteration")
+                        .value_name("STEPS")
+                        .takes_value(true)
+                        .validator(is_amount::<u64, _>)
+                        .help("EVM steps per iteration of the iterative execution"),
+                )
+                .arg(
+                    Arg::with_name("max_iterations")
+                        .long("max-iterations")
+                        .value_name("COUNT")
+                        .takes_value(true)
+                        .validator(is_amount::<u64, _>)
+                        .help("Cancel the iterative transaction after this number of iterations"),
+                )
+                .arg(
+                    Arg::with_name("iterative")
+                        .long("iterative")
+                        .takes_value(false)
+                        .help("Execute the transaction iteratively even if it fits into one iteration"),
+                )
+        )
+        .subcommand(
+            SubCommand::with_name("operator-balance")
+                .about("Manage operator balance accounts which accrue the gas payments")
+                .setting(AppSettings::SubcommandRequiredElseHelp)
+                .subcommand(
+                    balance_args(SubCommand::with_name("create"))
+                        .about("Create the operator balance account of the signer")
+                )
+                .subcommand(
+                    balance_args(SubCommand::with_name("show"))
+                        .about("Show the operator balance account")
+                        .arg(
+                            Arg::with_name("operator")
+                                .long("operator")
+                                .value_name("OPERATOR")
+                                .takes_value(true)
+                                .validator(is_valid_pubkey)
+                                .help("Operator public key [default: signer]"),
+                        )
+                )
+                .subcommand(
+                    balance_args(SubCommand::with_name("withdraw"))
+                        .about("Withdraw the accrued balance to the user balance of the same address")
+                )
+                .subcommand(
+                    balance_args(SubCommand::with_name("delete"))
+                        .about("Withdraw the accrued balance and delete the operator balance account")
+                )
+                .subcommand(
+                    SubCommand::with_name("list")
+                        .about("List operator balance accounts and accrued balances per chain")
+                        .arg(
+                            Arg::with_name("operator")
+                                .index(1)
+                                .value_name("OPERATOR")
+                                .takes_value(true)
+                                .multiple(true)
+                                .validator(is_valid_pubkey)
+                                .help("Operator public keys [default: signer]"),
+                        )
+                )
+        )
+        .subcommand(
+            SubCommand::with_name("holder")
+                .about("Manage holder accounts of the operator")
+                .setting(AppSettings::SubcommandRequiredElseHelp)
+                .subcommand(
+                    SubCommand::with_name("create")
+                        .about("Create the holder account of the signer")
+                        .arg(holder_seed_arg())
+                        .arg(
+                            Arg::with_name("size")
+                                .long("size")
+                                .value_name("SIZE")
+                                .takes_value(true)
+                                .validator(is_amount::<usize, _>)
+                                .help("Size of the holder account in bytes [default: 262144]"),
+                        )
+                )
+                .subcommand(
+                    SubCommand::with_name("write")
+                        .about("Write a signed Neon transaction into the holder account of the signer")
+                        .arg(holder_seed_arg())
+                        .arg(
+                            Arg::with_name("raw_transaction")
+                                .index(1)
+                                .value_name("RAW_TRANSACTION")
+                                .takes_value(true)
+                                .required(true)
+                                .validator(is_valid_hex)
+                                .help("Signed transaction in RLP encoding (hex)"),
+                        )
+                )
+                .subcommand(
+                    SubCommand::with_name("delete")
+                        .about("Delete the holder account of the signer and reclaim its rent")
+                        .arg(holder_seed_arg())
+                )
+                .subcommand(
+                    SubCommand::with_name("show")
+                        .about("Get values stored in a Holder acount")
+                        .arg(
+                            Arg::with_name("account")
+                                .index(1)
+                                .value_name("ACCOUNT")
+                                .takes_value(true)
+                                .required(true)
+                                .validator(is_valid_pubkey)
+                                .help("Public Key"),
+                        )
+                )
+                .subcommand(
+                    SubCommand::with_name("list")
+                        .about("List holder accounts of the operator")
+                        .arg(
+                            Arg::with_name("operator")
+                                .long("operator")
+                                .value_name("OPERATOR")
+                                .takes_value(true)
+                                .validator(is_valid_pubkey)
+                                .conflicts_with_all(&["cancel", "delete"])
+                                .help("Operator public key [default: signer]"),
+                        )
+                        .arg(
+                            Arg::with_name("cancel")
+                                .long("cancel")
+                                .takes_value(false)
+                                .help("Cancel transactions in the active holders"),
+                        )
+                        .arg(
+                            Arg::with_name("delete")
+                                .long("delete")
+                                .takes_value(false)
+                                .help("Delete finalized holders and reclaim their rent"),
+                        )
+                        .arg(
+                            Arg::with_name("operator_address")
+                                .long("operator-address")
+                                .value_name("ADDRESS")
+                                .takes_value(true)
+                                .validator(is_valid_address)
+                                .help("Address of the operator balance which receives the cancel payments"),
+                        )
+                )
+        )
+        .subcommand(
+            SubCommand::with_name("stuck-transactions")
+                .about("Report iterative transactions idle longer than the threshold and optionally cancel them")
+                .arg(
+                    Arg::with_name("threshold")
+                        .long("threshold")
+                        .value_name("SECONDS")
+                        .takes_value(true)
+                        .default_value("600")
+                        .validator(is_amount::<u64, _>)
+                        .help("Idle time after which the transaction is stuck"),
+                )
+                .arg(
+                    Arg::with_name("cancel")
+                        .long("cancel")
+                        .takes_value(false)
+                        .help("Cancel stuck transactions (dry-run report otherwise)"),
+                )
+                .arg(
+                    Arg::with_name("operator_address")
+                        .long("operator-address")
+                        .value_name("ADDRESS")
+                        .takes_value(true)
+                        .validator(is_valid_address)
+                        .help("Address of the operator balance which receives the cancel payments"),
+                )
+        )
         .subcommand(
             SubCommand::with_name("init-environment")
                 .about("Initialize and verify environment for NeonEVM execution")
@@ -5,11 +5,15 @@
 pub mod types;
 
 use crate::types::RNeonEVMLibResult;
-use std::{collections::HashMap, path::Path};
+use std::{
+    collections::HashMap,
+    path::Path,
+    sync::atomic::{AtomicUsize, Ordering},
+};
 use thiserror::Error;
 
 use abi_stable::{
-    library::{LibraryError, RootModule},
+    library::{lib_header_from_raw_library, LibraryError, RawLibrary, RootModule},
     package_version_strings,
     std_types::{RStr, RString},
     StableAbi,
@@ -53,10 +57,37 @@ where
     let paths = std::fs::read_dir(directory)?;
     let mut result = HashMap::new();
     for path in paths {
-        let lib = NeonEVMLib_Ref::load_from_file(&path?.path())?;
-        let hash = lib.hash()();
+        let (hash, lib) = load_library(path?.path())?;
 
-        result.insert(hash.into_string(), lib);
+        result.insert(hash, lib);
     }
     Ok(result)
 }
+
+/// Loads a single library and returns it together with its revision hash.
+/// `RootModule::load_from_file` returns the module cached by the first load,
+/// and the dynamic loader reuses a library already opened by the same path,
+/// so every call maps a fresh copy of the file and initializes its root module directly.
+pub fn load_library<P>(path: P) -> Result<(String, NeonEVMLib_Ref), NeonEVMLibLoadError>
+where
+    P: AsRef<Path>,
+{
+    static COPIES: AtomicUsize = AtomicUsize::new(0);
+
+    let copy = std::env::temp_dir().join(format!(
+        "neon-lib-{}-{}.so",
+        std::process::id(),
+        COPIES.fetch_add(1, Ordering::Relaxed)
+    ));
+    std::fs::copy(path, &copy)?;
+    let raw_library = RawLibrary::load_at(&copy);
+    std::fs::remove_file(&copy)?;
+
+    // Library is never unloaded, the root module keeps references into it
+    let raw_library: &'static RawLibrary = Box::leak(Box::new(raw_library?));
+    let header = unsafe { lib_header_from_raw_library(raw_library) }?;
+    let lib = header.init_root_module::<NeonEVMLib_Ref>()?;
+    let hash = lib.hash()();
+
+    Ok((hash.into_string(), lib))
+}
@@ -7,6 +7,50 @@ pub type RNeonEVMLibResult<'a> = LocalBorrowingFfiFuture<'a, RResult<RString, RS
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct NeonEVMLibError {
     pub code: u32,
+    /// Libraries built before the field was added report every error as internal
+    #[serde(default)]
+    pub kind: ErrorKind,
     pub message: String,
     pub data: Option<serde_json::Value>,
 }
+
+/// Classification of library errors used by neon-api and neon-rpc to report them
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    /// Malformed request or parameters
+    BadRequest,
+    /// Requested account or program was not found
+    NotFound,
+    /// Solana RPC, `ClickHouse` or simulator failure
+    Upstream,
+    /// EVM execution reverted
+    Revert,
+    #[default]
+    Internal,
+}
+
+impl ErrorKind {
+    /// JSON-RPC error code. Reverts use code 3 as geth does for `eth_call`.
+    #[must_use]
+    pub const fn jsonrpc_code(self) -> i64 {
+        match self {
+            Self::BadRequest => -32602,
+            Self::NotFound => -32001,
+            Self::Upstream => -32002,
+            Self::Revert => 3,
+            Self::Internal => -32603,
+        }
+    }
+
+    #[must_use]
+    pub const fn http_status(self) -> u16 {
+        match self {
+            Self::BadRequest => 400,
+            Self::NotFound => 404,
+            Self::Revert => 422,
+            Self::Internal => 500,
+            Self::Upstream => 502,
+        }
+    }
+}
@@ -0,0 +1,17 @@
+use super::params_to_neon_error;
+use crate::commands::emulate_batch::{self, EmulateBatchResponse};
+use crate::commands::get_config::BuildConfigSimulator;
+use crate::config::APIOptions;
+use crate::rpc::Rpc;
+use crate::{types::EmulateBatchApiRequest, NeonResult};
+
+pub async fn execute(
+    rpc: &(impl Rpc + BuildConfigSimulator),
+    config: &APIOptions,
+    params: &str,
+) -> NeonResult<EmulateBatchResponse> {
+    let params: EmulateBatchApiRequest =
+        serde_json::from_str(params).map_err(|_| params_to_neon_error(params))?;
+
+    emulate_batch::execute(rpc, config.evm_loader, params.body).await
+}
@@ -0,0 +1,17 @@
+use super::params_to_neon_error;
+use crate::commands::estimate_gas::{self, EstimateGasResponse};
+use crate::commands::get_config::BuildConfigSimulator;
+use crate::config::APIOptions;
+use crate::rpc::Rpc;
+use crate::{types::EstimateGasApiRequest, NeonResult};
+
+pub async fn execute(
+    rpc: &(impl Rpc + BuildConfigSimulator),
+    config: &APIOptions,
+    params: &str,
+) -> NeonResult<EstimateGasResponse> {
+    let params: EstimateGasApiRequest =
+        serde_json::from_str(params).map_err(|_| params_to_neon_error(params))?;
+
+    estimate_gas::execute(rpc, config.evm_loader, params.body).await
+}
@@ -0,0 +1,13 @@
+use crate::abi::state::State;
+use crate::commands::health::{self, HealthResponse};
+use crate::NeonResult;
+
+pub async fn execute(state: &State) -> NeonResult<HealthResponse> {
+    health::execute(
+        &state.rpc_client,
+        &state.tracer_db,
+        state.config.evm_loader,
+        state.config.health_max_slot_lag,
+    )
+    .await
+}
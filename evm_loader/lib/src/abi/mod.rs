@@ -1,9 +1,12 @@
 mod emulate;
+mod emulate_batch;
+mod estimate_gas;
 mod get_balance;
 mod get_config;
 mod get_contract;
 mod get_holder;
 mod get_storage_at;
+mod health;
 mod simulate_solana;
 pub mod state;
 mod trace;
@@ -95,44 +98,56 @@ async fn dispatch(method_str: &str, params_str: &str) -> Result<String, NeonErro
     match method {
         LibMethod::Emulate => emulate::execute(&rpc, config, params_str)
             .await
-            .map(|v| serde_json::to_string(&v).unwrap()),
+            .and_then(to_json),
+        LibMethod::EmulateBatch => emulate_batch::execute(&rpc, config, params_str)
+            .await
+            .and_then(to_json),
+        LibMethod::EstimateGas => estimate_gas::execute(&rpc, config, params_str)
+            .await
+            .and_then(to_json),
         LibMethod::GetStorageAt => get_storage_at::execute(&rpc, config, params_str)
             .await
-            .map(|v| serde_json::to_string(&v).unwrap()),
+            .and_then(to_json),
         LibMethod::GetBalance => get_balance::execute(&rpc, config, params_str)
             .await
-            .map(|v| serde_json::to_string(&v).unwrap()),
+            .and_then(to_json),
         LibMethod::GetConfig => get_config::execute(&rpc, config, params_str)
             .await
-            .map(|v| serde_json::to_string(&v).unwrap()),
+            .and_then(to_json),
         LibMethod::GetContract => get_contract::execute(&rpc, config, params_str)
             .await
-            .map(|v| serde_json::to_string(&v).unwrap()),
+            .and_then(to_json),
         LibMethod::GetHolder => get_holder::execute(&rpc, config, params_str)
             .await
-            .map(|v| serde_json::to_string(&v).unwrap()),
+            .and_then(to_json),
         LibMethod::Trace => trace::execute(&rpc, config, params_str)
             .await
-            .map(|v| serde_json::to_string(&v).unwrap()),
+            .and_then(to_json),
         LibMethod::SimulateSolana => simulate_solana::execute(&rpc, config, params_str)
             .await
-            .map(|v| serde_json::to_string(&v).unwrap()),
+            .and_then(to_json),
+        LibMethod::Metrics => to_json(crate::metrics::gather_namespaced("lib")),
+        LibMethod::Health => health::execute(state).await.and_then(to_json),
         // _ => Err(NeonError::IncorrectLibMethod),
     }
 }
 
+#[allow(clippy::needless_pass_by_value)]
+fn to_json<T: serde::Serialize>(value: T) -> Result<String, NeonError> {
+    serde_json::to_string(&value).map_err(NeonError::from)
+}
+
 fn params_to_neon_error(params: &str) -> NeonError {
-    NeonError::EnvironmentError(
-        crate::commands::init_environment::EnvironmentError::InvalidProgramParameter(params.into()),
-    )
+    NeonError::InvalidParameters(params.into())
 }
 
 fn neon_error_to_neon_lib_error(error: &NeonError) -> NeonEVMLibError {
     assert!(error.error_code() != 0);
     NeonEVMLibError {
         code: error.error_code(),
+        kind: error.kind(),
         message: error.to_string(),
-        data: None,
+        data: error.data(),
     }
 }
 
@@ -37,9 +37,9 @@ use std::{
 };
 
 use crate::commands::get_config::{BuildConfigSimulator, ChainInfo};
-use crate::tracing::{AccountOverrides, BlockOverrides};
+use crate::tracing::{AccountOverride, AccountOverrides, BlockOverrides};
 
-const FAKE_OPERATOR: Pubkey = pubkey!("neonoperator1111111111111111111111111111111");
+pub(crate) const FAKE_OPERATOR: Pubkey = pubkey!("neonoperator1111111111111111111111111111111");
 
 #[derive(Default, Clone, Copy)]
 pub struct ExecuteStatus {
@@ -108,6 +108,10 @@ pub struct EmulatorAccountStorage<'rpc, T: Rpc> {
     chains: Vec<ChainInfo>,
     block_number: u64,
     block_timestamp: i64,
+    block_coinbase: Address,
+    block_gas_limit: U256,
+    block_base_fee: U256,
+    block_prevrandao: U256,
     timestamp_used: RefCell<bool>,
     rent: Rent,
     state_overrides: Option<AccountOverrides>,
@@ -128,6 +132,12 @@ impl<'rpc, T: Rpc + BuildConfigSimulator> EmulatorAccountStorage<'rpc, T> {
     ) -> Result<EmulatorAccountStorage<T>, NeonError> {
         trace!("backend::new");
 
+        if let Some(state_overrides) = state_overrides.as_ref() {
+            for (address, overrides) in state_overrides {
+                overrides.validate(*address)?;
+            }
+        }
+
         let block_number = match block_overrides.as_ref().and_then(|o| o.number) {
             None => rpc.get_slot().await?,
             Some(number) => number,
@@ -138,6 +148,23 @@ impl<'rpc, T: Rpc + BuildConfigSimulator> EmulatorAccountStorage<'rpc, T> {
             Some(time) => time,
         };
 
+        let block_coinbase = block_overrides
+            .as_ref()
+            .and_then(|o| o.coinbase)
+            .unwrap_or_default();
+        let block_gas_limit = block_overrides
+            .as_ref()
+            .and_then(|o| o.gas_limit)
+            .map_or(U256::MAX, U256::from);
+        let block_base_fee = block_overrides
+            .as_ref()
+            .and_then(|o| o.base_fee)
+            .unwrap_or_default();
+        let block_prevrandao = block_overrides
+            .as_ref()
+            .and_then(|o| o.random.or(o.difficulty))
+            .unwrap_or_default();
+
         let chains = match chains {
             None => crate::commands::get_config::read_chains(rpc, program_id).await?,
             Some(chains) => chains,
@@ -157,7 +184,7 @@ impl<'rpc, T: Rpc + BuildConfigSimulator> EmulatorAccountStorage<'rpc, T> {
                 accounts_cache.insert(pubkey, Box::new(account));
             }
         }
-        let storage = Self {
+        let mut storage = Self {
             accounts: FrozenMap::new(),
             call_stack: vec![],
             program_id,
@@ -169,6 +196,10 @@ impl<'rpc, T: Rpc + BuildConfigSimulator> EmulatorAccountStorage<'rpc, T> {
             rpc,
             block_number,
             block_timestamp,
+            block_coinbase,
+            block_gas_limit,
+            block_base_fee,
+            block_prevrandao,
             timestamp_used: RefCell::new(false),
             state_overrides,
             rent,
@@ -178,6 +209,10 @@ impl<'rpc, T: Rpc + BuildConfigSimulator> EmulatorAccountStorage<'rpc, T> {
         };
 
         let target_chain_id = tx_chain_id.unwrap_or_else(|| storage.default_chain_id());
+        // Storage overrides are committed to the accounts cache: transactions see them as
+        // the on-chain state, so the overridden accounts are neither writable nor charged with rent
+        storage.apply_storage_overrides(target_chain_id).await?;
+        storage.commit_transaction();
         storage.apply_balance_overrides(target_chain_id).await?;
 
         Ok(storage)
@@ -201,6 +236,10 @@ impl<'rpc, T: Rpc + BuildConfigSimulator> EmulatorAccountStorage<'rpc, T> {
             rpc: other.rpc,
             block_number: other.block_number.saturating_add(block_shift),
             block_timestamp: other.block_timestamp.saturating_add(timestamp_shift),
+            block_coinbase: other.block_coinbase,
+            block_gas_limit: other.block_gas_limit,
+            block_base_fee: other.block_base_fee,
+            block_prevrandao: other.block_prevrandao,
             timestamp_used: RefCell::new(false),
             rent: other.rent,
             state_overrides: other.state_overrides.clone(),
@@ -208,6 +247,7 @@ impl<'rpc, T: Rpc + BuildConfigSimulator> EmulatorAccountStorage<'rpc, T> {
             used_accounts: other.used_accounts.clone(),
             return_data: RefCell::new(None),
         };
+        // Storage overrides are already in the accounts cache of `other`
         let target_chain_id = tx_chain_id.unwrap_or_else(|| storage.default_chain_id());
         storage.apply_balance_overrides(target_chain_id).await?;
         Ok(storage)
@@ -270,6 +310,48 @@ impl<'a, T: Rpc> EmulatorAccountStorage<'_, T> {
         Ok(())
     }
 
+    async fn apply_storage_overrides(&self, target_chain_id: u64) -> NeonResult<()> {
+        if let Some(state_overrides) = self.state_overrides.as_ref() {
+            for (address, overrides) in state_overrides {
+                if overrides.replaces_storage() {
+                    info!("apply state overrides {address}: clear storage");
+                    self.clear_static_storage(*address).await?;
+                }
+                for (index, value) in overrides.storage_slots() {
+                    info!(
+                        "apply storage overrides {address} -> {index} = {}",
+                        hex::encode(value)
+                    );
+                    self.write_storage(*address, target_chain_id, index, value)
+                        .await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Zeroes storage values kept in the contract account.
+    /// Storage cells are ignored by `get_storage_account`.
+    async fn clear_static_storage(&self, address: Address) -> NeonResult<()> {
+        let mut contract_data = self.get_contract_account(address).await?.borrow_mut();
+        if contract_data.is_empty() {
+            return Ok(());
+        }
+
+        let mut contract =
+            ContractAccount::from_account(self.program_id(), contract_data.into_account_info())?;
+        contract.set_storage_multiple_values(0, &[[0_u8; 32]; STORAGE_ENTRIES_IN_CONTRACT_ACCOUNT]);
+
+        Ok(())
+    }
+
+    fn is_storage_replaced(&self, address: Address) -> bool {
+        self.state_overrides
+            .as_ref()
+            .and_then(|overrides| overrides.get(&address))
+            .is_some_and(AccountOverride::replaces_storage)
+    }
+
     async fn download_accounts(&self, pubkeys: &[Pubkey]) -> Result<(), NeonError> {
         let accounts = self.rpc.get_multiple_accounts(pubkeys).await?;
 
@@ -650,6 +732,12 @@ impl<'a, T: Rpc> EmulatorAccountStorage<'_, T> {
             return Ok(account);
         }
 
+        if self.is_storage_replaced(address) && self.accounts_cache.get(&cell_pubkey).is_none() {
+            // `state` override: on-chain storage cells don't exist for the emulation.
+            // Cached cells are written by the override or by previous transactions of the batch.
+            return Ok(self.add_empty_account(cell_pubkey));
+        }
+
         match self._get_account_from_rpc(cell_pubkey).await? {
             Some(account) => self.add_account(cell_pubkey, account).await,
             None => Ok(self.add_empty_account(cell_pubkey)),
@@ -797,6 +885,41 @@ impl<'a, T: Rpc> EmulatorAccountStorage<'_, T> {
         }
     }
 
+    async fn write_storage(
+        &self,
+        address: Address,
+        chain_id: u64,
+        index: U256,
+        value: [u8; 32],
+    ) -> NeonResult<()> {
+        const STATIC_STORAGE_LIMIT: U256 = U256::new(STORAGE_ENTRIES_IN_CONTRACT_ACCOUNT as u128);
+
+        if index < STATIC_STORAGE_LIMIT {
+            let mut contract_data = self.get_contract_account(address).await?.borrow_mut();
+
+            let mut contract = if contract_data.is_empty() {
+                self.create_ethereum_contract(&mut contract_data, address, chain_id, 0, &[])?
+            } else {
+                ContractAccount::from_account(self.program_id(), contract_data.into_account_info())?
+            };
+            contract.set_storage_value(index.as_usize(), &value);
+            contract.update_lamports(&self.rent);
+            self.mark_account(contract_data.pubkey, true);
+        } else {
+            let subindex = (index & 0xFF).as_u8();
+            let index = index & !U256::new(0xFF);
+
+            let mut storage_data = self.get_storage_account(address, index).await?.borrow_mut();
+
+            let mut storage = self.get_or_create_ethereum_storage(&mut storage_data)?;
+            storage.update(subindex, &value)?;
+            storage.update_lamports(&self.rent);
+            self.mark_account(storage_data.pubkey, true);
+        }
+
+        Ok(())
+    }
+
     async fn mint(
         &mut self,
         address: Address,
@@ -854,9 +977,10 @@ impl<'a, T: Rpc> EmulatorAccountStorage<'_, T> {
         Ok(lamports_spend.saturating_sub(lamports_collected))
     }
 
-    pub fn get_regular_rent(&self) -> evm_loader::error::Result<u64> {
+    /// Rent paid for new and resized accounts, per account
+    pub fn get_regular_rent_per_account(&self) -> evm_loader::error::Result<Vec<(Pubkey, u64)>> {
         let accounts = self.accounts.clone();
-        let mut changes_in_rent = 0u64;
+        let mut changes_in_rent = Vec::new();
         for (pubkey, account) in &accounts.into_map() {
             if *pubkey == system_program::ID {
                 continue;
@@ -881,17 +1005,26 @@ impl<'a, T: Rpc> EmulatorAccountStorage<'_, T> {
                 return Err(ProgramError::AccountNotRentExempt.into());
             }
 
-            if let Some(lamports_after_upgrade) = lamports_after_upgrade {
-                changes_in_rent += new_lamports.saturating_sub(lamports_after_upgrade);
+            let rent = if let Some(lamports_after_upgrade) = lamports_after_upgrade {
                 info!("Changes in rent: {pubkey} {original_lamports} -> {lamports_after_upgrade} -> {new_lamports} | {original_size} -> {new_size}");
+                new_lamports.saturating_sub(lamports_after_upgrade)
             } else {
-                changes_in_rent += new_lamports.saturating_sub(original_lamports);
                 info!("Changes in rent: {pubkey} {original_lamports} -> {new_lamports} | {original_size} -> {new_size}");
+                new_lamports.saturating_sub(original_lamports)
+            };
+
+            if rent > 0 {
+                changes_in_rent.push((*pubkey, rent));
             }
         }
         Ok(changes_in_rent)
     }
 
+    pub fn get_regular_rent(&self) -> evm_loader::error::Result<u64> {
+        let changes_in_rent = self.get_regular_rent_per_account()?;
+        Ok(changes_in_rent.iter().map(|(_, rent)| rent).sum())
+    }
+
     pub fn get_changes_in_rent(&self) -> evm_loader::error::Result<u64> {
         Ok(self.get_upgrade_rent()? + self.get_regular_rent()?)
     }
@@ -899,6 +1032,27 @@ impl<'a, T: Rpc> EmulatorAccountStorage<'_, T> {
     pub fn is_timestamp_used(&self) -> bool {
         *self.timestamp_used.borrow()
     }
+
+    /// Makes state changes of the emulated transaction the initial state for the next one.
+    /// Used accounts, rent and iterations are counted from scratch after that.
+    pub fn commit_transaction(&mut self) {
+        let accounts = std::mem::take(&mut self.accounts).into_map();
+        let accounts_cache = self.accounts_cache.as_mut();
+        for (pubkey, account) in accounts {
+            let account = RefCell::into_inner(*account);
+            let account =
+                (account.lamports > 0 || account.is_busy()).then(|| Account::from(&account));
+            accounts_cache.insert(pubkey, Box::new(account));
+        }
+
+        self.call_stack.clear();
+        self.used_accounts = FrozenMap::new();
+        self.gas = 0;
+        self.realloc_iterations = 0;
+        self.execute_status = ExecuteStatus::default();
+        *self.timestamp_used.borrow_mut() = false;
+        *self.return_data.borrow_mut() = None;
+    }
 }
 
 #[async_trait(?Send)]
@@ -924,6 +1078,26 @@ impl<T: Rpc> AccountStorage for EmulatorAccountStorage<'_, T> {
         self.block_timestamp.try_into().unwrap()
     }
 
+    fn block_coinbase(&self) -> Address {
+        info!("block_coinbase");
+        self.block_coinbase
+    }
+
+    fn block_gas_limit(&self) -> U256 {
+        info!("block_gas_limit");
+        self.block_gas_limit
+    }
+
+    fn block_base_fee(&self) -> U256 {
+        info!("block_base_fee");
+        self.block_base_fee
+    }
+
+    fn block_prevrandao(&self) -> U256 {
+        info!("block_prevrandao");
+        self.block_prevrandao
+    }
+
     fn rent(&self) -> &Rent {
         &self.rent
     }
@@ -1067,12 +1241,6 @@ impl<T: Rpc> AccountStorage for EmulatorAccountStorage<'_, T> {
     }
 
     async fn storage(&self, address: Address, index: U256) -> [u8; 32] {
-        // TODO: move to reading data from Solana node
-        // let storage_override = self.account_override(address, |a| a.storage(index));
-        // if let Some(storage_override) = storage_override {
-        //     return storage_override;
-        // }
-
         let value = if index < U256::from(STORAGE_ENTRIES_IN_CONTRACT_ACCOUNT as u64) {
             let index: usize = index.as_usize();
             self.ethereum_contract_map_or(address, [0_u8; 32], |c| c.storage_value(index))
@@ -1193,40 +1361,10 @@ impl<T: Rpc> SyncedAccountStorage for EmulatorAccountStorage<'_, T> {
         value: [u8; 32],
     ) -> evm_loader::error::Result<()> {
         info!("set_storage {address} -> {index} = {}", hex::encode(value));
-        const STATIC_STORAGE_LIMIT: U256 = U256::new(STORAGE_ENTRIES_IN_CONTRACT_ACCOUNT as u128);
-
-        if index < STATIC_STORAGE_LIMIT {
-            let mut contract_data = self
-                .get_contract_account(address)
-                .await
-                .map_err(map_neon_error)?
-                .borrow_mut();
-
-            let mut contract = if contract_data.is_empty() {
-                self.create_ethereum_contract(&mut contract_data, address, 0, 0, &[])?
-            } else {
-                ContractAccount::from_account(self.program_id(), contract_data.into_account_info())?
-            };
-            contract.set_storage_value(index.as_usize(), &value);
-            contract.update_lamports(&self.rent);
-            self.mark_account(contract_data.pubkey, true);
-        } else {
-            let subindex = (index & 0xFF).as_u8();
-            let index = index & !U256::new(0xFF);
-
-            let mut storage_data = self
-                .get_storage_account(address, index)
-                .await
-                .map_err(map_neon_error)?
-                .borrow_mut();
-
-            let mut storage = self.get_or_create_ethereum_storage(&mut storage_data)?;
-            storage.update(subindex, &value)?;
-            storage.update_lamports(&self.rent);
-            self.mark_account(storage_data.pubkey, true);
-        }
 
-        Ok(())
+        self.write_storage(address, 0, index, value)
+            .await
+            .map_err(map_neon_error)
     }
 
     async fn increment_nonce(
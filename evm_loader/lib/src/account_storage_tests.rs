@@ -1,7 +1,11 @@
 use super::*;
 use crate::rpc;
 use crate::tracing::AccountOverride;
+use evm_loader::evm::{database::Database, Context};
+use evm_loader::executor::ExecutorState;
 use hex_literal::hex;
+use mpl_token_metadata::accounts::{MasterEdition, Metadata};
+use solana_sdk::{program_option::COption, program_pack::Pack};
 use std::collections::HashMap;
 use std::str::FromStr;
 
@@ -453,6 +457,9 @@ impl ActualContract {
 const LEGACY_CHAIN_ID: u64 = 1;
 const EXTRA_CHAIN_ID: u64 = 2;
 const MISSING_ADDRESS: Address = Address(hex!("7a250d5630b4cf539739df2c5dacb4c659f24800"));
+const METAPLEX_ADDRESS: Address = Address(hex!("ff00000000000000000000000000000000000005"));
+const NFT_OWNER: Address = Address(hex!("7a250d5630b4cf539739df2c5dacb4c659f24e10"));
+const NFT_MINT: Pubkey = Pubkey::new_from_array([0x4e; 32]);
 
 const MISSING_STORAGE_INDEX: U256 = U256::new(256u128);
 const ACTUAL_STORAGE_INDEX: U256 = U256::new(2 * 256u128);
@@ -582,6 +589,31 @@ const LEGACY_SUICIDE: LegacyContract = LegacyContract {
     },
 };
 
+fn nft_mint_with_pubkey(program_id: &Pubkey, rent: &Rent) -> (Pubkey, Account) {
+    let (authority, _) = NFT_OWNER.find_solana_address(program_id);
+    let mint = spl_token::state::Mint {
+        mint_authority: COption::Some(authority),
+        supply: 1,
+        decimals: 0,
+        is_initialized: true,
+        freeze_authority: COption::None,
+    };
+
+    let mut data = vec![0u8; spl_token::state::Mint::LEN];
+    mint.pack_into_slice(&mut data);
+
+    (
+        NFT_MINT,
+        Account {
+            lamports: rent.minimum_balance(data.len()),
+            data,
+            owner: spl_token::id(),
+            executable: false,
+            rent_epoch: 0,
+        },
+    )
+}
+
 struct Fixture {
     program_id: Pubkey,
     chains: Vec<ChainInfo>,
@@ -622,6 +654,7 @@ impl Fixture {
             LEGACY_CONTRACT_NO_BALANCE.account_with_pubkey(&program_id, &rent),
             LEGACY_SUICIDE.account_with_pubkey(&program_id, &rent),
             LEGACY_SUICIDE.outdate_storage_with_pubkey(&program_id, &rent),
+            nft_mint_with_pubkey(&program_id, &rent),
         ];
 
         let rpc_client = mock_rpc_client::MockRpcClient::new(&accounts);
@@ -757,6 +790,13 @@ impl<'rpc, T: Rpc> EmulatorAccountStorage<'rpc, T> {
             self.get_regular_rent().unwrap(),
             added_rent.saturating_sub(removed_rent)
         );
+
+        let per_account = self.get_regular_rent_per_account().unwrap();
+        assert!(per_account.iter().all(|(_, rent)| *rent > 0));
+        assert_eq!(
+            per_account.iter().map(|(_, rent)| rent).sum::<u64>(),
+            added_rent.saturating_sub(removed_rent)
+        );
     }
 }
 
@@ -1777,3 +1817,414 @@ async fn test_storage_new_from_other_and_override() {
         expected_balance
     );
 }
+
+#[tokio::test]
+async fn test_block_overrides() {
+    let mut fixture = Fixture::new();
+
+    {
+        let storage = fixture.build_account_storage().await;
+        assert_eq!(storage.block_coinbase(), Address::default());
+        assert_eq!(storage.block_gas_limit(), U256::MAX);
+        assert_eq!(storage.block_base_fee(), U256::ZERO);
+        assert_eq!(storage.block_prevrandao(), U256::ZERO);
+    }
+
+    let coinbase = Address::from_hex("0x0673ac30e9c5dd7955ae9fb7e46b3cddca435883").unwrap();
+    fixture.block_overrides = Some(BlockOverrides {
+        number: Some(100),
+        difficulty: Some(U256::from(2_u64)),
+        time: Some(1_700_000_000),
+        gas_limit: Some(30_000_000),
+        coinbase: Some(coinbase),
+        random: Some(U256::from(7_u64)),
+        base_fee: Some(U256::from(1_000_000_000_u64)),
+    });
+
+    let storage = fixture.build_account_storage().await;
+    assert_eq!(storage.block_number(), U256::from(100_u64));
+    assert_eq!(storage.block_coinbase(), coinbase);
+    assert_eq!(storage.block_gas_limit(), U256::from(30_000_000_u64));
+    assert_eq!(storage.block_base_fee(), U256::from(1_000_000_000_u64));
+    // `random` takes precedence over `difficulty`
+    assert_eq!(storage.block_prevrandao(), U256::from(7_u64));
+
+    let other = EmulatorAccountStorage::new_from_other(&storage, 0, 0, None)
+        .await
+        .unwrap();
+    assert_eq!(other.block_coinbase(), coinbase);
+    assert_eq!(other.block_prevrandao(), U256::from(7_u64));
+}
+
+fn storage_override(slots: &[(U256, [u8; 32])]) -> HashMap<web3::types::H256, web3::types::H256> {
+    slots
+        .iter()
+        .map(|(index, value)| {
+            (
+                web3::types::H256::from(index.to_be_bytes()),
+                web3::types::H256::from(*value),
+            )
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_state_diff_overrides_patch_storage() {
+    let contract = &ACTUAL_CONTRACT;
+    let outer_index = ACTUAL_STORAGE_INDEX + 1;
+
+    let mut fixture = Fixture::new();
+    fixture.state_overrides = Some(AccountOverrides::from([(
+        contract.address,
+        AccountOverride {
+            state_diff: Some(storage_override(&[
+                (U256::ONE, [1u8; 32]),
+                (outer_index, [2u8; 32]),
+            ])),
+            ..Default::default()
+        },
+    )]));
+    let storage = fixture.build_account_storage().await;
+
+    assert_eq!(
+        storage.storage(contract.address, U256::ONE).await,
+        [1u8; 32]
+    );
+    assert_eq!(
+        storage.storage(contract.address, outer_index).await,
+        [2u8; 32]
+    );
+
+    // Other slots keep on-chain values
+    assert_eq!(
+        storage.storage(contract.address, U256::ZERO).await,
+        contract.storage[0]
+    );
+    assert_eq!(
+        storage
+            .storage(contract.address, ACTUAL_STORAGE_INDEX)
+            .await,
+        contract.actual_storage.values[0].1
+    );
+    assert_eq!(*storage.code(contract.address).await, *contract.code);
+}
+
+#[tokio::test]
+async fn test_state_overrides_replace_storage() {
+    let contract = &ACTUAL_CONTRACT;
+    let outer_index = ACTUAL_STORAGE_INDEX + 1;
+
+    let mut fixture = Fixture::new();
+    fixture.state_overrides = Some(AccountOverrides::from([(
+        contract.address,
+        AccountOverride {
+            state: Some(storage_override(&[
+                (U256::ONE, [1u8; 32]),
+                (outer_index, [2u8; 32]),
+            ])),
+            ..Default::default()
+        },
+    )]));
+    let storage = fixture.build_account_storage().await;
+
+    assert_eq!(
+        storage.storage(contract.address, U256::ONE).await,
+        [1u8; 32]
+    );
+    assert_eq!(
+        storage.storage(contract.address, outer_index).await,
+        [2u8; 32]
+    );
+
+    // All other slots are cleared
+    assert_eq!(
+        storage.storage(contract.address, U256::ZERO).await,
+        [0u8; 32]
+    );
+    assert_eq!(
+        storage
+            .storage(contract.address, ACTUAL_STORAGE_INDEX)
+            .await,
+        [0u8; 32]
+    );
+    assert_eq!(*storage.code(contract.address).await, *contract.code);
+
+    // Overrides are applied again to the storage copy
+    let other = EmulatorAccountStorage::new_from_other(&storage, 0, 0, None)
+        .await
+        .unwrap();
+    assert_eq!(other.storage(contract.address, U256::ONE).await, [1u8; 32]);
+    assert_eq!(other.storage(contract.address, U256::ZERO).await, [0u8; 32]);
+}
+
+#[tokio::test]
+async fn test_state_overrides_in_batch() {
+    let contract = &ACTUAL_CONTRACT;
+    let outer_index = ACTUAL_STORAGE_INDEX + 1;
+
+    let mut fixture = Fixture::new();
+    fixture.state_overrides = Some(AccountOverrides::from([(
+        contract.address,
+        AccountOverride {
+            state: Some(storage_override(&[
+                (U256::ONE, [1u8; 32]),
+                (outer_index, [2u8; 32]),
+            ])),
+            ..Default::default()
+        },
+    )]));
+    let mut storage = fixture.build_account_storage().await;
+
+    // Overrides are the on-chain state of the emulation
+    storage.verify_used_accounts(&[]);
+    storage.verify_regular_rent(0, 0);
+
+    storage
+        .set_storage(contract.address, outer_index + 1, [3u8; 32])
+        .await
+        .unwrap();
+    storage
+        .set_storage(contract.address, U256::ZERO, [4u8; 32])
+        .await
+        .unwrap();
+    // Only the growth of the overridden cell is charged
+    storage.verify_regular_rent(fixture.storage_rent(2), fixture.storage_rent(1));
+
+    storage.commit_transaction();
+
+    // The next transaction of the batch sees both the overrides and the changes
+    assert_eq!(
+        storage.storage(contract.address, U256::ONE).await,
+        [1u8; 32]
+    );
+    assert_eq!(
+        storage.storage(contract.address, U256::ZERO).await,
+        [4u8; 32]
+    );
+    assert_eq!(
+        storage.storage(contract.address, outer_index).await,
+        [2u8; 32]
+    );
+    assert_eq!(
+        storage.storage(contract.address, outer_index + 1).await,
+        [3u8; 32]
+    );
+    assert_eq!(
+        storage
+            .storage(contract.address, ACTUAL_STORAGE_INDEX)
+            .await,
+        [0u8; 32]
+    );
+}
+
+#[tokio::test]
+async fn test_conflicting_state_overrides() {
+    let fixture = Fixture::new();
+    let overrides = AccountOverrides::from([(
+        ACTUAL_CONTRACT.address,
+        AccountOverride {
+            state: Some(storage_override(&[(U256::ONE, [1u8; 32])])),
+            state_diff: Some(storage_override(&[(U256::ZERO, [2u8; 32])])),
+            ..Default::default()
+        },
+    )]);
+
+    let result = EmulatorAccountStorage::new(
+        &fixture.mock_rpc,
+        fixture.program_id,
+        Some(fixture.chains.clone()),
+        None,
+        Some(overrides),
+        None,
+        None,
+    )
+    .await;
+
+    assert!(matches!(
+        result,
+        Err(NeonError::ConflictingStateOverrides(address)) if address == ACTUAL_CONTRACT.address
+    ));
+}
+
+#[tokio::test]
+async fn test_commit_transaction_keeps_state() {
+    let fixture = Fixture::new();
+    let mut storage = fixture.build_account_storage().await;
+
+    let from = &ACTUAL_BALANCE;
+    let contract = &ACTUAL_CONTRACT;
+    let amount = U256::new(10);
+    storage
+        .transfer(from.address, MISSING_ADDRESS, from.chain_id, amount)
+        .await
+        .unwrap();
+    storage
+        .set_storage(contract.address, U256::ZERO, [0x05u8; 32])
+        .await
+        .unwrap();
+    storage
+        .set_storage(contract.address, MISSING_STORAGE_INDEX, [0x06u8; 32])
+        .await
+        .unwrap();
+
+    storage.commit_transaction();
+    storage.verify_used_accounts(&[]);
+
+    // The next transaction of the batch starts from the committed state
+    assert_eq!(
+        storage.balance(from.address, from.chain_id).await,
+        from.balance - amount
+    );
+    assert_eq!(
+        storage.balance(MISSING_ADDRESS, LEGACY_CHAIN_ID).await,
+        amount
+    );
+    assert_eq!(
+        storage.storage(contract.address, U256::ZERO).await,
+        [0x05u8; 32]
+    );
+    assert_eq!(
+        storage
+            .storage(contract.address, MISSING_STORAGE_INDEX)
+            .await,
+        [0x06u8; 32]
+    );
+
+    // Rent of the accounts created by the previous transaction is not charged again
+    storage.verify_regular_rent(0, 0);
+}
+
+fn metaplex_input(selector: [u8; 4], mint: &Pubkey, strings: &[&str]) -> Vec<u8> {
+    let head_len = 32 * (1 + strings.len());
+
+    let mut input = selector.to_vec();
+    input.extend_from_slice(mint.as_ref());
+
+    let mut tail = Vec::new();
+    for s in strings {
+        let offset = U256::new((head_len + tail.len()) as u128);
+        input.extend_from_slice(&offset.to_be_bytes());
+
+        tail.extend_from_slice(&U256::new(s.len() as u128).to_be_bytes());
+        tail.extend_from_slice(s.as_bytes());
+        tail.resize(tail.len() + (32 - s.len() % 32) % 32, 0);
+    }
+
+    input.extend_from_slice(&tail);
+    input
+}
+
+fn decode_string(output: &[u8]) -> String {
+    let length = U256::from_be_bytes(*arrayref::array_ref![output, 32, 32]).as_usize();
+    String::from_utf8(output[64..64 + length].to_vec()).unwrap()
+}
+
+async fn call_metaplex<B: AccountStorage>(
+    state: &mut ExecutorState<'_, B>,
+    input: &[u8],
+    is_static: bool,
+) -> evm_loader::error::Result<Vec<u8>> {
+    let context = Context {
+        caller: NFT_OWNER,
+        contract: METAPLEX_ADDRESS,
+        contract_chain_id: LEGACY_CHAIN_ID,
+        value: U256::ZERO,
+        code_address: Some(METAPLEX_ADDRESS),
+    };
+
+    state
+        .precompile_extension(&context, &METAPLEX_ADDRESS, input, is_static)
+        .await
+        .expect("Metaplex is not a precompile extension")
+}
+
+async fn create_metadata<B: AccountStorage>(state: &mut ExecutorState<'_, B>) -> Vec<u8> {
+    let input = metaplex_input(
+        [0xc5, 0x73, 0x50, 0xc6],
+        &NFT_MINT,
+        &["Neon NFT", "NNFT", "https://neonevm.org/nft.json"],
+    );
+    call_metaplex(state, &input, false).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_metaplex_create_metadata() {
+    let fixture = Fixture::new();
+    let storage = fixture.build_account_storage().await;
+    let mut state = ExecutorState::new(&storage);
+
+    let is_initialized = metaplex_input([0xf7, 0xb6, 0x37, 0xbb], &NFT_MINT, &[]);
+    let result = call_metaplex(&mut state, &is_initialized, true).await;
+    assert_eq!(result.unwrap()[31], 0);
+
+    let metadata = create_metadata(&mut state).await;
+    assert_eq!(metadata, Metadata::find_pda(&NFT_MINT).0.to_bytes());
+
+    let result = call_metaplex(&mut state, &is_initialized, true).await;
+    assert_eq!(result.unwrap()[31], 1);
+
+    let name = metaplex_input([0x69, 0x1f, 0x34, 0x31], &NFT_MINT, &[]);
+    let result = call_metaplex(&mut state, &name, true).await.unwrap();
+    assert_eq!(decode_string(&result), "Neon NFT");
+
+    let symbol = metaplex_input([0x6b, 0xaa, 0x03, 0x30], &NFT_MINT, &[]);
+    let result = call_metaplex(&mut state, &symbol, true).await.unwrap();
+    assert_eq!(decode_string(&result), "NNFT");
+
+    let uri = metaplex_input([0x9e, 0xd1, 0x9d, 0xdb], &NFT_MINT, &[]);
+    let result = call_metaplex(&mut state, &uri, true).await.unwrap();
+    assert_eq!(decode_string(&result), "https://neonevm.org/nft.json");
+
+    // Metadata without the master edition is a fungible asset
+    let is_nft = metaplex_input([0x23, 0x5b, 0x2b, 0x94], &NFT_MINT, &[]);
+    let result = call_metaplex(&mut state, &is_nft, true).await;
+    assert_eq!(result.unwrap()[31], 0);
+}
+
+#[tokio::test]
+async fn test_metaplex_create_master_edition() {
+    let fixture = Fixture::new();
+    let storage = fixture.build_account_storage().await;
+    let mut state = ExecutorState::new(&storage);
+
+    create_metadata(&mut state).await;
+
+    let mut input = metaplex_input([0x4a, 0xe8, 0xb6, 0x6b], &NFT_MINT, &[]);
+    input.extend_from_slice(&U256::ZERO.to_be_bytes());
+    let edition = call_metaplex(&mut state, &input, false).await.unwrap();
+
+    let (edition_pubkey, _) = MasterEdition::find_pda(&NFT_MINT);
+    assert_eq!(edition, edition_pubkey.to_bytes());
+
+    let is_nft = metaplex_input([0x23, 0x5b, 0x2b, 0x94], &NFT_MINT, &[]);
+    let result = call_metaplex(&mut state, &is_nft, true).await;
+    assert_eq!(result.unwrap()[31], 1);
+
+    // Master edition takes over the mint authority
+    let mint = state.external_account(NFT_MINT).await.unwrap();
+    let mint = spl_token::state::Mint::unpack(&mint.data).unwrap();
+    assert_eq!(mint.mint_authority, COption::Some(edition_pubkey));
+}
+
+#[tokio::test]
+async fn test_metaplex_static_call() {
+    let fixture = Fixture::new();
+    let storage = fixture.build_account_storage().await;
+    let mut state = ExecutorState::new(&storage);
+
+    let input = metaplex_input([0xc5, 0x73, 0x50, 0xc6], &NFT_MINT, &["a", "b", "c"]);
+    let result = call_metaplex(&mut state, &input, true).await;
+    assert!(matches!(
+        result,
+        Err(evm_loader::error::Error::StaticModeViolation(address)) if address == METAPLEX_ADDRESS
+    ));
+
+    let unknown = metaplex_input([0xde, 0xad, 0xbe, 0xef], &NFT_MINT, &[]);
+    let result = call_metaplex(&mut state, &unknown, true).await;
+    assert!(matches!(
+        result,
+        Err(evm_loader::error::Error::UnknownPrecompileMethodSelector(
+            ..
+        ))
+    ));
+}
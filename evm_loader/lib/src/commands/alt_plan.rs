@@ -0,0 +1,237 @@
+use evm_loader::gasometer::{ACCOUNTS_PER_ALT_EXTEND, MIN_ACCOUNTS_TO_USE_ALT};
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, DisplayFromStr};
+use solana_sdk::{packet::PACKET_DATA_SIZE, pubkey::Pubkey, system_program};
+
+use crate::commands::emulate::SolanaAccount;
+
+/// Signer of the step transaction: operator
+const SIGNERS: usize = 1;
+/// Accounts which can't be loaded from a lookup table: operator, `evm_loader` and compute budget programs
+const STATIC_ACCOUNTS: usize = 3;
+/// Holder, treasury and operator balance accounts of the step instruction
+const OPERATOR_WRITABLE_ACCOUNTS: usize = 3;
+/// System program and operator record accounts of the step instruction
+const OPERATOR_READONLY_ACCOUNTS: usize = 2;
+/// Tag, treasury index, step count and unique index
+const STEP_INSTRUCTION_HEADER: usize = 1 + 4 + 4 + 4;
+/// `SetComputeUnitLimit` and `RequestHeapFrame` instructions: program index, no accounts, 5 bytes of data
+const COMPUTE_BUDGET_INSTRUCTIONS_SIZE: usize = 2 * (1 + 1 + 1 + 5);
+
+/// How the proxy should pack the step transaction of the emulated transaction.
+/// Holder, treasury, operator balance, system program and operator record accounts are not listed in
+/// `accounts`, but are counted in the sizes and should be added to the table by the proxy.
+#[serde_as]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct AltPlan {
+    /// Address lookup table is required
+    pub use_alt: bool,
+    /// Accounts of the emulated transaction to put into the lookup table
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    pub accounts: Vec<Pubkey>,
+    /// Number of `ExtendLookupTable` instructions
+    pub extend_instructions: usize,
+    /// Length of the signed transaction RLP
+    pub transaction_len: usize,
+    /// The transaction doesn't fit into the instruction data and should be written into a holder
+    pub use_holder: bool,
+    pub legacy_transaction_size: usize,
+    pub v0_transaction_size: usize,
+    /// The step transaction fits `PACKET_DATA_SIZE` bytes
+    pub fits_packet: bool,
+}
+
+const fn compact_len(len: usize) -> usize {
+    if len < 0x80 {
+        1
+    } else if len < 0x4000 {
+        2
+    } else {
+        3
+    }
+}
+
+const fn signatures_size() -> usize {
+    compact_len(SIGNERS) + SIGNERS * 64
+}
+
+const fn step_instruction_size(accounts: usize, data_len: usize) -> usize {
+    1 + compact_len(accounts) + accounts + compact_len(data_len) + data_len
+}
+
+const fn instructions_size(accounts: usize, data_len: usize) -> usize {
+    compact_len(3) + COMPUTE_BUDGET_INSTRUCTIONS_SIZE + step_instruction_size(accounts, data_len)
+}
+
+fn legacy_transaction_size(instruction_accounts: usize, data_len: usize) -> usize {
+    let keys = STATIC_ACCOUNTS + instruction_accounts - 1;
+
+    signatures_size()
+        + 3 // header
+        + compact_len(keys)
+        + keys * 32
+        + 32 // recent blockhash
+        + instructions_size(instruction_accounts, data_len)
+}
+
+fn v0_transaction_size(
+    instruction_accounts: usize,
+    writable: usize,
+    readonly: usize,
+    data_len: usize,
+) -> usize {
+    signatures_size()
+        + 1 // version prefix
+        + 3 // header
+        + compact_len(STATIC_ACCOUNTS)
+        + STATIC_ACCOUNTS * 32
+        + 32 // recent blockhash
+        + instructions_size(instruction_accounts, data_len)
+        + compact_len(1)
+        + 32 // lookup table address
+        + compact_len(writable)
+        + writable
+        + compact_len(readonly)
+        + readonly
+}
+
+/// Accounts of the emulated transaction which are not fixed accounts of the step instruction
+fn transaction_accounts(solana_accounts: &[SolanaAccount]) -> impl Iterator<Item = &SolanaAccount> {
+    solana_accounts
+        .iter()
+        .filter(|a| a.pubkey != system_program::ID)
+}
+
+/// Number of accounts of the step instruction: operator, holder, treasury, operator balance,
+/// system program, operator record and the accounts of the emulated transaction
+#[must_use]
+pub fn step_accounts_count(solana_accounts: &[SolanaAccount]) -> usize {
+    1 + OPERATOR_WRITABLE_ACCOUNTS
+        + OPERATOR_READONLY_ACCOUNTS
+        + transaction_accounts(solana_accounts).count()
+}
+
+/// Builds the plan of the step transaction for accounts used by the emulated transaction
+/// and the length of its signed RLP
+#[must_use]
+pub fn build(solana_accounts: &[SolanaAccount], transaction_len: usize) -> AltPlan {
+    let accounts = transaction_accounts(solana_accounts).collect::<Vec<_>>();
+
+    // Operator is a signer and is not loaded from the table
+    let writable = OPERATOR_WRITABLE_ACCOUNTS + accounts.iter().filter(|a| a.is_writable).count();
+    let readonly = OPERATOR_READONLY_ACCOUNTS + accounts.iter().filter(|a| !a.is_writable).count();
+    let instruction_accounts = step_accounts_count(solana_accounts);
+
+    let fits = |size: usize| size <= PACKET_DATA_SIZE;
+
+    let instruction_data_len = STEP_INSTRUCTION_HEADER + transaction_len;
+    let use_holder = !fits(legacy_transaction_size(
+        instruction_accounts,
+        instruction_data_len,
+    )) && !fits(v0_transaction_size(
+        instruction_accounts,
+        writable,
+        readonly,
+        instruction_data_len,
+    ));
+
+    let data_len = if use_holder {
+        STEP_INSTRUCTION_HEADER
+    } else {
+        instruction_data_len
+    };
+
+    let legacy_size = legacy_transaction_size(instruction_accounts, data_len);
+    let v0_size = v0_transaction_size(instruction_accounts, writable, readonly, data_len);
+
+    let use_alt = !fits(legacy_size) || instruction_accounts >= MIN_ACCOUNTS_TO_USE_ALT;
+    if !use_alt {
+        return AltPlan {
+            use_alt,
+            transaction_len,
+            use_holder,
+            legacy_transaction_size: legacy_size,
+            v0_transaction_size: v0_size,
+            fits_packet: fits(legacy_size),
+            ..AltPlan::default()
+        };
+    }
+
+    let table_accounts = writable + readonly;
+
+    AltPlan {
+        use_alt,
+        accounts: accounts.iter().map(|a| a.pubkey).collect(),
+        extend_instructions: (table_accounts + (ACCOUNTS_PER_ALT_EXTEND - 1))
+            / ACCOUNTS_PER_ALT_EXTEND,
+        transaction_len,
+        use_holder,
+        legacy_transaction_size: legacy_size,
+        v0_transaction_size: v0_size,
+        fits_packet: fits(v0_size),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solana_accounts(count: usize) -> Vec<SolanaAccount> {
+        (0..count)
+            .map(|i| SolanaAccount {
+                pubkey: Pubkey::new_unique(),
+                is_writable: i % 2 == 0,
+                is_legacy: false,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_step_accounts_count() {
+        let mut accounts = solana_accounts(4);
+        assert_eq!(step_accounts_count(&accounts), 10);
+
+        // System program is a fixed account of the step instruction
+        accounts.push(SolanaAccount {
+            pubkey: system_program::ID,
+            is_writable: false,
+            is_legacy: false,
+        });
+        assert_eq!(step_accounts_count(&accounts), 10);
+        assert_eq!(step_accounts_count(&[]), 6);
+    }
+
+    #[test]
+    fn test_small_transaction_without_alt() {
+        let plan = build(&solana_accounts(4), 200);
+
+        assert!(!plan.use_alt);
+        assert!(!plan.use_holder);
+        assert!(plan.fits_packet);
+        assert!(plan.accounts.is_empty());
+        assert_eq!(plan.extend_instructions, 0);
+    }
+
+    #[test]
+    fn test_large_transaction_uses_holder() {
+        let plan = build(&solana_accounts(4), 2000);
+
+        assert!(plan.use_holder);
+        assert!(!plan.use_alt);
+        assert!(plan.fits_packet);
+    }
+
+    #[test]
+    fn test_many_accounts_use_alt() {
+        let accounts = solana_accounts(60);
+        let plan = build(&accounts, 200);
+
+        assert!(plan.use_alt);
+        assert_eq!(plan.accounts.len(), 60);
+        // 60 accounts of the transaction + holder, treasury, operator balance,
+        // system program and operator record
+        assert_eq!(plan.extend_instructions, 3);
+        assert!(plan.v0_transaction_size < plan.legacy_transaction_size);
+        assert!(plan.fits_packet);
+    }
+}
@@ -1,31 +1,58 @@
-use crate::rpc::{check_account_for_fee, CloneRpcClient};
+use crate::rpc::{check_account_for_fee, CloneRpcClient, Rpc};
 use crate::{
     commands::get_neon_elf::read_elf_parameters_from_account, errors::NeonError, Config, NeonResult,
 };
 use evm_loader::account::{MainTreasury, Treasury};
+use evm_loader::instruction_builder;
 use log::{info, warn};
 use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, DisplayFromStr};
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
 use solana_sdk::signature::Signer;
-use solana_sdk::{
-    instruction::{AccountMeta, Instruction},
-    message::Message,
-    system_program,
-    transaction::Transaction,
-};
+use solana_sdk::{message::Message, transaction::Transaction};
 use spl_token::instruction::sync_native;
-use std::ops::Deref;
+use std::collections::HashMap;
+
+/// `CollectTreasure` instructions packed into one Solana transaction
+const COLLECT_BATCH_SIZE: usize = 20;
+
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TreasuryPoolBalance {
+    pub index: u32,
+    #[serde_as(as = "DisplayFromStr")]
+    pub pubkey: Pubkey,
+    pub exists: bool,
+    pub lamports: u64,
+    /// Lamports above the rent exempt minimum which can be collected
+    pub available: u64,
+}
+
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TreasuryReport {
+    #[serde_as(as = "DisplayFromStr")]
+    pub main_treasury: Pubkey,
+    pub main_balance: u64,
+    pub pool_count: u32,
+    pub pools: Vec<TreasuryPoolBalance>,
+    /// Lamports which can be collected from all pools
+    pub total_available: u64,
+    /// Main treasury balance together with the collectable lamports of the pools
+    pub total: u64,
+}
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CollectTreasuryReturn {
     pub pool_address: String,
     pub balance: u64,
+    pub collected: u64,
+    pub transactions: Vec<String>,
 }
 
-pub async fn execute(
-    config: &Config,
-    rpc_client: &CloneRpcClient,
-    signer: &dyn Signer,
-) -> NeonResult<CollectTreasuryReturn> {
+/// Reads balances of the main treasury and all auxiliary treasury pools
+pub async fn report(config: &Config, rpc_client: &CloneRpcClient) -> NeonResult<TreasuryReport> {
     let neon_params = read_elf_parameters_from_account(config, rpc_client).await?;
 
     let pool_count: u32 = neon_params
@@ -33,60 +60,71 @@ pub async fn execute(
         .and_then(|value| value.parse().ok())
         .ok_or(NeonError::IncorrectProgram(config.evm_loader))?;
 
-    let main_balance_address = MainTreasury::address(&config.evm_loader).0;
-
-    info!("Main pool balance: {}", main_balance_address);
-
-    for i in 0..pool_count {
-        let (aux_balance_address, _) = Treasury::address(&config.evm_loader, i);
-
-        if let Some(aux_balance_account) = rpc_client
-            .get_account_with_commitment(&aux_balance_address, config.commitment)
-            .await?
-            .value
-        {
-            let minimal_balance = rpc_client
-                .get_minimum_balance_for_rent_exemption(aux_balance_account.data.len())
-                .await?;
-            let available_lamports = aux_balance_account.lamports.saturating_sub(minimal_balance);
-            if available_lamports > 0 {
-                info!(
-                    "{:4}: collect {} lamports from {}",
-                    i, available_lamports, aux_balance_address
-                );
-                let mut message = Message::new(
-                    &[Instruction::new_with_bincode(
-                        config.evm_loader,
-                        &(30_u8, i),
-                        vec![
-                            AccountMeta::new(main_balance_address, false),
-                            AccountMeta::new(aux_balance_address, false),
-                            AccountMeta::new_readonly(system_program::id(), false),
-                        ],
-                    )],
-                    Some(&signer.pubkey()),
-                );
-                let blockhash = rpc_client.get_latest_blockhash().await?;
-                message.recent_blockhash = blockhash;
-
-                check_account_for_fee(rpc_client, &signer.pubkey(), &message).await?;
-
-                let mut trx = Transaction::new_unsigned(message);
-                trx.try_sign(&[signer], blockhash)?;
-                rpc_client
-                    .send_and_confirm_transaction_with_spinner(&trx)
+    let (main_treasury, _) = MainTreasury::address(&config.evm_loader);
+    let pubkeys: Vec<Pubkey> = (0..pool_count)
+        .map(|i| Treasury::address(&config.evm_loader, i).0)
+        .chain(std::iter::once(main_treasury))
+        .collect();
+
+    let mut accounts = rpc_client.get_multiple_accounts(&pubkeys).await?;
+    let main_balance = accounts
+        .pop()
+        .flatten()
+        .map_or(0, |account| account.lamports);
+
+    let mut rent = HashMap::new();
+    let mut pools = Vec::with_capacity(accounts.len());
+    for ((index, pubkey), account) in (0..pool_count).zip(pubkeys).zip(accounts) {
+        let Some(account) = account else {
+            pools.push(TreasuryPoolBalance {
+                index,
+                pubkey,
+                exists: false,
+                lamports: 0,
+                available: 0,
+            });
+            continue;
+        };
+
+        let data_len = account.data.len();
+        let minimal_balance = match rent.get(&data_len) {
+            Some(minimal_balance) => *minimal_balance,
+            None => {
+                let minimal_balance = rpc_client
+                    .get_minimum_balance_for_rent_exemption(data_len)
                     .await?;
-            } else {
-                info!("{:4}: skip account {}", i, aux_balance_address);
+                rent.insert(data_len, minimal_balance);
+                minimal_balance
             }
-        } else {
-            warn!("{:4}: not found account {}", i, aux_balance_address);
-        }
+        };
+
+        pools.push(TreasuryPoolBalance {
+            index,
+            pubkey,
+            exists: true,
+            lamports: account.lamports,
+            available: account.lamports.saturating_sub(minimal_balance),
+        });
     }
-    let mut message = Message::new(
-        &[sync_native(&spl_token::id(), &main_balance_address)?],
-        Some(&signer.pubkey()),
-    );
+
+    let total_available = pools.iter().map(|pool| pool.available).sum::<u64>();
+
+    Ok(TreasuryReport {
+        main_treasury,
+        main_balance,
+        pool_count,
+        pools,
+        total_available,
+        total: main_balance.saturating_add(total_available),
+    })
+}
+
+async fn send(
+    rpc_client: &CloneRpcClient,
+    signer: &dyn Signer,
+    instructions: &[Instruction],
+) -> NeonResult<String> {
+    let mut message = Message::new(instructions, Some(&signer.pubkey()));
     let blockhash = rpc_client.get_latest_blockhash().await?;
     message.recent_blockhash = blockhash;
 
@@ -94,17 +132,61 @@ pub async fn execute(
 
     let mut trx = Transaction::new_unsigned(message);
     trx.try_sign(&[signer], blockhash)?;
-    rpc_client
+    let signature = rpc_client
         .send_and_confirm_transaction_with_spinner(&trx)
         .await?;
 
+    Ok(signature.to_string())
+}
+
+/// Collects lamports from all auxiliary treasury pools to the main treasury.
+/// Empty pools are skipped, the rest are collected in batches of `COLLECT_BATCH_SIZE` pools.
+pub async fn execute(
+    config: &Config,
+    rpc_client: &CloneRpcClient,
+    signer: &dyn Signer,
+) -> NeonResult<CollectTreasuryReturn> {
+    let treasury = report(config, rpc_client).await?;
+    info!("Main pool balance: {}", treasury.main_treasury);
+
+    let mut collect = Vec::with_capacity(treasury.pools.len());
+    for pool in &treasury.pools {
+        if !pool.exists {
+            warn!("{:4}: not found account {}", pool.index, pool.pubkey);
+        } else if pool.available == 0 {
+            info!("{:4}: skip account {}", pool.index, pool.pubkey);
+        } else {
+            info!(
+                "{:4}: collect {} lamports from {}",
+                pool.index, pool.available, pool.pubkey
+            );
+            collect.push(pool.index);
+        }
+    }
+
+    let mut transactions = Vec::new();
+    for batch in collect.chunks(COLLECT_BATCH_SIZE) {
+        let instructions: Vec<Instruction> = batch
+            .iter()
+            .map(|index| instruction_builder::collect_treasury(&config.evm_loader, *index))
+            .collect();
+
+        transactions.push(send(rpc_client, signer, &instructions).await?);
+    }
+
+    let sync = sync_native(&spl_token::id(), &treasury.main_treasury)?;
+    transactions.push(send(rpc_client, signer, &[sync]).await?);
+
     let main_balance_account = rpc_client
-        .deref()
-        .get_account(&main_balance_address)
-        .await?;
+        .get_account_with_commitment(&treasury.main_treasury, config.commitment)
+        .await?
+        .value
+        .ok_or(NeonError::IncorrectProgram(config.evm_loader))?;
 
     Ok(CollectTreasuryReturn {
-        pool_address: main_balance_address.to_string(),
+        pool_address: treasury.main_treasury.to_string(),
         balance: main_balance_account.lamports,
+        collected: treasury.total_available,
+        transactions,
     })
 }
@@ -1,25 +1,33 @@
+use crate::commands::alt_plan::{self, AltPlan};
+use crate::commands::emulate_exact::{self, ExactEmulateResponse};
 use crate::commands::get_config::BuildConfigSimulator;
 use crate::rpc::Rpc;
+use crate::tracing::tracers::emulation::{EmulationLog, EmulationTracer};
+use crate::tracing::tracers::prestate_tracer::PrestateTracerDiffModeResult;
 use crate::tracing::tracers::Tracer;
-use crate::types::{EmulateRequest, TxParams};
+use crate::types::{solana_overrides, EmulateRequest, TxParams};
 use crate::{
     account_storage::{EmulatorAccountStorage, SyncedAccountStorage},
     errors::NeonError,
-    NeonResult,
+    metrics, NeonResult,
 };
+use ethnum::U256;
 use evm_loader::account_storage::AccountStorage;
 use evm_loader::error::build_revert_message;
 use evm_loader::{
     config::{EVM_STEPS_MIN, PAYMENT_TO_TREASURE},
     evm::{ExitStatus, Machine},
     executor::SyncedExecutorState,
-    gasometer::LAMPORTS_PER_SIGNATURE,
+    gasometer::{
+        address_lookup_table_cost, write_to_holder_cost, CANCEL_TRX_COST, LAMPORTS_PER_SIGNATURE,
+    },
+    types::{Transaction, TransactionPayload},
 };
 use log::{debug, info};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use serde_with::{hex::Hex, serde_as, DisplayFromStr};
-use solana_sdk::{account::Account, pubkey::Pubkey};
+use solana_sdk::pubkey::Pubkey;
 
 #[serde_as]
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -29,6 +37,37 @@ pub struct SolanaAccount {
     pub is_writable: bool,
     pub is_legacy: bool,
 }
+
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountRent {
+    #[serde_as(as = "DisplayFromStr")]
+    pub pubkey: Pubkey,
+    pub lamports: u64,
+}
+
+/// Lamports the operator spends on the transaction.
+/// `total` is `used_gas`, holder and ALT costs are charged by `evm_loader` only
+/// when the transaction is executed from a holder account or with an address lookup table.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct EmulateCost {
+    /// Iterations including begin, end and account reallocations
+    pub iterations: u64,
+    pub iterations_lamports: u64,
+    pub treasury_lamports: u64,
+    pub cancel_lamports: u64,
+    /// Rent for new and resized accounts
+    pub rent: Vec<AccountRent>,
+    /// Rent for upgrade of legacy accounts
+    pub upgrade_rent_lamports: u64,
+    pub rent_lamports: u64,
+    /// Number of accounts of the Solana instruction
+    pub accounts_count: usize,
+    pub alt_lamports: u64,
+    pub holder_write_lamports: u64,
+    pub total: u64,
+}
+
 #[serde_as]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EmulateResponse {
@@ -42,6 +81,29 @@ pub struct EmulateResponse {
     pub used_gas: u64,
     pub iterations: u64,
     pub solana_accounts: Vec<SolanaAccount>,
+    #[serde(default)]
+    pub cost: EmulateCost,
+    #[serde(default)]
+    pub alt_plan: AltPlan,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exact: Option<ExactEmulateResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logs: Option<Vec<EmulationLog>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state_diff: Option<PrestateTracerDiffModeResult>,
+}
+
+/// Optional outputs of `emulate_trx`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EmulateOptions {
+    pub with_logs: bool,
+    pub with_state_diff: bool,
+}
+
+impl EmulateOptions {
+    const fn is_empty(self) -> bool {
+        !self.with_logs && !self.with_state_diff
+    }
 }
 
 impl EmulateResponse {
@@ -58,6 +120,11 @@ impl EmulateResponse {
             used_gas: 0,
             iterations: 0,
             solana_accounts: vec![],
+            cost: EmulateCost::default(),
+            alt_plan: AltPlan::default(),
+            exact: None,
+            logs: None,
+            state_diff: None,
         }
     }
 }
@@ -77,12 +144,7 @@ pub async fn execute<T: Tracer>(
         .as_ref()
         .and_then(|t| t.state_overrides.clone());
 
-    let solana_overrides = emulate_request.solana_overrides.map(|overrides| {
-        overrides
-            .iter()
-            .map(|(pubkey, account)| (*pubkey, account.as_ref().map(Account::from)))
-            .collect()
-    });
+    let solana_overrides = solana_overrides(emulate_request.solana_overrides.as_ref());
 
     let mut storage = EmulatorAccountStorage::with_accounts(
         rpc,
@@ -91,14 +153,27 @@ pub async fn execute<T: Tracer>(
         emulate_request.chains,
         block_overrides,
         state_overrides,
-        solana_overrides,
+        solana_overrides.clone(),
         emulate_request.tx.chain_id,
     )
     .await?;
 
     let step_limit = emulate_request.step_limit.unwrap_or(100_000);
 
-    let result = emulate_trx(emulate_request.tx.clone(), &mut storage, step_limit, tracer).await?;
+    let origin = emulate_request.tx.from;
+    let options = EmulateOptions {
+        with_logs: emulate_request.with_logs,
+        with_state_diff: emulate_request.with_state_diff,
+    };
+    let (mut response, traces) = emulate_trx(
+        emulate_request.tx.clone(),
+        &mut storage,
+        step_limit,
+        tracer,
+        options,
+    )
+    .await?;
+    metrics::observe_emulation(response.steps_executed, response.iterations);
 
     if storage.is_timestamp_used() {
         let mut storage2 =
@@ -109,10 +184,10 @@ pub async fn execute<T: Tracer>(
             &mut storage2,
             step_limit,
             Option::<T>::None,
+            EmulateOptions::default(),
         )
         .await
         {
-            let response = &result.0;
             let response2 = &result2.0;
 
             let mut combined_solana_accounts = response.solana_accounts.clone();
@@ -128,7 +203,10 @@ pub async fn execute<T: Tracer>(
                 }
             });
 
-            let emul_response = EmulateResponse {
+            let combined_alt_plan =
+                alt_plan::build(&combined_solana_accounts, response.alt_plan.transaction_len);
+
+            response = EmulateResponse {
                 // We get the result from the first response (as it is executed on the current time)
                 result: response.result.clone(),
                 exit_status: response.exit_status.to_string(),
@@ -141,23 +219,124 @@ pub async fn execute<T: Tracer>(
                 used_gas: response.used_gas.max(response2.used_gas),
                 iterations: response.iterations.max(response2.iterations),
                 solana_accounts: combined_solana_accounts,
+                cost: if response2.used_gas > response.used_gas {
+                    response2.cost.clone()
+                } else {
+                    response.cost.clone()
+                },
+                alt_plan: combined_alt_plan,
+                exact: None,
+                logs: response.logs.take(),
+                state_diff: response.state_diff.take(),
             };
-
-            return Ok((emul_response, result.1));
         }
     }
 
-    Ok(result)
+    if let Some(exact) = emulate_request.exact {
+        let exact_response = emulate_exact::execute(
+            rpc,
+            program_id,
+            &exact,
+            origin,
+            storage.default_chain_id(),
+            &response.solana_accounts,
+            &solana_overrides.unwrap_or_default(),
+        )
+        .await?;
+
+        response.exact = Some(exact_response);
+    }
+
+    Ok((response, traces))
+}
+
+fn rlp_header_len(payload_len: usize) -> usize {
+    if payload_len < 56 {
+        1
+    } else {
+        let len_bytes = payload_len.to_be_bytes();
+        1 + len_bytes.iter().skip_while(|b| **b == 0).count()
+    }
+}
+
+fn rlp_bytes_len(bytes: &[u8]) -> usize {
+    if bytes.len() == 1 && bytes[0] < 0x80 {
+        1
+    } else {
+        rlp_header_len(bytes.len()) + bytes.len()
+    }
+}
+
+fn rlp_u256_len(value: U256) -> usize {
+    let bytes = value.to_be_bytes();
+    let leading_zeros = bytes.iter().take_while(|b| **b == 0).count();
+    rlp_bytes_len(&bytes[leading_zeros..])
 }
 
-async fn emulate_trx<T: Tracer>(
+fn rlp_list_len(payload_len: usize) -> usize {
+    rlp_header_len(payload_len) + payload_len
+}
+
+/// Length of the signed transaction RLP, the signature is counted with its maximal size
+fn signed_rlp_len(tx: &Transaction) -> usize {
+    const ADDRESS_LEN: usize = 1 + 20;
+    const SIGNATURE_LEN: usize = 2 * (1 + 32);
+
+    match &tx.transaction {
+        TransactionPayload::Legacy(legacy) => {
+            let chain_id = legacy.chain_id.unwrap_or_default();
+            let v = chain_id * 2 + 36;
+            let payload = rlp_u256_len(U256::from(legacy.nonce))
+                + rlp_u256_len(legacy.gas_price)
+                + rlp_u256_len(legacy.gas_limit)
+                + legacy.target.map_or(1, |_| ADDRESS_LEN)
+                + rlp_u256_len(legacy.value)
+                + rlp_bytes_len(&legacy.call_data)
+                + rlp_u256_len(v)
+                + SIGNATURE_LEN;
+            rlp_list_len(payload)
+        }
+        TransactionPayload::AccessList(access_list) => {
+            let items = access_list
+                .access_list
+                .iter()
+                .map(|(_, keys)| rlp_list_len(ADDRESS_LEN + rlp_list_len(keys.len() * 33)))
+                .sum();
+            let payload = rlp_u256_len(access_list.chain_id)
+                + rlp_u256_len(U256::from(access_list.nonce))
+                + rlp_u256_len(access_list.gas_price)
+                + rlp_u256_len(access_list.gas_limit)
+                + access_list.target.map_or(1, |_| ADDRESS_LEN)
+                + rlp_u256_len(access_list.value)
+                + rlp_bytes_len(&access_list.call_data)
+                + rlp_list_len(items)
+                + 1 // y parity
+                + SIGNATURE_LEN;
+            1 + rlp_list_len(payload) // transaction type
+        }
+    }
+}
+
+pub(crate) async fn emulate_trx<T: Tracer>(
     tx_params: TxParams,
     storage: &mut EmulatorAccountStorage<'_, impl Rpc>,
     step_limit: u64,
     tracer: Option<T>,
+    options: EmulateOptions,
 ) -> NeonResult<(EmulateResponse, Option<Value>)> {
     info!("tx_params: {:?}", tx_params);
 
+    let tracer = if tracer.is_some() || !options.is_empty() {
+        Some(EmulationTracer::new(
+            tracer,
+            &tx_params,
+            options.with_logs,
+            options.with_state_diff,
+        ))
+    } else {
+        None
+    };
+
     let (origin, tx) = tx_params.into_transaction(storage).await;
 
     info!("origin: {:?}", origin);
@@ -184,15 +363,21 @@ async fn emulate_trx<T: Tracer>(
 
     let steps_iterations = (steps_executed + (EVM_STEPS_MIN - 1)) / EVM_STEPS_MIN;
     let treasury_gas = steps_iterations * PAYMENT_TO_TREASURE;
-    let cancel_gas = LAMPORTS_PER_SIGNATURE;
+    let cancel_gas = CANCEL_TRX_COST;
 
     let begin_end_iterations = 2;
     let iterations: u64 = steps_iterations + begin_end_iterations + storage.realloc_iterations;
     let iterations_gas = iterations * LAMPORTS_PER_SIGNATURE;
-    let storage_gas = storage.get_changes_in_rent()?;
+
+    let upgrade_rent = storage.get_upgrade_rent()?;
+    let rent = storage.get_regular_rent_per_account()?;
+    let storage_gas = upgrade_rent + rent.iter().map(|(_, lamports)| lamports).sum::<u64>();
 
     let used_gas = storage_gas + iterations_gas + treasury_gas + cancel_gas;
 
+    let (tracer, logs, state_diff) =
+        tracer.map_or((None, None, None), |tracer| tracer.into_parts(used_gas));
+
     let solana_accounts = storage
         .used_accounts()
         .iter()
@@ -203,6 +388,26 @@ async fn emulate_trx<T: Tracer>(
         })
         .collect::<Vec<_>>();
 
+    let transaction_len = signed_rlp_len(&tx);
+    let accounts_count = alt_plan::step_accounts_count(&solana_accounts);
+    let cost = EmulateCost {
+        iterations,
+        iterations_lamports: iterations_gas,
+        treasury_lamports: treasury_gas,
+        cancel_lamports: cancel_gas,
+        rent: rent
+            .into_iter()
+            .map(|(pubkey, lamports)| AccountRent { pubkey, lamports })
+            .collect(),
+        upgrade_rent_lamports: upgrade_rent,
+        rent_lamports: storage_gas,
+        accounts_count,
+        alt_lamports: address_lookup_table_cost(accounts_count),
+        holder_write_lamports: write_to_holder_cost(transaction_len),
+        total: used_gas,
+    };
+    let alt_plan = alt_plan::build(&solana_accounts, transaction_len);
+
     Ok((
         EmulateResponse {
             exit_status: exit_status.to_string(),
@@ -214,6 +419,11 @@ async fn emulate_trx<T: Tracer>(
             solana_accounts,
             result: exit_status.into_result().unwrap_or_default(),
             iterations,
+            cost,
+            alt_plan,
+            exact: None,
+            logs,
+            state_diff,
         },
         tracer.map(|tracer| tracer.into_traces(used_gas)),
     ))
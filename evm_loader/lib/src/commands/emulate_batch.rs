@@ -0,0 +1,84 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::account_storage::EmulatorAccountStorage;
+use crate::commands::emulate::{emulate_trx, EmulateOptions, EmulateResponse};
+use crate::commands::get_config::BuildConfigSimulator;
+use crate::rpc::Rpc;
+use crate::tracing::tracers::{new_tracer, TracerTypeEnum};
+use crate::types::{solana_overrides, EmulateBatchRequest};
+use crate::{metrics, NeonResult};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmulateBatchResponse {
+    pub transactions: Vec<EmulateResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub traces: Option<Vec<Value>>,
+}
+
+/// Emulates transactions one after another on the same account storage,
+/// so every transaction sees the state changes of the previous ones
+pub async fn execute(
+    rpc: &(impl Rpc + BuildConfigSimulator),
+    program_id: Pubkey,
+    request: EmulateBatchRequest,
+) -> NeonResult<EmulateBatchResponse> {
+    let block_overrides = request
+        .trace_config
+        .as_ref()
+        .and_then(|t| t.block_overrides.clone());
+    let state_overrides = request
+        .trace_config
+        .as_ref()
+        .and_then(|t| t.state_overrides.clone());
+    let trace_config = request
+        .trace_config
+        .as_ref()
+        .map(|c| c.trace_config.clone())
+        .unwrap_or_default();
+
+    let solana_overrides = solana_overrides(request.solana_overrides.as_ref());
+
+    let mut storage = EmulatorAccountStorage::with_accounts(
+        rpc,
+        program_id,
+        &request.accounts,
+        request.chains,
+        block_overrides,
+        state_overrides,
+        solana_overrides,
+        request.txs.first().and_then(|tx| tx.chain_id),
+    )
+    .await?;
+
+    let step_limit = request.step_limit.unwrap_or(100_000);
+    let options = EmulateOptions {
+        with_logs: request.with_logs,
+        with_state_diff: request.with_state_diff,
+    };
+
+    let mut transactions = Vec::with_capacity(request.txs.len());
+    let mut traces = Vec::with_capacity(request.txs.len());
+
+    for tx in request.txs {
+        let tracer = if request.with_traces {
+            Some(new_tracer(&tx, trace_config.clone())?)
+        } else {
+            None::<TracerTypeEnum>
+        };
+
+        let (response, trace) = emulate_trx(tx, &mut storage, step_limit, tracer, options).await?;
+        metrics::observe_emulation(response.steps_executed, response.iterations);
+
+        storage.commit_transaction();
+
+        transactions.push(response);
+        traces.push(trace.unwrap_or_default());
+    }
+
+    Ok(EmulateBatchResponse {
+        transactions,
+        traces: request.with_traces.then_some(traces),
+    })
+}
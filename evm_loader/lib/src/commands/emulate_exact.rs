@@ -0,0 +1,487 @@
+//! Exact emulation: runs a Neon transaction through `SolanaSimulator` with the deployed
+//! `evm_loader` program, the same way an operator does - holder writes and iterative steps.
+
+use std::collections::HashMap;
+
+use evm_loader::{
+    account::{OperatorRecord, Treasury, TAG_STATE_FINALIZED},
+    config::EVM_STEPS_MIN,
+    instruction_builder::{self, ExecuteAccounts},
+    types::{Address, Transaction as NeonTransaction},
+};
+use log::debug;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use solana_sdk::{
+    account::Account,
+    compute_budget::ComputeBudgetInstruction,
+    instruction::{AccountMeta, Instruction},
+    message::Message,
+    native_token::LAMPORTS_PER_SOL,
+    packet::PACKET_DATA_SIZE,
+    pubkey::Pubkey,
+    signature::Signature,
+    system_program,
+    transaction::{Transaction, TransactionError, VersionedTransaction},
+};
+
+use crate::account_storage::{account_info, FAKE_OPERATOR};
+use crate::commands::emulate::SolanaAccount;
+use crate::commands::holder::{write_instructions, HOLDER_SIZE};
+use crate::errors::NeonError;
+use crate::rpc::Rpc;
+use crate::solana_simulator::{self, SolanaSimulator};
+use crate::types::ExactEmulateConfig;
+use crate::NeonResult;
+
+const HOLDER_SEED: &str = "emulate";
+const TREASURY_INDEX: u32 = 0;
+const COMPUTE_UNITS: u32 = 1_400_000;
+const HEAP_SIZE: u32 = 256 * 1024;
+const OPERATOR_LAMPORTS: u64 = 1_000 * LAMPORTS_PER_SOL;
+const DEFAULT_MAX_ITERATIONS: u64 = 1_000;
+
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExactIteration {
+    pub executed_units: u64,
+    pub error: Option<TransactionError>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExactEmulateResponse {
+    /// Transaction doesn't fit into an instruction and is executed from the holder
+    pub from_account: bool,
+    pub holder_writes: u64,
+    pub iterations: Vec<ExactIteration>,
+    pub iteration_count: u64,
+    /// State account reached the finalized state
+    pub finalized: bool,
+    /// Lamports spent by the operator: fees, rent and treasury payments
+    pub lamports: u64,
+    pub error: Option<TransactionError>,
+    /// Logs of the failed transaction
+    pub logs: Option<Vec<String>>,
+}
+
+struct Outcome {
+    status: Result<(), TransactionError>,
+    executed_units: u64,
+    logs: Option<Vec<String>>,
+}
+
+struct Accounts {
+    program_id: Pubkey,
+    operator: Pubkey,
+    operator_balance: Pubkey,
+    operator_record: Pubkey,
+    treasury: Pubkey,
+    holder: Pubkey,
+}
+
+struct ExactSimulator {
+    simulator: SolanaSimulator,
+    operator: Pubkey,
+}
+
+impl ExactSimulator {
+    fn build_transaction(&self, instructions: &[Instruction]) -> Transaction {
+        let message = Message::new_with_blockhash(
+            instructions,
+            Some(&self.operator),
+            &self.simulator.blockhash(),
+        );
+
+        // Signatures are not verified by the simulator
+        Transaction {
+            signatures: vec![
+                Signature::default();
+                usize::from(message.header.num_required_signatures)
+            ],
+            message,
+        }
+    }
+
+    fn process(&mut self, instruction: Instruction) -> NeonResult<Outcome> {
+        let instructions = [
+            ComputeBudgetInstruction::set_compute_unit_limit(COMPUTE_UNITS),
+            ComputeBudgetInstruction::request_heap_frame(HEAP_SIZE),
+            instruction,
+        ];
+        let tx = VersionedTransaction::from(self.build_transaction(&instructions));
+        let tx = self.simulator.sanitize_transaction(tx, false)?;
+
+        match self.simulator.process_transaction(tx) {
+            Ok(details) => Ok(Outcome {
+                status: details.status,
+                executed_units: details.executed_units,
+                logs: details.log_messages,
+            }),
+            // Transaction was rejected before execution, e.g. too many account locks
+            Err(solana_simulator::Error::TransactionError(error)) => Ok(Outcome {
+                status: Err(error),
+                executed_units: 0,
+                logs: None,
+            }),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn setup(&mut self, instruction: Instruction) -> NeonResult<()> {
+        let outcome = self.process(instruction)?;
+        outcome.status.map_err(NeonError::from)
+    }
+
+    fn lamports(&self, pubkey: &Pubkey) -> u64 {
+        self.simulator
+            .get_account(pubkey)
+            .map_or(0, |account| account.lamports)
+    }
+
+    fn is_finalized(&self, pubkey: &Pubkey) -> bool {
+        self.simulator
+            .get_account(pubkey)
+            .and_then(|account| account.data.first().copied())
+            == Some(TAG_STATE_FINALIZED)
+    }
+}
+
+fn transaction_step(
+    accounts: &Accounts,
+    solana_accounts: &[SolanaAccount],
+    step_count: u32,
+    index: u32,
+    raw_transaction: Option<&[u8]>,
+) -> Instruction {
+    let metas: Vec<AccountMeta> = solana_accounts
+        .iter()
+        .map(|account| {
+            if account.is_writable {
+                AccountMeta::new(account.pubkey, false)
+            } else {
+                AccountMeta::new_readonly(account.pubkey, false)
+            }
+        })
+        .collect();
+
+    let execute_accounts = ExecuteAccounts {
+        operator: accounts.operator,
+        operator_balance: accounts.operator_balance,
+        treasury_index: TREASURY_INDEX,
+        accounts: &metas,
+    };
+
+    // Unique index makes every iteration a distinct transaction
+    if let Some(raw_transaction) = raw_transaction {
+        instruction_builder::transaction_step_from_instruction(
+            &accounts.program_id,
+            &accounts.holder,
+            &execute_accounts,
+            step_count,
+            index,
+            raw_transaction,
+        )
+    } else {
+        instruction_builder::transaction_step_from_account(
+            &accounts.program_id,
+            &accounts.holder,
+            &execute_accounts,
+            step_count,
+            index,
+        )
+    }
+}
+
+/// Iterations stop on a failed Solana transaction or when the state account is finalized,
+/// running out of `max_iterations` leaves the Neon transaction incomplete
+fn check_completed(response: &ExactEmulateResponse, max_iterations: u64) -> NeonResult<()> {
+    if response.error.is_none() && !response.finalized {
+        return Err(NeonError::IterationLimitReached(max_iterations));
+    }
+
+    Ok(())
+}
+
+pub async fn execute(
+    rpc: &impl Rpc,
+    program_id: Pubkey,
+    config: &ExactEmulateConfig,
+    origin: Address,
+    default_chain_id: u64,
+    solana_accounts: &[SolanaAccount],
+    solana_overrides: &HashMap<Pubkey, Option<Account>>,
+) -> NeonResult<ExactEmulateResponse> {
+    let trx = NeonTransaction::from_rlp(&config.raw_transaction)?;
+    if trx.recover_caller_address()? != origin {
+        return Err(NeonError::InvalidParameters(format!(
+            "raw_transaction is not signed by {origin}"
+        )));
+    }
+
+    let chain_id = trx.chain_id().unwrap_or(default_chain_id);
+    let step_count = config.steps_per_iteration.unwrap_or(EVM_STEPS_MIN);
+    let step_count = u32::try_from(step_count)
+        .map_err(|_| NeonError::InvalidParameters("steps_per_iteration".to_string()))?;
+    let max_iterations = config.max_iterations.unwrap_or(DEFAULT_MAX_ITERATIONS);
+
+    let operator = config.operator.unwrap_or(FAKE_OPERATOR);
+    let operator_address = config.operator_address.unwrap_or_default();
+
+    let accounts = Accounts {
+        program_id,
+        operator,
+        operator_balance: operator_address
+            .find_operator_address(&program_id, chain_id, &operator)
+            .0,
+        operator_record: OperatorRecord::address(&program_id, &operator).0,
+        treasury: Treasury::address(&program_id, TREASURY_INDEX).0,
+        holder: instruction_builder::holder_address(&program_id, &operator, HOLDER_SEED),
+    };
+
+    let mut simulator = SolanaSimulator::new(rpc).await?;
+
+    let mut keys = vec![
+        program_id,
+        accounts.treasury,
+        accounts.operator_balance,
+        accounts.operator_record,
+    ];
+    keys.extend(solana_accounts.iter().map(|account| account.pubkey));
+    simulator.sync_accounts(rpc, &keys).await?;
+
+    for (pubkey, account) in solana_overrides {
+        simulator.set_account(pubkey, &account.clone().unwrap_or_default());
+    }
+
+    simulator.set_account(
+        &operator,
+        &Account {
+            lamports: OPERATOR_LAMPORTS,
+            owner: system_program::id(),
+            ..Account::default()
+        },
+    );
+
+    // Unregistered operators are registered in the simulator, registry status is kept otherwise
+    if simulator.get_account(&accounts.operator_record).is_none() {
+        let mut record = Account {
+            lamports: simulator
+                .rent()
+                .minimum_balance(OperatorRecord::required_account_size()),
+            data: vec![0; OperatorRecord::required_account_size()],
+            owner: program_id,
+            ..Account::default()
+        };
+        let info = account_info(&accounts.operator_record, &mut record);
+        OperatorRecord::initialize(&program_id, &info, operator)?;
+        simulator.set_account(&accounts.operator_record, &record);
+    }
+
+    let holder_lamports = simulator.rent().minimum_balance(HOLDER_SIZE);
+    simulator.set_account(
+        &accounts.holder,
+        &Account {
+            lamports: holder_lamports,
+            data: vec![0; HOLDER_SIZE],
+            owner: program_id,
+            ..Account::default()
+        },
+    );
+
+    let mut simulator = ExactSimulator {
+        simulator,
+        operator,
+    };
+
+    if simulator.lamports(&accounts.operator_balance) == 0 {
+        simulator.setup(instruction_builder::operator_balance_create(
+            &program_id,
+            &operator,
+            operator_address,
+            chain_id,
+        ))?;
+    }
+    simulator.setup(instruction_builder::holder_create(
+        &program_id,
+        &accounts.holder,
+        &operator,
+        HOLDER_SEED,
+    ))?;
+
+    let from_instruction = {
+        let instruction = transaction_step(
+            &accounts,
+            solana_accounts,
+            step_count,
+            0,
+            Some(&config.raw_transaction),
+        );
+        let tx = simulator.build_transaction(&[
+            ComputeBudgetInstruction::set_compute_unit_limit(COMPUTE_UNITS),
+            ComputeBudgetInstruction::request_heap_frame(HEAP_SIZE),
+            instruction,
+        ]);
+        bincode::serialized_size(&tx)? <= PACKET_DATA_SIZE as u64
+    };
+
+    let mut response = ExactEmulateResponse {
+        from_account: !from_instruction,
+        holder_writes: 0,
+        iterations: vec![],
+        iteration_count: 0,
+        finalized: false,
+        lamports: 0,
+        error: None,
+        logs: None,
+    };
+
+    let lamports_before = simulator.lamports(&operator);
+
+    if !from_instruction {
+        let writes = write_instructions(
+            &program_id,
+            &accounts.holder,
+            &operator,
+            &trx.hash(),
+            &config.raw_transaction,
+        );
+        for instruction in writes {
+            let outcome = simulator.process(instruction)?;
+            response.holder_writes += 1;
+
+            if let Err(error) = outcome.status {
+                response.error = Some(error);
+                response.logs = outcome.logs;
+                break;
+            }
+        }
+    }
+
+    let raw_transaction = from_instruction.then_some(config.raw_transaction.as_slice());
+
+    let mut index = 0_u32;
+    while response.error.is_none() && u64::from(index) < max_iterations {
+        let instruction = transaction_step(
+            &accounts,
+            solana_accounts,
+            step_count,
+            index,
+            raw_transaction,
+        );
+        let outcome = simulator.process(instruction)?;
+        debug!("iteration {index}: {} units", outcome.executed_units);
+
+        response.iterations.push(ExactIteration {
+            executed_units: outcome.executed_units,
+            error: outcome.status.clone().err(),
+        });
+
+        if let Err(error) = outcome.status {
+            response.error = Some(error);
+            response.logs = outcome.logs;
+            break;
+        }
+
+        if simulator.is_finalized(&accounts.holder) {
+            response.finalized = true;
+            break;
+        }
+
+        index += 1;
+    }
+
+    check_completed(&response, max_iterations)?;
+
+    response.iteration_count = response.iterations.len() as u64;
+    response.lamports = lamports_before.saturating_sub(simulator.lamports(&operator));
+
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use evm_loader::instruction_builder::ParsedInstruction;
+
+    fn accounts() -> Accounts {
+        Accounts {
+            program_id: Pubkey::new_unique(),
+            operator: Pubkey::new_unique(),
+            operator_balance: Pubkey::new_unique(),
+            operator_record: Pubkey::new_unique(),
+            treasury: Pubkey::new_unique(),
+            holder: Pubkey::new_unique(),
+        }
+    }
+
+    fn response(finalized: bool, error: Option<TransactionError>) -> ExactEmulateResponse {
+        ExactEmulateResponse {
+            from_account: false,
+            holder_writes: 0,
+            iterations: vec![],
+            iteration_count: 0,
+            finalized,
+            lamports: 0,
+            error,
+            logs: None,
+        }
+    }
+
+    #[test]
+    fn test_check_completed() {
+        assert!(check_completed(&response(true, None), 10).is_ok());
+
+        // Failed transaction is reported in the response
+        let failed = response(false, Some(TransactionError::AccountInUse));
+        assert!(check_completed(&failed, 10).is_ok());
+
+        let incomplete = check_completed(&response(false, None), 10);
+        assert!(matches!(
+            incomplete,
+            Err(NeonError::IterationLimitReached(10))
+        ));
+    }
+
+    #[test]
+    fn test_transaction_step() {
+        let accounts = accounts();
+        let solana_accounts = [SolanaAccount {
+            pubkey: Pubkey::new_unique(),
+            is_writable: true,
+            is_legacy: false,
+        }];
+
+        let instruction = transaction_step(&accounts, &solana_accounts, 500, 3, Some(&[1, 2, 3]));
+        assert_eq!(instruction.program_id, accounts.program_id);
+        assert_eq!(
+            ParsedInstruction::parse(&instruction.data).unwrap(),
+            ParsedInstruction::TransactionStepFromInstruction {
+                treasury_index: TREASURY_INDEX,
+                step_count: 500,
+                unique_index: 3,
+                transaction: &[1, 2, 3],
+            }
+        );
+
+        let metas = &instruction.accounts;
+        assert_eq!(metas[0].pubkey, accounts.holder);
+        assert!(metas
+            .iter()
+            .any(|meta| meta.pubkey == solana_accounts[0].pubkey && meta.is_writable));
+        assert_eq!(
+            metas[5].pubkey,
+            OperatorRecord::address(&accounts.program_id, &accounts.operator).0
+        );
+
+        let instruction = transaction_step(&accounts, &solana_accounts, 500, 3, None);
+        assert_eq!(
+            ParsedInstruction::parse(&instruction.data).unwrap(),
+            ParsedInstruction::TransactionStepFromAccount {
+                treasury_index: TREASURY_INDEX,
+                step_count: 500,
+                unique_index: 3,
+            }
+        );
+    }
+}
@@ -0,0 +1,277 @@
+use std::future::Future;
+
+use ethnum::U256;
+use log::debug;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::account_storage::EmulatorAccountStorage;
+use crate::commands::emulate::{
+    emulate_trx, EmulateCost, EmulateOptions, EmulateResponse, SolanaAccount,
+};
+use crate::commands::get_config::BuildConfigSimulator;
+use crate::errors::NeonError;
+use crate::rpc::Rpc;
+use crate::tracing::tracers::TracerTypeEnum;
+use crate::types::{solana_overrides, EstimateGasRequest, TxParams};
+use crate::{metrics, NeonResult};
+
+/// Search stops when `(hi - lo) / hi` drops below this ratio, in basis points (1.5% like geth)
+const ESTIMATE_GAS_ERROR_RATIO_BPS: u128 = 150;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EstimateGasResponse {
+    /// Minimal `gas_limit` the transaction succeeds with
+    pub gas_limit: u64,
+    pub used_gas: u64,
+    pub steps_executed: u64,
+    pub iterations: u64,
+    pub cost: EmulateCost,
+    pub solana_accounts: Vec<SolanaAccount>,
+    /// Number of emulations performed by the search
+    pub emulations: u64,
+}
+
+fn is_passed(response: &EmulateResponse, gas_limit: u64) -> bool {
+    response.exit_status == "succeed" && response.used_gas <= gas_limit
+}
+
+async fn emulate_with_gas_limit(
+    base: &EmulatorAccountStorage<'_, impl Rpc + BuildConfigSimulator>,
+    tx: &TxParams,
+    step_limit: u64,
+    gas_limit: u64,
+) -> NeonResult<EmulateResponse> {
+    let mut storage = EmulatorAccountStorage::new_from_other(base, 0, 0, tx.chain_id).await?;
+
+    let mut tx = tx.clone();
+    tx.gas_limit = Some(U256::from(gas_limit));
+
+    let (response, _) = emulate_trx(
+        tx,
+        &mut storage,
+        step_limit,
+        None::<TracerTypeEnum>,
+        EmulateOptions::default(),
+    )
+    .await?;
+    metrics::observe_emulation(response.steps_executed, response.iterations);
+
+    debug!(
+        "estimate_gas: gas_limit={gas_limit}, exit_status={}, used_gas={}",
+        response.exit_status, response.used_gas
+    );
+
+    Ok(response)
+}
+
+struct Estimate<R> {
+    gas_limit: u64,
+    best: R,
+    emulations: u64,
+}
+
+/// Searches for the minimal gas limit in `[used_gas, cap]`, the transaction passes with `cap`.
+/// `probe` emulates the transaction with the gas limit and returns the response if it passes.
+async fn search_gas_limit<R, F, Fut>(
+    used_gas: u64,
+    cap: u64,
+    mut best: R,
+    mut probe: F,
+) -> NeonResult<Estimate<R>>
+where
+    F: FnMut(u64) -> Fut,
+    Fut: Future<Output = NeonResult<Option<R>>>,
+{
+    let mut emulations = 0_u64;
+    let mut lo = used_gas.saturating_sub(1);
+    let mut hi = cap;
+
+    // Most transactions pass with a limit slightly above the used gas
+    let optimistic = used_gas.saturating_mul(64) / 63;
+    if optimistic < hi {
+        emulations += 1;
+        match probe(optimistic).await? {
+            Some(response) => {
+                hi = optimistic;
+                best = response;
+            }
+            None => lo = optimistic,
+        }
+    }
+
+    while lo + 1 < hi {
+        let range = u128::from(hi - lo) * 10_000;
+        if range / u128::from(hi) < ESTIMATE_GAS_ERROR_RATIO_BPS {
+            break;
+        }
+
+        // Most usually the gas is much closer to the lower bound
+        let mid = (lo + (hi - lo) / 2).min(lo.saturating_mul(2)).max(lo + 1);
+
+        emulations += 1;
+        match probe(mid).await? {
+            Some(response) => {
+                hi = mid;
+                best = response;
+            }
+            None => lo = mid,
+        }
+    }
+
+    Ok(Estimate {
+        gas_limit: hi,
+        best,
+        emulations,
+    })
+}
+
+/// Finds the minimal `gas_limit` the transaction succeeds with.
+/// Every probe is emulated on a copy of the same account snapshot,
+/// so Solana accounts are downloaded only once.
+pub async fn execute(
+    rpc: &(impl Rpc + BuildConfigSimulator),
+    program_id: Pubkey,
+    request: EstimateGasRequest,
+) -> NeonResult<EstimateGasResponse> {
+    let block_overrides = request
+        .trace_config
+        .as_ref()
+        .and_then(|t| t.block_overrides.clone());
+    let state_overrides = request
+        .trace_config
+        .as_ref()
+        .and_then(|t| t.state_overrides.clone());
+
+    let solana_overrides = solana_overrides(request.solana_overrides.as_ref());
+
+    let mut storage = EmulatorAccountStorage::with_accounts(
+        rpc,
+        program_id,
+        &request.accounts,
+        request.chains,
+        block_overrides,
+        state_overrides,
+        solana_overrides,
+        request.tx.chain_id,
+    )
+    .await?;
+
+    let step_limit = request.step_limit.unwrap_or(100_000);
+    let tx = request.tx;
+
+    let cap = tx.gas_limit.map_or(u64::MAX, |gas_limit| {
+        gas_limit.min(U256::from(u64::MAX)).as_u64()
+    });
+
+    // The first run downloads accounts into the snapshot shared by the next probes
+    let mut first_tx = tx.clone();
+    first_tx.gas_limit = Some(U256::from(cap));
+    let (best, _) = emulate_trx(
+        first_tx,
+        &mut storage,
+        step_limit,
+        None::<TracerTypeEnum>,
+        EmulateOptions::default(),
+    )
+    .await?;
+    metrics::observe_emulation(best.steps_executed, best.iterations);
+
+    if !is_passed(&best, cap) {
+        let message = format!(
+            "transaction fails with gas_limit {cap}: {}",
+            best.exit_status
+        );
+        return Err(NeonError::GasEstimationFailed(message, best.result));
+    }
+
+    let used_gas = best.used_gas;
+    let storage = &storage;
+    let tx = &tx;
+    let Estimate {
+        gas_limit,
+        best,
+        emulations,
+    } = search_gas_limit(used_gas, cap, best, move |gas_limit| async move {
+        emulate_with_gas_limit(storage, tx, step_limit, gas_limit)
+            .await
+            .map(|response| is_passed(&response, gas_limit).then_some(response))
+    })
+    .await?;
+
+    Ok(EstimateGasResponse {
+        gas_limit,
+        used_gas: best.used_gas,
+        steps_executed: best.steps_executed,
+        iterations: best.iterations,
+        cost: best.cost,
+        solana_accounts: best.solana_accounts,
+        // The first run is not counted by the search
+        emulations: emulations + 1,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Transaction uses `used_gas`, but fails with a gas limit below `required_gas`
+    async fn search(used_gas: u64, required_gas: u64, cap: u64) -> Estimate<u64> {
+        search_gas_limit(used_gas, cap, cap, |gas_limit| async move {
+            Ok((gas_limit >= required_gas).then_some(gas_limit))
+        })
+        .await
+        .unwrap()
+    }
+
+    fn assert_bounds(estimate: &Estimate<u64>, required_gas: u64) {
+        assert!(estimate.gas_limit >= required_gas);
+        assert_eq!(estimate.best, estimate.gas_limit);
+
+        let error = u128::from(estimate.gas_limit - required_gas) * 10_000;
+        assert!(error / u128::from(estimate.gas_limit) < ESTIMATE_GAS_ERROR_RATIO_BPS);
+    }
+
+    #[tokio::test]
+    async fn test_search_optimistic() {
+        let estimate = search(63_000, 63_000, u64::MAX).await;
+
+        // The optimistic limit passes, one more probe narrows the range below the error ratio
+        assert_bounds(&estimate, 63_000);
+        assert_eq!(estimate.gas_limit, 63_499);
+        assert_eq!(estimate.emulations, 2);
+    }
+
+    #[tokio::test]
+    async fn test_search_bounds() {
+        for (used_gas, required_gas, cap) in [
+            (21_000, 21_000, u64::MAX),
+            (100_000, 150_000, u64::MAX),
+            (100_000, 3_000_000, 10_000_000),
+            (1, 2, 3),
+        ] {
+            let estimate = search(used_gas, required_gas, cap).await;
+
+            assert_bounds(&estimate, required_gas);
+            assert!(estimate.gas_limit <= cap);
+            assert!(estimate.emulations < 100);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_search_passes_only_at_cap() {
+        let cap = 1_000_000;
+        let estimate = search(100_000, cap, cap).await;
+
+        assert_eq!(estimate.gas_limit, cap);
+        assert_eq!(estimate.best, cap);
+    }
+
+    #[tokio::test]
+    async fn test_search_used_gas_at_cap() {
+        let estimate = search(50_000, 50_000, 50_000).await;
+
+        assert_eq!(estimate.gas_limit, 50_000);
+        assert_eq!(estimate.emulations, 0);
+    }
+}
@@ -3,6 +3,7 @@
 use async_trait::async_trait;
 use base64::Engine;
 use enum_dispatch::enum_dispatch;
+use evm_loader::instruction_builder::tag;
 use solana_sdk::signer::Signer;
 use std::collections::BTreeMap;
 use tokio::sync::OnceCell;
@@ -202,14 +203,18 @@ impl ConfigSimulator<'_> {
     }
 
     async fn get_version(&mut self) -> NeonResult<(String, String)> {
-        let return_data = self.simulate_evm_instruction(0xA7, &[]).await?;
+        let return_data = self
+            .simulate_evm_instruction(tag::CONFIG_GET_VERSION, &[])
+            .await?;
         let (version, revision) = bincode::deserialize(&return_data)?;
 
         Ok((version, revision))
     }
 
     async fn get_status(&mut self) -> NeonResult<Status> {
-        let return_data = self.simulate_evm_instruction(0xA6, &[]).await?;
+        let return_data = self
+            .simulate_evm_instruction(tag::CONFIG_GET_STATUS, &[])
+            .await?;
         match return_data.first() {
             Some(0) => Ok(Status::Emergency),
             Some(1) => Ok(Status::Ok),
@@ -218,7 +223,9 @@ impl ConfigSimulator<'_> {
     }
 
     async fn get_environment(&mut self) -> NeonResult<String> {
-        let return_data = self.simulate_evm_instruction(0xA2, &[]).await?;
+        let return_data = self
+            .simulate_evm_instruction(tag::CONFIG_GET_ENVIRONMENT, &[])
+            .await?;
         let environment = String::from_utf8(return_data)?;
 
         Ok(environment)
@@ -227,13 +234,17 @@ impl ConfigSimulator<'_> {
     async fn get_chains(&mut self) -> NeonResult<Vec<ChainInfo>> {
         let mut result = Vec::new();
 
-        let return_data = self.simulate_evm_instruction(0xA0, &[]).await?;
+        let return_data = self
+            .simulate_evm_instruction(tag::CONFIG_GET_CHAIN_COUNT, &[])
+            .await?;
         let chain_count = return_data.as_slice().try_into()?;
         let chain_count = usize::from_le_bytes(chain_count);
 
         for i in 0..chain_count {
             let index = i.to_le_bytes();
-            let return_data = self.simulate_evm_instruction(0xA1, &index).await?;
+            let return_data = self
+                .simulate_evm_instruction(tag::CONFIG_GET_CHAIN_INFO, &index)
+                .await?;
 
             let (id, name, token) = bincode::deserialize(&return_data)?;
             result.push(ChainInfo { id, name, token });
@@ -245,13 +256,17 @@ impl ConfigSimulator<'_> {
     async fn get_properties(&mut self) -> NeonResult<BTreeMap<String, String>> {
         let mut result = BTreeMap::new();
 
-        let return_data = self.simulate_evm_instruction(0xA3, &[]).await?;
+        let return_data = self
+            .simulate_evm_instruction(tag::CONFIG_GET_PROPERTY_COUNT, &[])
+            .await?;
         let count = return_data.as_slice().try_into()?;
         let count = usize::from_le_bytes(count);
 
         for i in 0..count {
             let index = i.to_le_bytes();
-            let return_data = self.simulate_evm_instruction(0xA4, &index).await?;
+            let return_data = self
+                .simulate_evm_instruction(tag::CONFIG_GET_PROPERTY_BY_INDEX, &index)
+                .await?;
 
             let (name, value) = bincode::deserialize(&return_data)?;
             result.insert(name, value);
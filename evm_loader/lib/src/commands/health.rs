@@ -0,0 +1,145 @@
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::commands::get_config::{self, Status};
+use crate::rpc::{CloneRpcClient, Rpc};
+use crate::types::tracer_ch_common::EthSyncStatus;
+use crate::types::TracerDb;
+use crate::NeonResult;
+
+#[skip_serializing_none]
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SolanaHealth {
+    pub ok: bool,
+    pub slot: Option<u64>,
+    pub error: Option<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct TracerDbHealth {
+    pub ok: bool,
+    pub latest_block: Option<u64>,
+    /// Number of slots the tracer DB is behind Solana RPC
+    pub slot_lag: Option<u64>,
+    pub syncing: Option<bool>,
+    pub error: Option<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct EvmConfigHealth {
+    pub ok: bool,
+    pub version: Option<String>,
+    pub revision: Option<String>,
+    pub status: Option<Status>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct HealthResponse {
+    /// All upstream dependencies are available
+    pub ready: bool,
+    pub solana: SolanaHealth,
+    pub tracer_db: TracerDbHealth,
+    pub evm_config: EvmConfigHealth,
+}
+
+async fn check_solana(rpc_client: &CloneRpcClient) -> SolanaHealth {
+    match Rpc::get_slot(rpc_client).await {
+        Ok(slot) => SolanaHealth {
+            ok: true,
+            slot: Some(slot),
+            error: None,
+        },
+        Err(e) => SolanaHealth {
+            ok: false,
+            slot: None,
+            error: Some(e.to_string()),
+        },
+    }
+}
+
+async fn check_tracer_db(
+    tracer_db: &TracerDb,
+    solana_slot: Option<u64>,
+    max_slot_lag: u64,
+) -> TracerDbHealth {
+    let latest_block = match tracer_db.get_latest_block().await {
+        Ok(latest_block) => latest_block,
+        Err(e) => {
+            return TracerDbHealth {
+                error: Some(e.to_string()),
+                ..TracerDbHealth::default()
+            }
+        }
+    };
+
+    let syncing = match tracer_db.get_sync_status().await {
+        Ok(EthSyncStatus::Syncing(_)) => true,
+        Ok(EthSyncStatus::Synced) => false,
+        Err(e) => {
+            return TracerDbHealth {
+                latest_block: Some(latest_block),
+                error: Some(e.to_string()),
+                ..TracerDbHealth::default()
+            }
+        }
+    };
+
+    let slot_lag = solana_slot.map(|slot| slot.saturating_sub(latest_block));
+    let is_lagging = slot_lag.map_or(false, |lag| lag > max_slot_lag);
+
+    let error = if syncing {
+        Some("tracer db is syncing".to_string())
+    } else if is_lagging {
+        Some(format!(
+            "tracer db is more than {max_slot_lag} slots behind"
+        ))
+    } else {
+        None
+    };
+
+    TracerDbHealth {
+        ok: error.is_none(),
+        latest_block: Some(latest_block),
+        slot_lag,
+        syncing: Some(syncing),
+        error,
+    }
+}
+
+async fn check_evm_config(rpc_client: &CloneRpcClient, program_id: Pubkey) -> EvmConfigHealth {
+    match get_config::execute(rpc_client, program_id).await {
+        Ok(config) => EvmConfigHealth {
+            ok: true,
+            version: Some(config.version),
+            revision: Some(config.revision),
+            status: Some(config.status),
+            error: None,
+        },
+        Err(e) => EvmConfigHealth {
+            error: Some(e.to_string()),
+            ..EvmConfigHealth::default()
+        },
+    }
+}
+
+pub async fn execute(
+    rpc_client: &CloneRpcClient,
+    tracer_db: &TracerDb,
+    program_id: Pubkey,
+    max_slot_lag: u64,
+) -> NeonResult<HealthResponse> {
+    let solana = check_solana(rpc_client).await;
+    let tracer_db = check_tracer_db(tracer_db, solana.slot, max_slot_lag).await;
+    let evm_config = check_evm_config(rpc_client, program_id).await;
+
+    Ok(HealthResponse {
+        ready: solana.ok && tracer_db.ok && evm_config.ok,
+        solana,
+        tracer_db,
+        evm_config,
+    })
+}
@@ -0,0 +1,566 @@
+//! Management of the operator holder accounts: creation, transaction upload, deletion and
+//! cleanup of the holders left by abandoned transactions.
+
+use std::collections::BTreeSet;
+use std::ops::Deref;
+
+use evm_loader::account::{
+    legacy::{TAG_HOLDER_DEPRECATED, TAG_STATE_FINALIZED_DEPRECATED},
+    StateAccount, ACCOUNT_PREFIX_LEN, TAG_HOLDER, TAG_STATE, TAG_STATE_FINALIZED,
+};
+use evm_loader::config::HOLDER_MSG_SIZE;
+use evm_loader::instruction_builder;
+use evm_loader::types::{Address, Transaction as NeonTransaction};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, skip_serializing_none, DisplayFromStr};
+use solana_account_decoder::{UiAccountEncoding, UiDataSliceConfig};
+use solana_client::{
+    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+    rpc_filter::{Memcmp, RpcFilterType},
+};
+use solana_sdk::{
+    account::Account,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signer::Signer,
+    system_instruction,
+};
+
+use crate::account_storage::account_info;
+use crate::commands::get_config::read_chains;
+use crate::commands::get_holder::{read_holder, GetHolderResponse, Status};
+use crate::commands::send_neon_transaction::with_compute_budget;
+use crate::commands::send_transaction;
+use crate::errors::NeonError;
+use crate::rpc::CloneRpcClient;
+use crate::{Config, NeonResult};
+
+pub const DEFAULT_HOLDER_SEED: &str = "holder";
+pub const HOLDER_SIZE: usize = 256 * 1024;
+/// Transaction bytes per holder write, the same size the gas cost of the transaction assumes
+#[allow(clippy::cast_possible_truncation)] // HOLDER_MSG_SIZE < usize::max
+pub const HOLDER_CHUNK_SIZE: usize = HOLDER_MSG_SIZE as usize;
+
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HolderReturn {
+    #[serde_as(as = "DisplayFromStr")]
+    pub holder: Pubkey,
+    pub transactions: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HolderAction {
+    Canceled,
+    Deleted,
+    CanceledAndDeleted,
+}
+
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HolderInfo {
+    #[serde_as(as = "DisplayFromStr")]
+    pub pubkey: Pubkey,
+    pub lamports: u64,
+    #[serde(flatten)]
+    pub holder: GetHolderResponse,
+    pub action: Option<HolderAction>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HolderListReturn {
+    pub holders: Vec<HolderInfo>,
+    pub transactions: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct HolderCleanupConfig {
+    /// Cancel transactions in the active holders
+    pub cancel: bool,
+    /// Delete finalized holders
+    pub delete: bool,
+    /// Address of the operator balance which pays for cancels, zero address by default
+    pub operator_address: Option<Address>,
+}
+
+/// Instructions creating the holder account of the operator
+pub(crate) async fn create_instructions(
+    config: &Config,
+    rpc_client: &CloneRpcClient,
+    operator: &Pubkey,
+    seed: &str,
+    size: usize,
+) -> NeonResult<Vec<Instruction>> {
+    let holder = instruction_builder::holder_address(&config.evm_loader, operator, seed);
+    let lamports = rpc_client
+        .get_minimum_balance_for_rent_exemption(size)
+        .await?;
+
+    Ok(vec![
+        system_instruction::create_account_with_seed(
+            operator,
+            &holder,
+            operator,
+            seed,
+            lamports,
+            size as u64,
+            &config.evm_loader,
+        ),
+        instruction_builder::holder_create(&config.evm_loader, &holder, operator, seed),
+    ])
+}
+
+/// Instructions writing the transaction into the holder, one instruction per Solana transaction
+pub(crate) fn write_instructions(
+    program_id: &Pubkey,
+    holder: &Pubkey,
+    operator: &Pubkey,
+    hash: &[u8; 32],
+    raw_transaction: &[u8],
+) -> Vec<Instruction> {
+    raw_transaction
+        .chunks(HOLDER_CHUNK_SIZE)
+        .enumerate()
+        .map(|(index, chunk)| {
+            instruction_builder::holder_write(
+                program_id,
+                holder,
+                operator,
+                hash,
+                index * HOLDER_CHUNK_SIZE,
+                chunk,
+            )
+        })
+        .collect()
+}
+
+/// Instruction canceling the transaction in the active holder.
+/// All accounts locked by the transaction are passed as writable.
+pub(crate) async fn cancel_instruction(
+    config: &Config,
+    rpc_client: &CloneRpcClient,
+    operator: &Pubkey,
+    operator_address: Option<Address>,
+    holder: &Pubkey,
+    state: &GetHolderResponse,
+) -> NeonResult<Instruction> {
+    let (Some(hash), Some(accounts)) = (state.tx, state.accounts.as_ref()) else {
+        return Err(NeonError::InvalidParameters(format!(
+            "Holder {holder} doesn't contain an active transaction"
+        )));
+    };
+
+    let chain_id = match state.chain_id {
+        Some(chain_id) => chain_id,
+        None => read_chains(rpc_client, config.evm_loader)
+            .await?
+            .into_iter()
+            .find(|chain| chain.name == "neon")
+            .map(|chain| chain.id)
+            .ok_or(NeonError::IncorrectProgram(config.evm_loader))?,
+    };
+    let (operator_balance, _) = operator_address.unwrap_or_default().find_operator_address(
+        &config.evm_loader,
+        chain_id,
+        operator,
+    );
+
+    let metas: Vec<AccountMeta> = accounts
+        .iter()
+        .map(|pubkey| AccountMeta::new(*pubkey, false))
+        .collect();
+
+    Ok(instruction_builder::cancel(
+        &config.evm_loader,
+        holder,
+        operator,
+        &operator_balance,
+        &hash,
+        &metas,
+    ))
+}
+
+async fn read(
+    config: &Config,
+    rpc_client: &CloneRpcClient,
+    holder: &Pubkey,
+) -> NeonResult<Option<GetHolderResponse>> {
+    let Some(mut account) = rpc_client
+        .get_account_with_commitment(holder, config.commitment)
+        .await?
+        .value
+    else {
+        return Ok(None);
+    };
+
+    let info = account_info(holder, &mut account);
+    read_holder(&config.evm_loader, info).map(Some)
+}
+
+async fn send_one_by_one(
+    rpc_client: &CloneRpcClient,
+    signer: &dyn Signer,
+    transactions: Vec<Vec<Instruction>>,
+) -> NeonResult<Vec<String>> {
+    let mut signatures = Vec::with_capacity(transactions.len());
+    for instructions in transactions {
+        let signature = send_transaction(rpc_client, signer, &instructions).await?;
+        info!("{signature}");
+        signatures.push(signature.to_string());
+    }
+
+    Ok(signatures)
+}
+
+/// Creates the holder account of the signer. Existing holder is skipped.
+pub async fn create(
+    config: &Config,
+    rpc_client: &CloneRpcClient,
+    signer: &dyn Signer,
+    seed: &str,
+    size: Option<usize>,
+) -> NeonResult<HolderReturn> {
+    let operator = signer.pubkey();
+    let holder = instruction_builder::holder_address(&config.evm_loader, &operator, seed);
+
+    if read(config, rpc_client, &holder).await?.is_some() {
+        info!("Holder {holder} already exists");
+        return Ok(HolderReturn {
+            holder,
+            transactions: vec![],
+        });
+    }
+
+    let instructions = create_instructions(
+        config,
+        rpc_client,
+        &operator,
+        seed,
+        size.unwrap_or(HOLDER_SIZE),
+    )
+    .await?;
+    let transactions = send_one_by_one(rpc_client, signer, vec![instructions]).await?;
+
+    Ok(HolderReturn {
+        holder,
+        transactions,
+    })
+}
+
+/// Writes the signed Neon transaction into the holder of the signer
+pub async fn write(
+    config: &Config,
+    rpc_client: &CloneRpcClient,
+    signer: &dyn Signer,
+    seed: &str,
+    raw_transaction: &[u8],
+) -> NeonResult<HolderReturn> {
+    let operator = signer.pubkey();
+    let holder = instruction_builder::holder_address(&config.evm_loader, &operator, seed);
+    let hash = NeonTransaction::from_rlp(raw_transaction)?.hash();
+
+    match read(config, rpc_client, &holder).await? {
+        None => {
+            return Err(NeonError::InvalidParameters(format!(
+                "Holder {holder} doesn't exist"
+            )))
+        }
+        Some(GetHolderResponse {
+            status: Status::Active,
+            ..
+        }) => {
+            return Err(NeonError::InvalidParameters(format!(
+                "Holder {holder} is locked by an active transaction"
+            )))
+        }
+        Some(_) => {}
+    }
+
+    let transactions = write_instructions(
+        &config.evm_loader,
+        &holder,
+        &operator,
+        &hash,
+        raw_transaction,
+    )
+    .into_iter()
+    .map(|instruction| vec![instruction])
+    .collect();
+    let transactions = send_one_by_one(rpc_client, signer, transactions).await?;
+
+    Ok(HolderReturn {
+        holder,
+        transactions,
+    })
+}
+
+/// Deletes the holder of the signer, the rent is returned to the signer
+pub async fn delete(
+    config: &Config,
+    rpc_client: &CloneRpcClient,
+    signer: &dyn Signer,
+    seed: &str,
+) -> NeonResult<HolderReturn> {
+    let operator = signer.pubkey();
+    let holder = instruction_builder::holder_address(&config.evm_loader, &operator, seed);
+
+    let instruction = instruction_builder::holder_delete(&config.evm_loader, &holder, &operator);
+    let transactions = send_one_by_one(rpc_client, signer, vec![vec![instruction]]).await?;
+
+    Ok(HolderReturn {
+        holder,
+        transactions,
+    })
+}
+
+pub(crate) async fn program_accounts(
+    config: &Config,
+    rpc_client: &CloneRpcClient,
+    tag: u8,
+    owner: Option<(usize, &Pubkey)>,
+) -> NeonResult<Vec<(Pubkey, Account)>> {
+    program_accounts_slice(config, rpc_client, tag, owner, None).await
+}
+
+async fn program_accounts_slice(
+    config: &Config,
+    rpc_client: &CloneRpcClient,
+    tag: u8,
+    owner: Option<(usize, &Pubkey)>,
+    data_slice: Option<UiDataSliceConfig>,
+) -> NeonResult<Vec<(Pubkey, Account)>> {
+    let mut filters = vec![RpcFilterType::Memcmp(Memcmp::new_raw_bytes(0, vec![tag]))];
+    if let Some((offset, owner)) = owner {
+        filters.push(RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+            offset,
+            owner.to_bytes().to_vec(),
+        )));
+    }
+
+    let accounts = rpc_client
+        .deref()
+        .get_program_accounts_with_config(
+            &config.evm_loader,
+            RpcProgramAccountsConfig {
+                filters: Some(filters),
+                account_config: RpcAccountInfoConfig {
+                    encoding: Some(UiAccountEncoding::Base64),
+                    data_slice,
+                    commitment: Some(config.commitment),
+                    ..RpcAccountInfoConfig::default()
+                },
+                ..RpcProgramAccountsConfig::default()
+            },
+        )
+        .await?;
+
+    Ok(accounts)
+}
+
+/// Distinct offsets of the serialized data in the active state accounts.
+/// The accounts contain only the beginning of the data with the state header.
+fn state_data_offsets(accounts: Vec<(Pubkey, Account)>) -> BTreeSet<usize> {
+    accounts
+        .into_iter()
+        .filter(|(_, account)| account.data.len() >= StateAccount::HEADER_LEN)
+        .map(|(pubkey, mut account)| {
+            let info = account_info(&pubkey, &mut account);
+            StateAccount::data_offset(&info)
+        })
+        .collect()
+}
+
+/// Lists holder accounts of the operator: idle holders, active and finalized transactions
+pub async fn list(
+    config: &Config,
+    rpc_client: &CloneRpcClient,
+    operator: &Pubkey,
+) -> NeonResult<Vec<HolderInfo>> {
+    // Owner offset in the account data
+    let tags = [
+        (TAG_HOLDER, ACCOUNT_PREFIX_LEN),
+        (TAG_STATE_FINALIZED, ACCOUNT_PREFIX_LEN),
+        (TAG_HOLDER_DEPRECATED, 1),
+        (TAG_STATE_FINALIZED_DEPRECATED, 1),
+    ];
+
+    let mut accounts = Vec::new();
+    for (tag, offset) in tags {
+        let owner = Some((offset, operator));
+        accounts.extend(program_accounts(config, rpc_client, tag, owner).await?);
+    }
+
+    // The owner of the active state is serialized together with the transaction,
+    // its offset is read from the state headers first
+    let headers = program_accounts_slice(
+        config,
+        rpc_client,
+        TAG_STATE,
+        None,
+        Some(UiDataSliceConfig {
+            offset: 0,
+            length: StateAccount::HEADER_LEN,
+        }),
+    )
+    .await?;
+    for offset in state_data_offsets(headers) {
+        let owner = Some((offset, operator));
+        accounts.extend(program_accounts(config, rpc_client, TAG_STATE, owner).await?);
+    }
+
+    let mut holders = Vec::new();
+    for (pubkey, mut account) in accounts {
+        let lamports = account.lamports;
+        let info = account_info(&pubkey, &mut account);
+        let holder = match read_holder(&config.evm_loader, info) {
+            Ok(holder) if holder.owner == Some(*operator) => holder,
+            Ok(_) => continue,
+            Err(e) => GetHolderResponse::error(e),
+        };
+
+        holders.push(HolderInfo {
+            pubkey,
+            lamports,
+            holder,
+            action: None,
+        });
+    }
+    holders.sort_unstable_by_key(|holder| holder.pubkey);
+
+    Ok(holders)
+}
+
+/// Lists holder accounts of the signer, cancels active transactions and deletes
+/// finalized holders according to the `cleanup` config
+pub async fn cleanup(
+    config: &Config,
+    rpc_client: &CloneRpcClient,
+    signer: &dyn Signer,
+    cleanup: &HolderCleanupConfig,
+) -> NeonResult<HolderListReturn> {
+    let operator = signer.pubkey();
+    let mut holders = list(config, rpc_client, &operator).await?;
+    let mut transactions = Vec::new();
+
+    for holder in &mut holders {
+        let mut finalized = matches!(holder.holder.status, Status::Finalized);
+
+        if cleanup.cancel && matches!(holder.holder.status, Status::Active) {
+            let instruction = cancel_instruction(
+                config,
+                rpc_client,
+                &operator,
+                cleanup.operator_address,
+                &holder.pubkey,
+                &holder.holder,
+            )
+            .await?;
+
+            match send_transaction(rpc_client, signer, &with_compute_budget(instruction)).await {
+                Ok(signature) => {
+                    info!("Cancel {}: {signature}", holder.pubkey);
+                    transactions.push(signature.to_string());
+                    holder.action = Some(HolderAction::Canceled);
+                    finalized = true;
+                }
+                Err(e) => warn!("Failed to cancel {}: {e}", holder.pubkey),
+            }
+        }
+
+        if cleanup.delete && finalized {
+            let instruction =
+                instruction_builder::holder_delete(&config.evm_loader, &holder.pubkey, &operator);
+
+            match send_transaction(rpc_client, signer, &[instruction]).await {
+                Ok(signature) => {
+                    info!("Delete {}: {signature}", holder.pubkey);
+                    transactions.push(signature.to_string());
+                    holder.action = Some(match holder.action {
+                        Some(HolderAction::Canceled) => HolderAction::CanceledAndDeleted,
+                        _ => HolderAction::Deleted,
+                    });
+                }
+                Err(e) => warn!("Failed to delete {}: {e}", holder.pubkey),
+            }
+        }
+    }
+
+    Ok(HolderListReturn {
+        holders,
+        transactions,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state_header(evm_state_len: usize, evm_machine_len: usize) -> (Pubkey, Account) {
+        let mut data = vec![TAG_STATE, 0];
+        data.extend(evm_state_len.to_le_bytes());
+        data.extend(evm_machine_len.to_le_bytes());
+        data.extend(1000_usize.to_le_bytes());
+
+        let account = Account {
+            lamports: 1_000_000,
+            data,
+            owner: Pubkey::new_unique(),
+            executable: false,
+            rent_epoch: 0,
+        };
+
+        (Pubkey::new_unique(), account)
+    }
+
+    #[test]
+    fn test_state_data_offsets() {
+        let (short, mut account) = state_header(1, 1);
+        account.data.truncate(StateAccount::HEADER_LEN - 1);
+
+        let offsets = state_data_offsets(vec![
+            state_header(100, 50),
+            state_header(10, 0),
+            state_header(50, 100),
+            (short, account),
+        ]);
+
+        let header_len = StateAccount::HEADER_LEN;
+        assert_eq!(
+            header_len,
+            ACCOUNT_PREFIX_LEN + 3 * std::mem::size_of::<usize>()
+        );
+        assert_eq!(
+            offsets.into_iter().collect::<Vec<_>>(),
+            vec![header_len + 10, header_len + 150]
+        );
+    }
+
+    #[test]
+    fn test_write_instructions() {
+        let (program_id, holder, operator) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+
+        for len in [
+            1,
+            HOLDER_CHUNK_SIZE,
+            HOLDER_CHUNK_SIZE + 1,
+            10 * HOLDER_CHUNK_SIZE,
+        ] {
+            let writes =
+                write_instructions(&program_id, &holder, &operator, &[0; 32], &vec![1; len]);
+
+            // Operator pays the holder writes estimated by the gas of the transaction
+            let cost = evm_loader::gasometer::write_to_holder_cost(len);
+            assert_eq!(
+                cost,
+                writes.len() as u64 * evm_loader::gasometer::LAMPORTS_PER_SIGNATURE
+            );
+        }
+    }
+}
@@ -18,20 +18,18 @@ use {
     },
     evm_loader::{
         account::{MainTreasury, Treasury},
-        config::TREASURY_POOL_SEED,
+        config::{DEPOSIT_AUTHORITY_SEED, TREASURY_POOL_SEED},
+        instruction_builder,
     },
     log::{error, info, warn},
     solana_sdk::{
-        bpf_loader_upgradeable,
-        instruction::{AccountMeta, Instruction},
         program_pack::Pack,
         pubkey::Pubkey,
         signer::keypair::{read_keypair_file, Keypair},
         signer::Signer,
-        system_instruction, system_program,
+        system_instruction,
     },
-    spl_associated_token_account::get_associated_token_address,
-    spl_token::{self, native_mint},
+    spl_associated_token_account::get_associated_token_address_with_program_id,
     std::collections::HashMap,
     std::path::Path,
     thiserror::Error,
@@ -124,11 +122,6 @@ pub async fn execute(
     let executor = Rc::new(TransactionExecutor::new(client, fee_payer, send_trx));
     let keys = keys_dir.map_or(Ok(HashMap::new()), read_keys_dir)?;
 
-    let program_data_address = Pubkey::find_program_address(
-        &[&config.evm_loader.to_bytes()],
-        &bpf_loader_upgradeable::id(),
-    )
-    .0;
     let (program_upgrade_authority, program_data) =
         read_program_data_from_account(config, client, &config.evm_loader).await?;
     let data = file.map_or(Ok(program_data), read_program_data)?;
@@ -202,16 +195,26 @@ pub async fn execute(
     executor.checkpoint(config.commitment).await?;
 
     //====================== Create 'Deposit' NEON-token balance ======================================================
-    let (deposit_authority, _) = Pubkey::find_program_address(&[b"Deposit"], &config.evm_loader);
+    let (deposit_authority, _) =
+        Pubkey::find_program_address(&[DEPOSIT_AUTHORITY_SEED.as_bytes()], &config.evm_loader);
     let chains = super::get_config::read_chains(client, config.evm_loader).await?;
     for chain in chains {
-        let pool = get_associated_token_address(&deposit_authority, &chain.token);
+        // Chain token can be either SPL Token or SPL Token-2022 mint
+        let token_program = executor
+            .get_account(&chain.token)
+            .await?
+            .map_or_else(spl_token::id, |mint| mint.owner);
+        let pool = get_associated_token_address_with_program_id(
+            &deposit_authority,
+            &chain.token,
+            &token_program,
+        );
 
         executor
             .check_and_create_object(
                 "Token pool account",
                 executor
-                    .get_account_data_pack::<spl_token::state::Account>(&spl_token::id(), &pool)
+                    .get_account_data_pack::<spl_token::state::Account>(&token_program, &pool)
                     .await,
                 |account| async move {
                     if account.mint != chain.token || account.owner != deposit_authority {
@@ -227,7 +230,7 @@ pub async fn execute(
                             &executor.fee_payer.pubkey(),
                             &deposit_authority,
                             &chain.token,
-                            &spl_token::id(),
+                            &token_program,
                         ),
                     ])
                     .await?;
@@ -258,18 +261,10 @@ pub async fn execute(
                 }
                 let transaction = executor
                     .create_transaction(
-                        &[Instruction::new_with_bincode(
-                            config.evm_loader,
-                            &(0x29_u8), // evm_loader::instruction::EvmInstruction::CreateMainTreasury
-                            vec![
-                                AccountMeta::new(main_balance_address, false),
-                                AccountMeta::new_readonly(program_data_address, false),
-                                AccountMeta::new_readonly(signer.pubkey(), true),
-                                AccountMeta::new_readonly(spl_token::id(), false),
-                                AccountMeta::new_readonly(system_program::id(), false),
-                                AccountMeta::new_readonly(native_mint::id(), false),
-                                AccountMeta::new(executor.fee_payer.pubkey(), true),
-                            ],
+                        &[instruction_builder::create_main_treasury(
+                            &config.evm_loader,
+                            &signer.pubkey(),
+                            &executor.fee_payer.pubkey(),
                         )],
                         &[signer],
                     )
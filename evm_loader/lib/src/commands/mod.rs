@@ -11,16 +11,26 @@ use solana_sdk::{
     transaction::Transaction,
 };
 
+pub mod alt_plan;
 pub mod collect_treasury;
 pub mod emulate;
+pub mod emulate_batch;
+pub mod emulate_exact;
+pub mod estimate_gas;
 pub mod get_balance;
 pub mod get_config;
 pub mod get_contract;
 pub mod get_holder;
 pub mod get_neon_elf;
 pub mod get_storage_at;
+pub mod health;
+pub mod holder;
 pub mod init_environment;
+pub mod operator_balance;
+pub mod operator_registry;
+pub mod send_neon_transaction;
 pub mod simulate_solana;
+pub mod stuck_transactions;
 pub mod trace;
 mod transaction_executor;
 
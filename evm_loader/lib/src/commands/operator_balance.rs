@@ -0,0 +1,348 @@
+use std::collections::BTreeMap;
+use std::ops::Deref;
+
+use ethnum::U256;
+use evm_loader::account::{OperatorBalanceAccount, ACCOUNT_PREFIX_LEN, TAG_OPERATOR_BALANCE};
+use evm_loader::instruction_builder;
+use evm_loader::types::Address;
+use log::info;
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, skip_serializing_none, DisplayFromStr};
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::{
+    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+    rpc_filter::{Memcmp, RpcFilterType},
+};
+use solana_sdk::{account::Account, instruction::Instruction, pubkey::Pubkey, signer::Signer};
+
+use crate::account_storage::account_info;
+use crate::commands::get_config::read_chains;
+use crate::commands::send_transaction;
+use crate::errors::NeonError;
+use crate::rpc::CloneRpcClient;
+use crate::{Config, NeonResult};
+
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OperatorBalanceInfo {
+    #[serde_as(as = "DisplayFromStr")]
+    pub pubkey: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub operator: Pubkey,
+    pub address: Address,
+    pub chain_id: u64,
+    /// Name of the chain, `None` for chains unknown to the program
+    pub chain: Option<String>,
+    pub balance: U256,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChainBalance {
+    pub chain_id: u64,
+    pub chain: Option<String>,
+    pub balance: U256,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OperatorBalanceListReturn {
+    pub accounts: Vec<OperatorBalanceInfo>,
+    /// Accrued balance of all listed accounts per chain
+    pub totals: Vec<ChainBalance>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OperatorBalanceReturn {
+    /// `None` if there was nothing to do
+    pub transaction: Option<String>,
+}
+
+fn read_info(
+    program_id: &Pubkey,
+    pubkey: Pubkey,
+    mut account: Account,
+) -> NeonResult<OperatorBalanceInfo> {
+    let info = account_info(&pubkey, &mut account);
+    let operator_balance = OperatorBalanceAccount::from_account(program_id, &info)?;
+
+    Ok(OperatorBalanceInfo {
+        pubkey,
+        operator: operator_balance.owner(),
+        address: operator_balance.address(),
+        chain_id: operator_balance.chain_id(),
+        chain: None,
+        balance: operator_balance.balance(),
+    })
+}
+
+async fn chain_names(
+    config: &Config,
+    rpc_client: &CloneRpcClient,
+) -> NeonResult<BTreeMap<u64, String>> {
+    let chains = read_chains(rpc_client, config.evm_loader).await?;
+
+    Ok(chains
+        .into_iter()
+        .map(|chain| (chain.id, chain.name))
+        .collect())
+}
+
+async fn send(
+    rpc_client: &CloneRpcClient,
+    signer: &dyn Signer,
+    instructions: &[Instruction],
+) -> NeonResult<OperatorBalanceReturn> {
+    let signature = send_transaction(rpc_client, signer, instructions).await?;
+    info!("{signature}");
+
+    Ok(OperatorBalanceReturn {
+        transaction: Some(signature.to_string()),
+    })
+}
+
+/// Reads the operator balance account, `None` if it doesn't exist
+pub async fn show(
+    config: &Config,
+    rpc_client: &CloneRpcClient,
+    operator: Pubkey,
+    address: Address,
+    chain_id: u64,
+) -> NeonResult<Option<OperatorBalanceInfo>> {
+    let (pubkey, _) = address.find_operator_address(&config.evm_loader, chain_id, &operator);
+    let Some(account) = rpc_client
+        .get_account_with_commitment(&pubkey, config.commitment)
+        .await?
+        .value
+    else {
+        return Ok(None);
+    };
+
+    let mut info = read_info(&config.evm_loader, pubkey, account)?;
+    info.chain = chain_names(config, rpc_client).await?.remove(&chain_id);
+
+    Ok(Some(info))
+}
+
+/// Creates the operator balance account of the signer. Existing account is skipped.
+pub async fn create(
+    config: &Config,
+    rpc_client: &CloneRpcClient,
+    signer: &dyn Signer,
+    address: Address,
+    chain_id: u64,
+) -> NeonResult<OperatorBalanceReturn> {
+    let operator = signer.pubkey();
+    if !chain_names(config, rpc_client)
+        .await?
+        .contains_key(&chain_id)
+    {
+        return Err(NeonError::InvalidParameters(format!(
+            "Unknown chain id {chain_id}"
+        )));
+    }
+
+    if let Some(info) = show(config, rpc_client, operator, address, chain_id).await? {
+        info!("Operator balance {} already exists", info.pubkey);
+        return Ok(OperatorBalanceReturn { transaction: None });
+    }
+
+    let instruction = instruction_builder::operator_balance_create(
+        &config.evm_loader,
+        &operator,
+        address,
+        chain_id,
+    );
+    send(rpc_client, signer, &[instruction]).await
+}
+
+/// Instructions moving the accrued balance to the user balance of the same address and chain.
+/// The user balance account is created if it doesn't exist.
+async fn withdraw_instructions(
+    config: &Config,
+    rpc_client: &CloneRpcClient,
+    operator: &Pubkey,
+    info: &OperatorBalanceInfo,
+) -> NeonResult<Vec<Instruction>> {
+    let mut instructions = Vec::with_capacity(2);
+
+    let (balance, _) = info
+        .address
+        .find_balance_address(&config.evm_loader, info.chain_id);
+    let balance_exists = rpc_client
+        .get_account_with_commitment(&balance, config.commitment)
+        .await?
+        .value
+        .is_some_and(|account| account.owner == config.evm_loader);
+    if !balance_exists {
+        instructions.push(instruction_builder::account_create_balance(
+            &config.evm_loader,
+            operator,
+            info.address,
+            info.chain_id,
+        ));
+    }
+
+    instructions.push(instruction_builder::operator_balance_withdraw(
+        &config.evm_loader,
+        operator,
+        info.address,
+        info.chain_id,
+    ));
+
+    Ok(instructions)
+}
+
+/// Withdraws the accrued balance of the signer's operator balance account
+pub async fn withdraw(
+    config: &Config,
+    rpc_client: &CloneRpcClient,
+    signer: &dyn Signer,
+    address: Address,
+    chain_id: u64,
+) -> NeonResult<OperatorBalanceReturn> {
+    let operator = signer.pubkey();
+    let info = show(config, rpc_client, operator, address, chain_id)
+        .await?
+        .ok_or_else(|| {
+            NeonError::InvalidParameters(format!(
+                "Operator balance {address} (chain {chain_id}) doesn't exist"
+            ))
+        })?;
+
+    if info.balance == 0 {
+        info!("Nothing to withdraw from {}", info.pubkey);
+        return Ok(OperatorBalanceReturn { transaction: None });
+    }
+
+    info!("Withdraw {} from {}", info.balance, info.pubkey);
+    let instructions = withdraw_instructions(config, rpc_client, &operator, &info).await?;
+    send(rpc_client, signer, &instructions).await
+}
+
+/// Deletes the signer's operator balance account, the accrued balance is withdrawn in the same transaction
+pub async fn delete(
+    config: &Config,
+    rpc_client: &CloneRpcClient,
+    signer: &dyn Signer,
+    address: Address,
+    chain_id: u64,
+) -> NeonResult<OperatorBalanceReturn> {
+    let operator = signer.pubkey();
+    let Some(info) = show(config, rpc_client, operator, address, chain_id).await? else {
+        info!("Operator balance {address} (chain {chain_id}) doesn't exist");
+        return Ok(OperatorBalanceReturn { transaction: None });
+    };
+
+    let mut instructions = if info.balance > 0 {
+        info!("Withdraw {} from {}", info.balance, info.pubkey);
+        withdraw_instructions(config, rpc_client, &operator, &info).await?
+    } else {
+        Vec::with_capacity(1)
+    };
+    instructions.push(instruction_builder::operator_balance_delete(
+        &config.evm_loader,
+        &operator,
+        address,
+        chain_id,
+    ));
+
+    send(rpc_client, signer, &instructions).await
+}
+
+/// Lists operator balance accounts of the operators with the accrued balances per chain
+pub async fn list(
+    config: &Config,
+    rpc_client: &CloneRpcClient,
+    operators: &[Pubkey],
+) -> NeonResult<OperatorBalanceListReturn> {
+    let chains = chain_names(config, rpc_client).await?;
+
+    let mut accounts = Vec::new();
+    for operator in operators {
+        let filters = vec![
+            RpcFilterType::DataSize(OperatorBalanceAccount::required_account_size() as u64),
+            RpcFilterType::Memcmp(Memcmp::new_raw_bytes(0, vec![TAG_OPERATOR_BALANCE])),
+            RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+                ACCOUNT_PREFIX_LEN,
+                operator.to_bytes().to_vec(),
+            )),
+        ];
+        let operator_accounts = rpc_client
+            .deref()
+            .get_program_accounts_with_config(
+                &config.evm_loader,
+                RpcProgramAccountsConfig {
+                    filters: Some(filters),
+                    account_config: RpcAccountInfoConfig {
+                        encoding: Some(UiAccountEncoding::Base64),
+                        commitment: Some(config.commitment),
+                        ..RpcAccountInfoConfig::default()
+                    },
+                    ..RpcProgramAccountsConfig::default()
+                },
+            )
+            .await?;
+
+        for (pubkey, account) in operator_accounts {
+            let Ok(mut info) = read_info(&config.evm_loader, pubkey, account) else {
+                continue;
+            };
+            info.chain = chains.get(&info.chain_id).cloned();
+            accounts.push(info);
+        }
+    }
+    accounts.sort_unstable_by_key(|info| (info.operator, info.chain_id, info.address));
+
+    let mut totals = BTreeMap::<u64, U256>::new();
+    for info in &accounts {
+        let total = totals.entry(info.chain_id).or_default();
+        *total = total.saturating_add(info.balance);
+    }
+    let totals = totals
+        .into_iter()
+        .map(|(chain_id, balance)| ChainBalance {
+            chain_id,
+            chain: chains.get(&chain_id).cloned(),
+            balance,
+        })
+        .collect();
+
+    Ok(OperatorBalanceListReturn { accounts, totals })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_info() {
+        let program_id = Pubkey::new_unique();
+        let operator = Pubkey::new_unique();
+        let address = Address::from([0x11; 20]);
+        let (pubkey, _) = address.find_operator_address(&program_id, 111, &operator);
+
+        let mut data = vec![TAG_OPERATOR_BALANCE, 2];
+        data.extend_from_slice(operator.as_ref());
+        data.extend_from_slice(address.as_bytes());
+        data.extend_from_slice(&111_u64.to_le_bytes());
+        data.extend_from_slice(&U256::new(1_000).to_le_bytes());
+        assert_eq!(data.len(), OperatorBalanceAccount::required_account_size());
+
+        let account = Account {
+            lamports: 1,
+            data,
+            owner: program_id,
+            ..Account::default()
+        };
+
+        let info = read_info(&program_id, pubkey, account.clone()).unwrap();
+        assert_eq!(info.operator, operator);
+        assert_eq!(info.address, address);
+        assert_eq!(info.chain_id, 111);
+        assert_eq!(info.balance, 1_000);
+
+        assert!(read_info(&Pubkey::new_unique(), pubkey, account).is_err());
+    }
+}
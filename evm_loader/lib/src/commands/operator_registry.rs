@@ -0,0 +1,259 @@
+use std::ops::Deref;
+
+use evm_loader::account::{OperatorRecord, TAG_OPERATOR_RECORD};
+use evm_loader::instruction_builder;
+use log::info;
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, DisplayFromStr};
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::{
+    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+    rpc_filter::{Memcmp, RpcFilterType},
+};
+use solana_sdk::{account::Account, instruction::Instruction, pubkey::Pubkey, signer::Signer};
+
+use crate::account_storage::account_info;
+use crate::commands::send_transaction;
+use crate::rpc::CloneRpcClient;
+use crate::{Config, NeonResult};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OperatorStatus {
+    NotRegistered,
+    Active,
+    Suspended,
+}
+
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OperatorRecordInfo {
+    #[serde_as(as = "DisplayFromStr")]
+    pub operator: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub record: Pubkey,
+    pub status: OperatorStatus,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OperatorRegistryReturn {
+    pub transactions: Vec<String>,
+}
+
+fn read_status(program_id: &Pubkey, record: Pubkey, account: Option<Account>) -> OperatorStatus {
+    let Some(mut account) = account else {
+        return OperatorStatus::NotRegistered;
+    };
+
+    let info = account_info(&record, &mut account);
+    match OperatorRecord::from_account(program_id, &info) {
+        Ok(record) if record.is_suspended() => OperatorStatus::Suspended,
+        Ok(_) => OperatorStatus::Active,
+        Err(_) => OperatorStatus::NotRegistered,
+    }
+}
+
+async fn send_one_by_one(
+    rpc_client: &CloneRpcClient,
+    signer: &dyn Signer,
+    instructions: Vec<Instruction>,
+) -> NeonResult<OperatorRegistryReturn> {
+    let mut transactions = Vec::with_capacity(instructions.len());
+    for instruction in instructions {
+        let signature = send_transaction(rpc_client, signer, &[instruction]).await?;
+        info!("{signature}");
+        transactions.push(signature.to_string());
+    }
+
+    Ok(OperatorRegistryReturn { transactions })
+}
+
+/// Registers operators. The signer must be the upgrade authority of the program.
+/// Already registered operators are skipped.
+pub async fn add(
+    config: &Config,
+    rpc_client: &CloneRpcClient,
+    signer: &dyn Signer,
+    operators: &[Pubkey],
+) -> NeonResult<OperatorRegistryReturn> {
+    let mut instructions = Vec::with_capacity(operators.len());
+    for operator in operators {
+        if status(config, rpc_client, *operator).await?.status != OperatorStatus::NotRegistered {
+            info!("Operator {operator} is already registered");
+            continue;
+        }
+
+        instructions.push(instruction_builder::operator_registry_add(
+            &config.evm_loader,
+            &signer.pubkey(),
+            &signer.pubkey(),
+            operator,
+        ));
+    }
+
+    send_one_by_one(rpc_client, signer, instructions).await
+}
+
+/// Removes operators from the registry, record lamports are returned to the signer
+pub async fn remove(
+    config: &Config,
+    rpc_client: &CloneRpcClient,
+    signer: &dyn Signer,
+    operators: &[Pubkey],
+) -> NeonResult<OperatorRegistryReturn> {
+    let instructions = operators
+        .iter()
+        .map(|operator| {
+            instruction_builder::operator_registry_remove(
+                &config.evm_loader,
+                &signer.pubkey(),
+                &signer.pubkey(),
+                operator,
+            )
+        })
+        .collect();
+
+    send_one_by_one(rpc_client, signer, instructions).await
+}
+
+/// Suspends (or resumes) operators without removing them from the registry
+pub async fn set_suspended(
+    config: &Config,
+    rpc_client: &CloneRpcClient,
+    signer: &dyn Signer,
+    operators: &[Pubkey],
+    suspended: bool,
+) -> NeonResult<OperatorRegistryReturn> {
+    let instructions = operators
+        .iter()
+        .map(|operator| {
+            instruction_builder::operator_registry_suspend(
+                &config.evm_loader,
+                &signer.pubkey(),
+                operator,
+                suspended,
+            )
+        })
+        .collect();
+
+    send_one_by_one(rpc_client, signer, instructions).await
+}
+
+pub async fn status(
+    config: &Config,
+    rpc_client: &CloneRpcClient,
+    operator: Pubkey,
+) -> NeonResult<OperatorRecordInfo> {
+    let (record, _) = OperatorRecord::address(&config.evm_loader, &operator);
+    let account = rpc_client
+        .get_account_with_commitment(&record, config.commitment)
+        .await?
+        .value;
+
+    Ok(OperatorRecordInfo {
+        operator,
+        record,
+        status: read_status(&config.evm_loader, record, account),
+    })
+}
+
+pub async fn list(
+    config: &Config,
+    rpc_client: &CloneRpcClient,
+) -> NeonResult<Vec<OperatorRecordInfo>> {
+    let filters = vec![
+        RpcFilterType::DataSize(OperatorRecord::required_account_size() as u64),
+        RpcFilterType::Memcmp(Memcmp::new_raw_bytes(0, vec![TAG_OPERATOR_RECORD])),
+    ];
+    let accounts = rpc_client
+        .deref()
+        .get_program_accounts_with_config(
+            &config.evm_loader,
+            RpcProgramAccountsConfig {
+                filters: Some(filters),
+                account_config: RpcAccountInfoConfig {
+                    encoding: Some(UiAccountEncoding::Base64),
+                    commitment: Some(config.commitment),
+                    ..RpcAccountInfoConfig::default()
+                },
+                ..RpcProgramAccountsConfig::default()
+            },
+        )
+        .await?;
+
+    let mut records = Vec::with_capacity(accounts.len());
+    for (record, mut account) in accounts {
+        let info = account_info(&record, &mut account);
+        let Ok(entry) = OperatorRecord::from_account(&config.evm_loader, &info) else {
+            continue;
+        };
+
+        let status = if entry.is_suspended() {
+            OperatorStatus::Suspended
+        } else {
+            OperatorStatus::Active
+        };
+
+        records.push(OperatorRecordInfo {
+            operator: entry.operator(),
+            record,
+            status,
+        });
+    }
+    records.sort_unstable_by_key(|r| r.operator);
+
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record_account(program_id: &Pubkey, record: &Pubkey, operator: Pubkey) -> Account {
+        let mut account = Account {
+            lamports: 1,
+            data: vec![0; OperatorRecord::required_account_size()],
+            owner: *program_id,
+            ..Account::default()
+        };
+        let info = account_info(record, &mut account);
+        OperatorRecord::initialize(program_id, &info, operator).unwrap();
+
+        account
+    }
+
+    #[test]
+    fn test_read_status() {
+        let program_id = Pubkey::new_unique();
+        let operator = Pubkey::new_unique();
+        let (record, _) = OperatorRecord::address(&program_id, &operator);
+
+        assert_eq!(
+            read_status(&program_id, record, None),
+            OperatorStatus::NotRegistered
+        );
+
+        let mut account = record_account(&program_id, &record, operator);
+        assert_eq!(
+            read_status(&program_id, record, Some(account.clone())),
+            OperatorStatus::Active
+        );
+
+        {
+            let info = account_info(&record, &mut account);
+            let mut entry = OperatorRecord::from_account(&program_id, &info).unwrap();
+            assert_eq!(entry.operator(), operator);
+            entry.set_suspended(true);
+        }
+        assert_eq!(
+            read_status(&program_id, record, Some(account.clone())),
+            OperatorStatus::Suspended
+        );
+
+        account.owner = Pubkey::new_unique();
+        assert_eq!(
+            read_status(&program_id, record, Some(account)),
+            OperatorStatus::NotRegistered
+        );
+    }
+}
@@ -0,0 +1,628 @@
+//! Sends a signed Neon transaction the same way an operator does: emulation, holder writes,
+//! single-shot or iterative execution, and cancellation of the failed iterative transaction.
+
+use arrayref::array_ref;
+use evm_loader::{
+    account::TAG_STATE,
+    config::EVM_STEPS_MIN,
+    instruction_builder::{self, ExecuteAccounts},
+    types::{Address, Transaction as NeonTransaction},
+};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use serde_with::{hex::Hex, serde_as, skip_serializing_none};
+use solana_client::{
+    client_error::{ClientError, ClientErrorKind},
+    rpc_config::RpcTransactionConfig,
+    rpc_request::RpcError,
+};
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    compute_budget::ComputeBudgetInstruction,
+    instruction::{AccountMeta, Instruction},
+    message::Message,
+    packet::PACKET_DATA_SIZE,
+    pubkey::Pubkey,
+    signature::Signature,
+    signer::Signer,
+    transaction::{Transaction, TransactionError},
+};
+use solana_transaction_status::UiTransactionEncoding;
+
+use crate::commands::emulate::{self, EmulateResponse};
+use crate::commands::get_config::read_chains;
+use crate::commands::get_neon_elf::read_elf_parameters_from_account;
+use crate::commands::holder::{
+    create_instructions, write_instructions, DEFAULT_HOLDER_SEED, HOLDER_SIZE,
+};
+use crate::commands::send_transaction;
+use crate::errors::NeonError;
+use crate::receipt::Receipt;
+use crate::rpc::CloneRpcClient;
+use crate::tracing::tracers::TracerTypeEnum;
+use crate::types::{AccessListItem, EmulateRequest, TxParams};
+use crate::{Config, NeonResult};
+
+const COMPUTE_UNITS: u32 = 1_400_000;
+const HEAP_SIZE: u32 = 256 * 1024;
+const DEFAULT_MAX_ITERATIONS: u64 = 1_000;
+const MAX_ITERATION_RETRIES: u32 = 5;
+
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SendTransactionConfig {
+    /// Signed transaction in RLP encoding
+    #[serde_as(as = "Hex")]
+    pub raw_transaction: Vec<u8>,
+    /// Address of the operator balance account, zero address by default
+    pub operator_address: Option<Address>,
+    /// Seed of the operator holder account
+    pub holder_seed: Option<String>,
+    pub steps_per_iteration: Option<u64>,
+    pub max_iterations: Option<u64>,
+    /// Execute the transaction iteratively even if it fits into one iteration
+    #[serde(default)]
+    pub iterative: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecutionMode {
+    Instruction,
+    Account,
+    InstructionWithSolanaCall,
+    AccountWithSolanaCall,
+    IterativeInstruction,
+    IterativeAccount,
+}
+
+impl ExecutionMode {
+    /// Transactions with Solana calls are executed in one iteration.
+    /// Others are iterative if requested or if the emulation takes more than one iteration.
+    fn select(
+        iterative: bool,
+        from_account: bool,
+        external_solana_call: bool,
+        steps_executed: u64,
+        steps_per_iteration: u64,
+    ) -> NeonResult<Self> {
+        if external_solana_call && iterative {
+            return Err(NeonError::InvalidParameters(
+                "transactions with Solana calls can't be executed iteratively".to_string(),
+            ));
+        }
+        let iterative =
+            iterative || (!external_solana_call && steps_executed > steps_per_iteration);
+
+        Ok(match (iterative, from_account, external_solana_call) {
+            (true, false, _) => Self::IterativeInstruction,
+            (true, true, _) => Self::IterativeAccount,
+            (false, false, false) => Self::Instruction,
+            (false, true, false) => Self::Account,
+            (false, false, true) => Self::InstructionWithSolanaCall,
+            (false, true, true) => Self::AccountWithSolanaCall,
+        })
+    }
+
+    const fn is_iterative(self) -> bool {
+        matches!(self, Self::IterativeInstruction | Self::IterativeAccount)
+    }
+}
+
+/// Next step after a failed iteration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Recovery {
+    /// Send the iteration again
+    Retry,
+    /// The program rejected the iteration, the transaction must be canceled
+    Cancel,
+    /// Stop without canceling, the transaction stays in the holder
+    Fail,
+}
+
+impl Recovery {
+    fn from_error(error: &NeonError, retries: u32) -> Self {
+        let NeonError::ClientError(error) = error else {
+            return Self::Fail;
+        };
+
+        if is_transient(error) {
+            return if retries < MAX_ITERATION_RETRIES {
+                Self::Retry
+            } else {
+                Self::Fail
+            };
+        }
+
+        match error.get_transaction_error() {
+            Some(TransactionError::InstructionError(..)) => Self::Cancel,
+            _ => Self::Fail,
+        }
+    }
+}
+
+/// Blockhash expiration and RPC timeouts
+fn is_transient(error: &ClientError) -> bool {
+    if let Some(error) = error.get_transaction_error() {
+        return error == TransactionError::BlockhashNotFound;
+    }
+
+    match error.kind() {
+        ClientErrorKind::Io(_) => true,
+        ClientErrorKind::Reqwest(error) => error.is_timeout() || error.is_connect(),
+        // Confirmation of the expired transaction
+        ClientErrorKind::RpcError(RpcError::ForUser(message)) => {
+            message.starts_with("unable to confirm transaction")
+        }
+        _ => false,
+    }
+}
+
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SendTransactionReturn {
+    #[serde_as(as = "Hex")]
+    pub transaction_hash: [u8; 32],
+    pub mode: ExecutionMode,
+    /// Solana transactions in the order of sending, including holder writes
+    pub signatures: Vec<String>,
+    /// Iterative transaction failed and was canceled
+    pub canceled: bool,
+    pub receipt: Receipt,
+}
+
+struct Sender<'a> {
+    rpc_client: &'a CloneRpcClient,
+    signer: &'a dyn Signer,
+    signatures: Vec<String>,
+}
+
+impl<'a> Sender<'a> {
+    async fn send(&mut self, instructions: &[Instruction]) -> NeonResult<Signature> {
+        let signature = send_transaction(self.rpc_client, self.signer, instructions).await?;
+        info!("{signature}");
+        self.signatures.push(signature.to_string());
+
+        Ok(signature)
+    }
+
+    /// Sends the execution instruction with the maximal compute budget and returns its logs
+    async fn execute(&mut self, instruction: Instruction) -> NeonResult<Vec<String>> {
+        let signature = self.send(&with_compute_budget(instruction)).await?;
+        transaction_logs(self.rpc_client, &signature).await
+    }
+}
+
+pub(crate) fn with_compute_budget(instruction: Instruction) -> [Instruction; 3] {
+    [
+        ComputeBudgetInstruction::set_compute_unit_limit(COMPUTE_UNITS),
+        ComputeBudgetInstruction::request_heap_frame(HEAP_SIZE),
+        instruction,
+    ]
+}
+
+fn fits_into_transaction(instruction: Instruction, payer: &Pubkey) -> NeonResult<bool> {
+    let message = Message::new(&with_compute_budget(instruction), Some(payer));
+    let transaction = Transaction::new_unsigned(message);
+
+    Ok(bincode::serialized_size(&transaction)? <= PACKET_DATA_SIZE as u64)
+}
+
+async fn transaction_logs(
+    rpc_client: &CloneRpcClient,
+    signature: &Signature,
+) -> NeonResult<Vec<String>> {
+    let transaction = rpc_client
+        .get_transaction_with_config(
+            signature,
+            RpcTransactionConfig {
+                encoding: Some(UiTransactionEncoding::Json),
+                commitment: Some(CommitmentConfig::confirmed()),
+                max_supported_transaction_version: Some(0),
+            },
+        )
+        .await?;
+
+    let logs: Option<Vec<String>> = transaction
+        .transaction
+        .meta
+        .and_then(|meta| meta.log_messages.into());
+
+    Ok(logs.unwrap_or_default())
+}
+
+async fn emulate(
+    rpc_client: &CloneRpcClient,
+    program_id: Pubkey,
+    trx: &NeonTransaction,
+    origin: Address,
+) -> NeonResult<EmulateResponse> {
+    let access_list = trx.access_list().map(|list| {
+        list.iter()
+            .map(|(address, storage_keys)| AccessListItem {
+                address: *address,
+                storage_keys: storage_keys.clone(),
+            })
+            .collect()
+    });
+
+    let request = EmulateRequest {
+        tx: TxParams {
+            nonce: Some(trx.nonce()),
+            from: origin,
+            to: trx.target(),
+            data: Some(trx.call_data().to_vec()),
+            value: Some(trx.value()),
+            gas_limit: Some(trx.gas_limit()),
+            actual_gas_used: None,
+            gas_price: Some(trx.gas_price()),
+            access_list,
+            chain_id: trx.chain_id(),
+        },
+        step_limit: None,
+        chains: None,
+        trace_config: None,
+        accounts: vec![],
+        solana_overrides: None,
+        exact: None,
+        with_logs: false,
+        with_state_diff: false,
+    };
+
+    let (response, _) =
+        emulate::execute(rpc_client, program_id, request, None::<TracerTypeEnum>).await?;
+
+    Ok(response)
+}
+
+/// Creates the holder account if it doesn't exist.
+/// A holder locked by another transaction must be canceled first.
+async fn prepare_holder(
+    config: &Config,
+    sender: &mut Sender<'_>,
+    holder: &Pubkey,
+    seed: &str,
+) -> NeonResult<()> {
+    let rpc_client = sender.rpc_client;
+    let operator = sender.signer.pubkey();
+
+    if let Some(account) = rpc_client
+        .get_account_with_commitment(holder, config.commitment)
+        .await?
+        .value
+    {
+        if account.owner == config.evm_loader && account.data.first() == Some(&TAG_STATE) {
+            return Err(NeonError::InvalidParameters(format!(
+                "Holder {holder} is locked by another transaction"
+            )));
+        }
+
+        return Ok(());
+    }
+
+    let instructions =
+        create_instructions(config, rpc_client, &operator, seed, HOLDER_SIZE).await?;
+    sender.send(&instructions).await?;
+
+    Ok(())
+}
+
+async fn write_holder(
+    config: &Config,
+    sender: &mut Sender<'_>,
+    holder: &Pubkey,
+    hash: &[u8; 32],
+    raw_transaction: &[u8],
+) -> NeonResult<()> {
+    let operator = sender.signer.pubkey();
+
+    for instruction in
+        write_instructions(&config.evm_loader, holder, &operator, hash, raw_transaction)
+    {
+        sender.send(&[instruction]).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn execute(
+    config: &Config,
+    rpc_client: &CloneRpcClient,
+    signer: &dyn Signer,
+    params: SendTransactionConfig,
+) -> NeonResult<SendTransactionReturn> {
+    let program_id = config.evm_loader;
+    let operator = signer.pubkey();
+
+    let trx = NeonTransaction::from_rlp(&params.raw_transaction)?;
+    let origin = trx.recover_caller_address()?;
+    let hash = trx.hash();
+
+    let emulation = emulate(rpc_client, program_id, &trx, origin).await?;
+    info!(
+        "emulation: {}, {} steps, {} gas",
+        emulation.exit_status, emulation.steps_executed, emulation.used_gas
+    );
+
+    let steps_per_iteration = params.steps_per_iteration.unwrap_or(EVM_STEPS_MIN);
+    let step_count = u32::try_from(steps_per_iteration)
+        .map_err(|_| NeonError::InvalidParameters("steps_per_iteration".to_string()))?;
+
+    let chain_id = match trx.chain_id() {
+        Some(chain_id) => chain_id,
+        None => read_chains(rpc_client, program_id)
+            .await?
+            .into_iter()
+            .find(|chain| chain.name == "neon")
+            .map(|chain| chain.id)
+            .ok_or(NeonError::IncorrectProgram(program_id))?,
+    };
+
+    let neon_params = read_elf_parameters_from_account(config, rpc_client).await?;
+    let pool_count: u32 = neon_params
+        .get("NEON_TREASURY_POOL_COUNT")
+        .and_then(|value| value.parse().ok())
+        .filter(|count| *count > 0)
+        .ok_or(NeonError::IncorrectProgram(program_id))?;
+    let treasury_index = u32::from_le_bytes(*array_ref![hash, 0, 4]) % pool_count;
+
+    let (operator_balance, _) = params
+        .operator_address
+        .unwrap_or_default()
+        .find_operator_address(&program_id, chain_id, &operator);
+
+    let metas: Vec<AccountMeta> = emulation
+        .solana_accounts
+        .iter()
+        .map(|account| {
+            if account.is_writable {
+                AccountMeta::new(account.pubkey, false)
+            } else {
+                AccountMeta::new_readonly(account.pubkey, false)
+            }
+        })
+        .collect();
+    let accounts = ExecuteAccounts {
+        operator,
+        operator_balance,
+        treasury_index,
+        accounts: &metas,
+    };
+
+    let holder_seed = params.holder_seed.as_deref().unwrap_or(DEFAULT_HOLDER_SEED);
+    let holder = instruction_builder::holder_address(&program_id, &operator, holder_seed);
+
+    let single_instruction = if emulation.external_solana_call {
+        instruction_builder::transaction_execute_from_instruction_with_solana_call(
+            &program_id,
+            &accounts,
+            &params.raw_transaction,
+        )
+    } else {
+        instruction_builder::transaction_execute_from_instruction(
+            &program_id,
+            &accounts,
+            &params.raw_transaction,
+        )
+    };
+    let from_account = !fits_into_transaction(single_instruction.clone(), &operator)?;
+
+    let mode = ExecutionMode::select(
+        params.iterative,
+        from_account,
+        emulation.external_solana_call,
+        emulation.steps_executed,
+        steps_per_iteration,
+    )?;
+    info!("execution mode: {mode:?}");
+
+    let mut sender = Sender {
+        rpc_client,
+        signer,
+        signatures: vec![],
+    };
+
+    if mode.is_iterative() || from_account {
+        prepare_holder(config, &mut sender, &holder, holder_seed).await?;
+    }
+    if from_account {
+        write_holder(config, &mut sender, &holder, &hash, &params.raw_transaction).await?;
+    }
+
+    let mut canceled = false;
+    let receipt = match mode {
+        ExecutionMode::Instruction | ExecutionMode::InstructionWithSolanaCall => {
+            let logs = sender.execute(single_instruction).await?;
+            Receipt::from_logs(&program_id, logs.iter().map(String::as_str))
+        }
+        ExecutionMode::Account => {
+            let instruction = instruction_builder::transaction_execute_from_account(
+                &program_id,
+                &holder,
+                &accounts,
+            );
+            let logs = sender.execute(instruction).await?;
+            Receipt::from_logs(&program_id, logs.iter().map(String::as_str))
+        }
+        ExecutionMode::AccountWithSolanaCall => {
+            let instruction =
+                instruction_builder::transaction_execute_from_account_with_solana_call(
+                    &program_id,
+                    &holder,
+                    &accounts,
+                );
+            let logs = sender.execute(instruction).await?;
+            Receipt::from_logs(&program_id, logs.iter().map(String::as_str))
+        }
+        ExecutionMode::IterativeInstruction | ExecutionMode::IterativeAccount => {
+            let raw_transaction = (!from_account).then_some(params.raw_transaction.as_slice());
+            let max_iterations = params.max_iterations.unwrap_or(DEFAULT_MAX_ITERATIONS);
+
+            let mut receipt = Receipt::default();
+            let mut index = 0_u32;
+            let mut retries = 0_u32;
+            while !receipt.is_completed() {
+                if u64::from(index) >= max_iterations {
+                    warn!("transaction is not completed in {max_iterations} iterations");
+                    canceled = true;
+                    break;
+                }
+
+                let instruction = if let Some(raw_transaction) = raw_transaction {
+                    instruction_builder::transaction_step_from_instruction(
+                        &program_id,
+                        &holder,
+                        &accounts,
+                        step_count,
+                        index,
+                        raw_transaction,
+                    )
+                } else {
+                    instruction_builder::transaction_step_from_account(
+                        &program_id,
+                        &holder,
+                        &accounts,
+                        step_count,
+                        index,
+                    )
+                };
+
+                match sender.execute(instruction).await {
+                    Ok(logs) => {
+                        receipt.append_logs(&program_id, logs.iter().map(String::as_str));
+                        retries = 0;
+                    }
+                    Err(e) => match Recovery::from_error(&e, retries) {
+                        Recovery::Retry => {
+                            warn!("iteration {index} failed, retrying: {e}");
+                            retries += 1;
+                            continue;
+                        }
+                        Recovery::Cancel => {
+                            let holder_account = rpc_client
+                                .get_account_with_commitment(&holder, config.commitment)
+                                .await?
+                                .value;
+                            // Nothing to cancel if the transaction didn't begin
+                            if holder_account.and_then(|a| a.data.first().copied())
+                                != Some(TAG_STATE)
+                            {
+                                return Err(e);
+                            }
+
+                            warn!("iteration {index} failed: {e}");
+                            canceled = true;
+                            break;
+                        }
+                        Recovery::Fail => return Err(e),
+                    },
+                }
+
+                index += 1;
+            }
+
+            if canceled {
+                let instruction = instruction_builder::cancel(
+                    &program_id,
+                    &holder,
+                    &operator,
+                    &operator_balance,
+                    &hash,
+                    &metas,
+                );
+                let logs = sender.execute(instruction).await?;
+                receipt.append_logs(&program_id, logs.iter().map(String::as_str));
+            }
+
+            receipt
+        }
+    };
+
+    Ok(SendTransactionReturn {
+        transaction_hash: hash,
+        mode,
+        signatures: sender.signatures,
+        canceled,
+        receipt,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::instruction::InstructionError;
+
+    #[test]
+    fn test_execution_mode() {
+        let select = |iterative, from_account, solana_call, steps| {
+            ExecutionMode::select(iterative, from_account, solana_call, steps, EVM_STEPS_MIN)
+                .unwrap()
+        };
+
+        assert_eq!(select(false, false, false, 10), ExecutionMode::Instruction);
+        assert_eq!(select(false, true, false, 10), ExecutionMode::Account);
+        assert_eq!(
+            select(false, false, false, EVM_STEPS_MIN + 1),
+            ExecutionMode::IterativeInstruction
+        );
+        assert_eq!(
+            select(false, true, false, EVM_STEPS_MIN + 1),
+            ExecutionMode::IterativeAccount
+        );
+        assert_eq!(
+            select(true, false, false, 10),
+            ExecutionMode::IterativeInstruction
+        );
+        assert_eq!(
+            select(true, true, false, 10),
+            ExecutionMode::IterativeAccount
+        );
+
+        // Solana calls are never iterative
+        assert_eq!(
+            select(false, false, true, EVM_STEPS_MIN + 1),
+            ExecutionMode::InstructionWithSolanaCall
+        );
+        assert_eq!(
+            select(false, true, true, EVM_STEPS_MIN + 1),
+            ExecutionMode::AccountWithSolanaCall
+        );
+        assert!(ExecutionMode::select(true, false, true, 10, EVM_STEPS_MIN).is_err());
+    }
+
+    #[test]
+    fn test_recovery() {
+        let client_error = |error: ClientError| NeonError::ClientError(error);
+
+        let expired = client_error(TransactionError::BlockhashNotFound.into());
+        assert_eq!(Recovery::from_error(&expired, 0), Recovery::Retry);
+        assert_eq!(
+            Recovery::from_error(&expired, MAX_ITERATION_RETRIES - 1),
+            Recovery::Retry
+        );
+        assert_eq!(
+            Recovery::from_error(&expired, MAX_ITERATION_RETRIES),
+            Recovery::Fail
+        );
+
+        let unconfirmed = client_error(
+            RpcError::ForUser("unable to confirm transaction. This can happen in situations such as transaction expiration and insufficient fee-payer funds".to_string()).into(),
+        );
+        assert_eq!(Recovery::from_error(&unconfirmed, 0), Recovery::Retry);
+
+        let timeout = client_error(std::io::Error::from(std::io::ErrorKind::TimedOut).into());
+        assert_eq!(Recovery::from_error(&timeout, 0), Recovery::Retry);
+
+        let failed =
+            client_error(TransactionError::InstructionError(2, InstructionError::Custom(0)).into());
+        assert_eq!(Recovery::from_error(&failed, 0), Recovery::Cancel);
+
+        let other = client_error(TransactionError::InsufficientFundsForFee.into());
+        assert_eq!(Recovery::from_error(&other, 0), Recovery::Fail);
+        assert_eq!(
+            Recovery::from_error(&NeonError::InvalidParameters(String::new()), 0),
+            Recovery::Fail
+        );
+    }
+}
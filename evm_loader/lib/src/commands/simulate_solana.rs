@@ -1,11 +1,14 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use bincode::Options;
 use serde::{Deserialize, Serialize};
-use serde_with::serde_as;
+use serde_with::{hex::Hex, serde_as, DisplayFromStr};
+use solana_accounts_db::transaction_results::TransactionExecutionDetails;
 use solana_program_runtime::compute_budget::ComputeBudget;
 use solana_runtime::runtime_config::RuntimeConfig;
 use solana_sdk::{
+    account::Account,
+    inner_instruction::InnerInstructionsList,
     pubkey::Pubkey,
     transaction::{SanitizedTransaction, Transaction, VersionedTransaction},
 };
@@ -13,22 +16,68 @@ use solana_sdk::{
 use crate::{
     rpc::Rpc,
     solana_simulator::{SolanaSimulator, SyncState},
-    types::SimulateSolanaRequest,
+    types::{SerializedAccount, SimulateSolanaRequest},
     NeonResult,
 };
 
+#[serde_as]
+#[derive(Deserialize, Serialize, Debug)]
+pub struct SimulateSolanaAccountDiff {
+    #[serde_as(as = "DisplayFromStr")]
+    pub pubkey: Pubkey,
+    pub pre: Option<SerializedAccount>,
+    pub post: Option<SerializedAccount>,
+}
+
+#[serde_as]
+#[derive(Deserialize, Serialize, Debug)]
+pub struct SimulateSolanaReturnData {
+    #[serde_as(as = "DisplayFromStr")]
+    pub program_id: Pubkey,
+    #[serde_as(as = "Hex")]
+    pub data: Vec<u8>,
+}
+
+#[serde_as]
+#[derive(Deserialize, Serialize, Debug)]
+pub struct SimulateSolanaInnerInstruction {
+    #[serde_as(as = "DisplayFromStr")]
+    pub program_id: Pubkey,
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    pub accounts: Vec<Pubkey>,
+    #[serde_as(as = "Hex")]
+    pub data: Vec<u8>,
+    pub stack_height: u8,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct SimulateSolanaInnerInstructions {
+    /// Index of the transaction instruction
+    pub index: usize,
+    pub instructions: Vec<SimulateSolanaInnerInstruction>,
+}
+
 #[serde_as]
 #[derive(Deserialize, Serialize, Debug, Default)]
 pub struct SimulateSolanaTransactionResult {
     pub error: Option<solana_sdk::transaction::TransactionError>,
     pub logs: Vec<String>,
     pub executed_units: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account_diffs: Option<Vec<SimulateSolanaAccountDiff>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub return_data: Option<SimulateSolanaReturnData>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inner_instructions: Option<Vec<SimulateSolanaInnerInstructions>>,
+    /// Compute units consumed by every instruction, parsed from logs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instruction_units: Option<Vec<u64>>,
 }
 
 #[serde_as]
 #[derive(Deserialize, Serialize, Debug, Default)]
 pub struct SimulateSolanaResponse {
-    transactions: Vec<SimulateSolanaTransactionResult>,
+    pub transactions: Vec<SimulateSolanaTransactionResult>,
 }
 
 fn decode_transaction(data: &[u8]) -> NeonResult<VersionedTransaction> {
@@ -74,6 +123,107 @@ fn account_keys(txs: &[SanitizedTransaction]) -> Vec<Pubkey> {
     accounts.into_iter().collect()
 }
 
+fn overridden_keys(
+    solana_overrides: &HashMap<Pubkey, Option<SerializedAccount>>,
+    programs: &HashMap<Pubkey, Vec<u8>>,
+) -> HashSet<Pubkey> {
+    solana_overrides
+        .keys()
+        .chain(programs.keys())
+        .copied()
+        .collect()
+}
+
+fn writable_keys(tx: &SanitizedTransaction) -> Vec<Pubkey> {
+    let message = tx.message();
+    message
+        .account_keys()
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| message.is_writable(*i))
+        .map(|(_, key)| *key)
+        .collect()
+}
+
+fn account_diffs(
+    simulator: &SolanaSimulator,
+    pre_accounts: Vec<(Pubkey, Option<Account>)>,
+) -> Vec<SimulateSolanaAccountDiff> {
+    pre_accounts
+        .into_iter()
+        .filter_map(|(pubkey, pre)| {
+            let post = simulator.get_account(&pubkey);
+            (pre != post).then(|| SimulateSolanaAccountDiff {
+                pubkey,
+                pre: pre.as_ref().map(SerializedAccount::from),
+                post: post.as_ref().map(SerializedAccount::from),
+            })
+        })
+        .collect()
+}
+
+fn inner_instructions(
+    keys: &[Pubkey],
+    inner_instructions: InnerInstructionsList,
+) -> Vec<SimulateSolanaInnerInstructions> {
+    let key = |index: u8| keys.get(usize::from(index)).copied().unwrap_or_default();
+
+    inner_instructions
+        .into_iter()
+        .enumerate()
+        .filter(|(_, instructions)| !instructions.is_empty())
+        .map(|(index, instructions)| SimulateSolanaInnerInstructions {
+            index,
+            instructions: instructions
+                .into_iter()
+                .map(|inner| SimulateSolanaInnerInstruction {
+                    program_id: key(inner.instruction.program_id_index),
+                    accounts: inner.instruction.accounts.iter().map(|i| key(*i)).collect(),
+                    data: inner.instruction.data,
+                    stack_height: inner.stack_height,
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+/// Sums "Program <id> consumed <n> of <m> compute units" messages of the top level instructions
+fn instruction_units(logs: &[String], instructions_count: usize) -> Vec<u64> {
+    let mut units = vec![0_u64; instructions_count];
+    let mut index: Option<usize> = None;
+    let mut depth = 0_usize;
+
+    for log in logs {
+        let Some(message) = log.strip_prefix("Program ") else {
+            continue;
+        };
+
+        let words = message.split_whitespace().collect::<Vec<_>>();
+        match words.as_slice() {
+            [_, "invoke", level] => {
+                depth = level
+                    .trim_start_matches('[')
+                    .trim_end_matches(']')
+                    .parse()
+                    .unwrap_or(depth + 1);
+                if depth == 1 {
+                    index = Some(index.map_or(0, |i| i + 1));
+                }
+            }
+            [_, "consumed", consumed, "of", _, "compute", "units"] if depth == 1 => {
+                let consumed = consumed.parse::<u64>().unwrap_or_default();
+                if let Some(units) = index.and_then(|i| units.get_mut(i)) {
+                    *units += consumed;
+                }
+            }
+            [_, "success"] | [_, "failed:", ..] => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+
+    units
+}
+
 fn runtime_config(request: &SimulateSolanaRequest) -> RuntimeConfig {
     let compute_units = request.compute_units.unwrap_or(1_400_000);
     let heap_size = request.heap_size.unwrap_or(256 * 1024);
@@ -93,10 +243,28 @@ pub async fn execute(
     request: SimulateSolanaRequest,
 ) -> NeonResult<SimulateSolanaResponse> {
     let verify = request.verify.unwrap_or(true);
+    let with_account_diffs = request.with_account_diffs;
+    let with_return_data = request.with_return_data;
+    let with_inner_instructions = request.with_inner_instructions;
+    let with_instruction_units = request.with_instruction_units;
     let config = runtime_config(&request);
 
     let mut simulator = SolanaSimulator::new_with_config(rpc, config, SyncState::Yes).await?;
 
+    // Overridden accounts and programs are not downloaded from RPC
+    let solana_overrides = request.solana_overrides.unwrap_or_default();
+    let programs = request.programs.unwrap_or_default();
+    let overridden = overridden_keys(&solana_overrides, &programs);
+
+    for (pubkey, account) in &solana_overrides {
+        let account = account.as_ref().map(Account::from).unwrap_or_default();
+        simulator.set_account(pubkey, &account);
+    }
+
+    for (program_id, elf) in programs {
+        simulator.set_program_account(&program_id, elf);
+    }
+
     // Decode transactions from bytes
     let mut transactions: Vec<VersionedTransaction> = vec![];
     for data in request.transactions {
@@ -105,7 +273,8 @@ pub async fn execute(
     }
 
     // Download ALT
-    let alt = address_table_lookups(&transactions);
+    let mut alt = address_table_lookups(&transactions);
+    alt.retain(|key| !overridden.contains(key));
     simulator.sync_accounts(rpc, &alt).await?;
 
     // Sanitize transactions (verify tx and decode ALT)
@@ -116,7 +285,8 @@ pub async fn execute(
     }
 
     // Download accounts
-    let accounts = account_keys(&sanitized_transactions);
+    let mut accounts = account_keys(&sanitized_transactions);
+    accounts.retain(|key| !overridden.contains(key));
     simulator.sync_accounts(rpc, &accounts).await?;
 
     simulator.replace_blockhash(&request.blockhash.into());
@@ -124,11 +294,46 @@ pub async fn execute(
     // Process transactions
     let mut results = Vec::new();
     for tx in sanitized_transactions {
-        let r = simulator.process_transaction(tx)?;
+        let pre_accounts = with_account_diffs.then(|| {
+            writable_keys(&tx)
+                .into_iter()
+                .map(|key| (key, simulator.get_account(&key)))
+                .collect::<Vec<_>>()
+        });
+        let instructions_count = tx.message().instructions().len();
+        let keys = tx
+            .message()
+            .account_keys()
+            .iter()
+            .copied()
+            .collect::<Vec<_>>();
+
+        let TransactionExecutionDetails {
+            status,
+            log_messages,
+            inner_instructions: inner,
+            return_data,
+            executed_units,
+            ..
+        } = simulator.process_transaction(tx)?;
+        let logs = log_messages.unwrap_or_default();
+
         results.push(SimulateSolanaTransactionResult {
-            error: r.status.err(),
-            logs: r.log_messages.unwrap_or_default(),
-            executed_units: r.executed_units,
+            error: status.err(),
+            account_diffs: pre_accounts.map(|pre| account_diffs(&simulator, pre)),
+            return_data: return_data.filter(|_| with_return_data).map(|r| {
+                SimulateSolanaReturnData {
+                    program_id: r.program_id,
+                    data: r.data,
+                }
+            }),
+            inner_instructions: inner
+                .filter(|_| with_inner_instructions)
+                .map(|inner| inner_instructions(&keys, inner)),
+            instruction_units: with_instruction_units
+                .then(|| instruction_units(&logs, instructions_count)),
+            logs,
+            executed_units,
         });
     }
 
@@ -136,3 +341,28 @@ pub async fn execute(
         transactions: results,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::instruction_units;
+
+    #[test]
+    fn test_instruction_units() {
+        let logs = [
+            "Program ComputeBudget111111111111111111111111111111 invoke [1]",
+            "Program ComputeBudget111111111111111111111111111111 success",
+            "Program eeLSJgWzzxrqKv1UxtRVVH8FX3qCQWUs9QuAjJpETGU invoke [1]",
+            "Program log: Instruction: Execute Transaction from Instruction",
+            "Program 11111111111111111111111111111111 invoke [2]",
+            "Program 11111111111111111111111111111111 success",
+            "Program eeLSJgWzzxrqKv1UxtRVVH8FX3qCQWUs9QuAjJpETGU consumed 25000 of 1399850 compute units",
+            "Program eeLSJgWzzxrqKv1UxtRVVH8FX3qCQWUs9QuAjJpETGU success",
+            "Program eeLSJgWzzxrqKv1UxtRVVH8FX3qCQWUs9QuAjJpETGU invoke [1]",
+            "Program eeLSJgWzzxrqKv1UxtRVVH8FX3qCQWUs9QuAjJpETGU consumed 1200 of 1374850 compute units",
+            "Program eeLSJgWzzxrqKv1UxtRVVH8FX3qCQWUs9QuAjJpETGU failed: custom program error: 0x1",
+        ]
+        .map(String::from);
+
+        assert_eq!(instruction_units(&logs, 3), vec![0, 25000, 1200]);
+    }
+}
@@ -0,0 +1,241 @@
+//! Detection of iterative transactions abandoned in the middle of the execution.
+//! A `StateAccount` keeps the touched accounts locked until the transaction is canceled,
+//! the idle time is measured from the last Solana transaction which used the state account.
+
+use evm_loader::account::TAG_STATE;
+use evm_loader::types::Address;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use serde_with::{hex::Hex, serde_as, skip_serializing_none, DisplayFromStr};
+use solana_client::rpc_client::GetConfirmedSignaturesForAddress2Config;
+use solana_sdk::{clock::UnixTimestamp, pubkey::Pubkey, signature::Signature, signer::Signer};
+
+use crate::account_storage::account_info;
+use crate::commands::get_holder::{read_holder, GetHolderResponse, Status};
+use crate::commands::holder::{cancel_instruction, program_accounts};
+use crate::commands::send_neon_transaction::with_compute_budget;
+use crate::commands::send_transaction;
+use crate::rpc::{CloneRpcClient, Rpc};
+use crate::{Config, NeonResult};
+
+#[derive(Debug, Clone, Default)]
+pub struct StuckTransactionsConfig {
+    /// Transactions idle longer than this number of seconds are stuck
+    pub threshold: u64,
+    /// Cancel stuck transactions, only report them otherwise
+    pub cancel: bool,
+    /// Address of the operator balance which receives the cancel payments, zero address by default
+    pub operator_address: Option<Address>,
+}
+
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StateInfo {
+    #[serde_as(as = "DisplayFromStr")]
+    pub pubkey: Pubkey,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub owner: Option<Pubkey>,
+    #[serde_as(as = "Option<Hex>")]
+    pub transaction_hash: Option<[u8; 32]>,
+    pub chain_id: Option<u64>,
+    pub steps_executed: u64,
+    /// Number of accounts locked by the transaction
+    pub locked_accounts: usize,
+    /// Slot of the last Solana transaction which used the state account
+    pub last_slot: Option<u64>,
+    pub last_activity: Option<UnixTimestamp>,
+    /// `None` if the time of the last activity is unknown
+    pub idle_seconds: Option<u64>,
+    pub is_stuck: bool,
+    /// Signature of the cancel transaction
+    pub cancel: Option<String>,
+    pub cancel_error: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StuckTransactionsReturn {
+    pub slot: u64,
+    pub timestamp: UnixTimestamp,
+    pub threshold: u64,
+    pub dry_run: bool,
+    pub states: Vec<StateInfo>,
+}
+
+fn idle_seconds(now: UnixTimestamp, last_activity: Option<UnixTimestamp>) -> Option<u64> {
+    let last_activity = last_activity?;
+    Some(u64::try_from(now.saturating_sub(last_activity)).unwrap_or(0))
+}
+
+fn is_stuck(idle_seconds: Option<u64>, threshold: u64) -> bool {
+    idle_seconds.is_some_and(|idle| idle >= threshold)
+}
+
+/// Slot and time of the last transaction which used the account
+async fn last_activity(
+    config: &Config,
+    rpc_client: &CloneRpcClient,
+    pubkey: &Pubkey,
+) -> NeonResult<(Option<u64>, Option<UnixTimestamp>)> {
+    let signatures = rpc_client
+        .get_signatures_for_address_with_config(
+            pubkey,
+            GetConfirmedSignaturesForAddress2Config {
+                limit: Some(1),
+                commitment: Some(config.commitment),
+                ..GetConfirmedSignaturesForAddress2Config::default()
+            },
+        )
+        .await?;
+
+    let Some(last) = signatures.into_iter().next() else {
+        return Ok((None, None));
+    };
+
+    let block_time = match last.block_time {
+        Some(block_time) => Some(block_time),
+        None => rpc_client.get_block_time(last.slot).await.ok(),
+    };
+
+    Ok((Some(last.slot), block_time))
+}
+
+async fn cancel(
+    config: &Config,
+    rpc_client: &CloneRpcClient,
+    signer: &dyn Signer,
+    operator_address: Option<Address>,
+    pubkey: &Pubkey,
+    state: &GetHolderResponse,
+) -> NeonResult<Signature> {
+    let instruction = cancel_instruction(
+        config,
+        rpc_client,
+        &signer.pubkey(),
+        operator_address,
+        pubkey,
+        state,
+    )
+    .await?;
+    let signature = send_transaction(rpc_client, signer, &with_compute_budget(instruction)).await?;
+
+    Ok(signature)
+}
+
+fn state_info(pubkey: Pubkey, state: &GetHolderResponse) -> StateInfo {
+    StateInfo {
+        pubkey,
+        owner: state.owner,
+        transaction_hash: state.tx,
+        chain_id: state.chain_id,
+        steps_executed: state.steps_executed,
+        locked_accounts: state.accounts.as_ref().map_or(0, Vec::len),
+        last_slot: None,
+        last_activity: None,
+        idle_seconds: None,
+        is_stuck: false,
+        cancel: None,
+        cancel_error: None,
+        error: None,
+    }
+}
+
+/// Scans all active state accounts and reports the idle time of their transactions.
+/// Stuck transactions are canceled by the signer if `cancel` is set.
+/// Failures of a single state are reported in its `error` and `cancel_error`.
+pub async fn execute(
+    config: &Config,
+    rpc_client: &CloneRpcClient,
+    signer: Option<&dyn Signer>,
+    params: &StuckTransactionsConfig,
+) -> NeonResult<StuckTransactionsReturn> {
+    let slot = rpc_client.get_slot().await?;
+    let timestamp = rpc_client.get_block_time(slot).await?;
+
+    let accounts = program_accounts(config, rpc_client, TAG_STATE, None).await?;
+
+    let mut states = Vec::with_capacity(accounts.len());
+    for (pubkey, mut account) in accounts {
+        let info = account_info(&pubkey, &mut account);
+        let state = match read_holder(&config.evm_loader, info) {
+            Ok(state) if matches!(state.status, Status::Active) => state,
+            Ok(_) => continue,
+            Err(e) => {
+                let mut info = state_info(pubkey, &GetHolderResponse::default());
+                info.error = Some(e.to_string());
+                states.push(info);
+                continue;
+            }
+        };
+
+        let mut info = state_info(pubkey, &state);
+        match last_activity(config, rpc_client, &pubkey).await {
+            Ok(activity) => (info.last_slot, info.last_activity) = activity,
+            Err(e) => {
+                warn!("Failed to read the last activity of {pubkey}: {e}");
+                info.error = Some(e.to_string());
+            }
+        }
+        info.idle_seconds = idle_seconds(timestamp, info.last_activity);
+        info.is_stuck = is_stuck(info.idle_seconds, params.threshold);
+
+        if info.is_stuck {
+            info!(
+                "State {pubkey} is idle for {} seconds",
+                info.idle_seconds.unwrap_or_default()
+            );
+        }
+
+        let cancel_signer = signer.filter(|_| info.is_stuck && params.cancel);
+        if let Some(signer) = cancel_signer {
+            let operator_address = params.operator_address;
+            match cancel(
+                config,
+                rpc_client,
+                signer,
+                operator_address,
+                &pubkey,
+                &state,
+            )
+            .await
+            {
+                Ok(signature) => {
+                    info!("Cancel {pubkey}: {signature}");
+                    info.cancel = Some(signature.to_string());
+                }
+                Err(e) => {
+                    warn!("Failed to cancel {pubkey}: {e}");
+                    info.cancel_error = Some(e.to_string());
+                }
+            }
+        }
+
+        states.push(info);
+    }
+    states.sort_unstable_by(|a, b| b.idle_seconds.cmp(&a.idle_seconds));
+
+    Ok(StuckTransactionsReturn {
+        slot,
+        timestamp,
+        threshold: params.threshold,
+        dry_run: !params.cancel || signer.is_none(),
+        states,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_idle_seconds() {
+        assert_eq!(idle_seconds(1_000, Some(400)), Some(600));
+        assert_eq!(idle_seconds(1_000, Some(1_200)), Some(0));
+        assert_eq!(idle_seconds(1_000, None), None);
+
+        assert!(is_stuck(Some(600), 600));
+        assert!(!is_stuck(Some(599), 600));
+        assert!(!is_stuck(None, 0));
+    }
+}
@@ -6,6 +6,7 @@ use solana_client::nonblocking::rpc_client::RpcClient;
 
 use {
     crate::errors::NeonError,
+    evm_loader::account::token,
     log::{debug, error, info, warn},
     solana_sdk::{
         account::Account,
@@ -87,7 +88,7 @@ impl<'a, 'b> TransactionExecutor<'a, 'b> {
                 return Err(NeonError::IncorrectProgram(account_info.owner));
             }
 
-            let account: T = T::unpack(&account_info.data)?;
+            let account: T = token::unpack(owner_program_id, &account_info.data)?;
             if !account.is_initialized() {
                 return Err(NeonError::AccountNotFound(*account_key));
             }
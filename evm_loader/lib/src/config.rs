@@ -26,6 +26,13 @@ pub struct APIOptions {
     pub evm_loader: Pubkey,
     pub key_for_config: Pubkey,
     pub db_config: ChDbConfig,
+    /// Tracer DB lagging behind Solana by more slots is reported as not ready
+    #[serde(default = "default_health_max_slot_lag")]
+    pub health_max_slot_lag: u64,
+}
+
+const fn default_health_max_slot_lag() -> u64 {
+    150
 }
 
 /// # Errors
@@ -64,6 +71,10 @@ pub fn load_api_config_from_environment() -> APIOptions {
 
     let db_config = load_db_config_from_environment();
 
+    let health_max_slot_lag = env::var("NEON_HEALTH_MAX_SLOT_LAG")
+        .map_or(Ok(default_health_max_slot_lag()), |v| v.parse())
+        .expect("NEON_HEALTH_MAX_SLOT_LAG variable must be a valid number");
+
     APIOptions {
         solana_cli_config_path,
         commitment,
@@ -73,6 +84,7 @@ pub fn load_api_config_from_environment() -> APIOptions {
         evm_loader,
         key_for_config,
         db_config,
+        health_max_slot_lag,
     }
 }
 
@@ -5,8 +5,11 @@ use std::array::TryFromSliceError;
 use std::net::AddrParseError;
 use std::string::FromUtf8Error;
 
+use evm_loader::types::Address;
 use log::error;
+pub use neon_lib_interface::types::ErrorKind;
 use neon_lib_interface::NeonEVMLibLoadError;
+use serde_json::Value;
 use solana_cli::cli::CliError as SolanaCliError;
 use solana_client::client_error::ClientError as SolanaClientError;
 use solana_client::tpu_client::TpuSenderError as SolanaTpuSenderError;
@@ -120,6 +123,14 @@ pub enum NeonError {
     StrumParseError(#[from] strum::ParseError),
     #[error("Solana Simulator error {0:?}")]
     SolanaSimulatorError(#[from] crate::solana_simulator::Error),
+    #[error("Invalid parameters {0:?}")]
+    InvalidParameters(String),
+    #[error("Gas estimation failed: {0}")]
+    GasEstimationFailed(String, Vec<u8>),
+    #[error("Account {0} has both `state` and `stateDiff` overrides")]
+    ConflictingStateOverrides(Address),
+    #[error("Transaction is not finalized after {0} iterations")]
+    IterationLimitReached(u64),
 }
 
 impl NeonError {
@@ -165,8 +176,92 @@ impl NeonError {
             NeonError::IncorrectLibMethod => 263,
             NeonError::StrumParseError(_) => 264,
             NeonError::SolanaSimulatorError(_) => 265,
+            NeonError::InvalidParameters(_) => 266,
+            NeonError::GasEstimationFailed(_, _) => 267,
+            NeonError::ConflictingStateOverrides(_) => 268,
+            NeonError::IterationLimitReached(_) => 269,
         }
     }
+
+    /// Classification used by neon-api and neon-rpc to report the error
+    #[must_use]
+    pub const fn kind(&self) -> ErrorKind {
+        match self {
+            NeonError::PubkeyError(_)
+            | NeonError::AddrParseError(_)
+            | NeonError::FromHexError(_)
+            | NeonError::IncorrectAddress(_)
+            | NeonError::IncorrectIndex(_)
+            | NeonError::TxParametersParsingError(_)
+            | NeonError::EarlySlot(_, _)
+            | NeonError::SerdeJson(_)
+            | NeonError::IncorrectLibMethod
+            | NeonError::StrumParseError(_)
+            | NeonError::InvalidParameters(_)
+            | NeonError::ConflictingStateOverrides(_) => ErrorKind::BadRequest,
+            NeonError::AccountNotFound(_) | NeonError::AssociatedPdaNotFound(_, _) => {
+                ErrorKind::NotFound
+            }
+            NeonError::ClientError(_)
+            | NeonError::TpuSenderError(_)
+            | NeonError::ClickHouse(_)
+            | NeonError::SolanaSimulatorError(_) => ErrorKind::Upstream,
+            NeonError::TooManySteps | NeonError::GasEstimationFailed(_, _) => ErrorKind::Revert,
+            NeonError::EvmError(e) => evm_error_kind(e),
+            NeonError::IncompleteEnvironment
+            | NeonError::WrongEnvironment
+            | NeonError::EnvironmentError(_)
+            | NeonError::Panic(_)
+            | NeonError::StdIoError(_)
+            | NeonError::ProgramError(_)
+            | NeonError::SignerError(_)
+            | NeonError::CliError(_)
+            | NeonError::EvmLoaderNotSpecified
+            | NeonError::KeypairNotSpecified
+            | NeonError::IncorrectProgram(_)
+            | NeonError::AccountIsNotBpf(_)
+            | NeonError::AccountIsNotUpgradeable(_)
+            | NeonError::InvalidAssociatedPda(_, _)
+            | NeonError::InvalidChDbConfig
+            | NeonError::TransactionError(_)
+            | NeonError::BincodeError(_)
+            | NeonError::FromUtf8Error(_)
+            | NeonError::TryFromSliceError(_)
+            | NeonError::SolanaKeyForConfigNotSpecified
+            | NeonError::NeonEVMLibLoadError(_)
+            | NeonError::LoadingDBConfigError
+            | NeonError::IterationLimitReached(_) => ErrorKind::Internal,
+        }
+    }
+
+    /// Additional error data for API clients: the revert data of the transaction,
+    /// as returned by `eth_call`. `None` if the error doesn't carry revert data.
+    #[must_use]
+    pub fn data(&self) -> Option<Value> {
+        match self {
+            NeonError::GasEstimationFailed(_, result) if !result.is_empty() => {
+                Some(Value::String(format!("0x{}", hex::encode(result))))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// EVM faults are turned into the revert of the transaction by the EVM itself,
+/// so program errors are either rejected transactions or failures of the program.
+const fn evm_error_kind(error: &evm_loader::error::Error) -> ErrorKind {
+    use evm_loader::error::Error;
+
+    match error {
+        Error::RlpError(_)
+        | Error::Secp256k1Error(_)
+        | Error::InvalidTransactionNonce(_, _, _)
+        | Error::InvalidChainId(_)
+        | Error::InsufficientBalance(_, _, _)
+        | Error::OutOfGas(_, _)
+        | Error::SenderHasDeployedCode(_) => ErrorKind::BadRequest,
+        _ => ErrorKind::Internal,
+    }
 }
 
 #[derive(Debug, Error)]
@@ -175,3 +270,58 @@ pub enum NeonAPIError {
     #[error("Std I/O error. {0:?}")]
     StdIoError(#[from] std::io::Error),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{ErrorKind, NeonError};
+    use evm_loader::types::Address;
+    use solana_sdk::pubkey::Pubkey;
+
+    #[test]
+    fn test_error_kind() {
+        let not_found = NeonError::AccountNotFound(Pubkey::new_unique());
+        assert_eq!(not_found.kind(), ErrorKind::NotFound);
+        assert_eq!(not_found.kind().http_status(), 404);
+        assert!(not_found.data().is_none());
+
+        let bad_request = NeonError::InvalidParameters("{}".into());
+        assert_eq!(bad_request.kind(), ErrorKind::BadRequest);
+        assert_eq!(bad_request.kind().jsonrpc_code(), -32602);
+
+        let conflict = NeonError::ConflictingStateOverrides(Address::default());
+        assert_eq!(conflict.kind(), ErrorKind::BadRequest);
+
+        let revert = NeonError::TooManySteps;
+        assert_eq!(revert.kind(), ErrorKind::Revert);
+        assert_eq!(revert.kind().jsonrpc_code(), 3);
+        assert!(revert.data().is_none());
+
+        let estimate = NeonError::GasEstimationFailed("revert".into(), vec![0xde, 0xad]);
+        assert_eq!(estimate.kind(), ErrorKind::Revert);
+        assert_eq!(estimate.data().unwrap(), "0xdead");
+
+        let empty = NeonError::GasEstimationFailed("out of gas".into(), vec![]);
+        assert!(empty.data().is_none());
+
+        assert_eq!(NeonError::Panic(String::new()).kind(), ErrorKind::Internal);
+    }
+
+    #[test]
+    fn test_evm_error_kind() {
+        let owner = NeonError::from(evm_loader::error::Error::AccountInvalidOwner(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        ));
+        assert_eq!(owner.kind(), ErrorKind::Internal);
+
+        let custom = NeonError::from(evm_loader::error::Error::from("custom"));
+        assert_eq!(custom.kind(), ErrorKind::Internal);
+
+        let nonce = NeonError::from(evm_loader::error::Error::InvalidTransactionNonce(
+            Address::default(),
+            1,
+            0,
+        ));
+        assert_eq!(nonce.kind(), ErrorKind::BadRequest);
+    }
+}
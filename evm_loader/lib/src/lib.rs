@@ -16,6 +16,8 @@ pub mod build_info_common;
 pub mod commands;
 pub mod config;
 pub mod errors;
+pub mod metrics;
+pub mod receipt;
 pub mod rpc;
 
 pub mod solana_simulator;
@@ -44,6 +46,10 @@ use strum_macros::{AsRefStr, Display, EnumString, IntoStaticStr};
 pub enum LibMethod {
     #[strum(serialize = "emulate")]
     Emulate,
+    #[strum(serialize = "emulate_batch")]
+    EmulateBatch,
+    #[strum(serialize = "estimate_gas")]
+    EstimateGas,
     #[strum(serialize = "get_storage_at")]
     GetStorageAt,
     #[strum(serialize = "config")]
@@ -58,4 +64,8 @@ pub enum LibMethod {
     Trace,
     #[strum(serialize = "simulate_solana")]
     SimulateSolana,
+    #[strum(serialize = "metrics")]
+    Metrics,
+    #[strum(serialize = "health")]
+    Health,
 }
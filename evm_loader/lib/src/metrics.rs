@@ -0,0 +1,146 @@
+//! Prometheus metrics of neon-api and neon-rpc.
+//!
+//! neon-rpc loads neon-lib as a shared library, so metrics recorded inside the
+//! library live in the library's own registry and are exported through `LibMethod::Metrics`
+//! under the `neon_lib_` namespace, so they don't clash with the metrics of neon-rpc itself.
+
+use std::time::Duration;
+
+use lazy_static::lazy_static;
+use prometheus::{
+    exponential_buckets, proto::MetricFamily, register_histogram, register_histogram_vec,
+    register_int_counter_vec, Histogram, HistogramVec, IntCounterVec, TextEncoder,
+};
+
+pub use prometheus::TEXT_FORMAT as CONTENT_TYPE;
+
+lazy_static! {
+    static ref REQUESTS: IntCounterVec = register_int_counter_vec!(
+        "neon_requests_total",
+        "Number of processed requests",
+        &["method"]
+    )
+    .unwrap();
+    static ref REQUEST_DURATION: HistogramVec = register_histogram_vec!(
+        "neon_request_duration_seconds",
+        "Request processing time",
+        &["method"]
+    )
+    .unwrap();
+    static ref ERRORS: IntCounterVec = register_int_counter_vec!(
+        "neon_errors_total",
+        "Number of failed requests by NeonError code",
+        &["error_code"]
+    )
+    .unwrap();
+    static ref SOLANA_RPC_REQUESTS: IntCounterVec = register_int_counter_vec!(
+        "neon_solana_rpc_requests_total",
+        "Number of Solana RPC requests",
+        &["method", "status"]
+    )
+    .unwrap();
+    static ref SOLANA_RPC_DURATION: HistogramVec = register_histogram_vec!(
+        "neon_solana_rpc_duration_seconds",
+        "Solana RPC request time including retries",
+        &["method"]
+    )
+    .unwrap();
+    static ref CLICKHOUSE_QUERY_DURATION: HistogramVec = register_histogram_vec!(
+        "neon_clickhouse_query_duration_seconds",
+        "ClickHouse query time",
+        &["query"]
+    )
+    .unwrap();
+    static ref EMULATION_STEPS: Histogram = register_histogram!(
+        "neon_emulation_steps",
+        "Number of EVM steps executed by emulation",
+        exponential_buckets(100.0, 4.0, 9).unwrap()
+    )
+    .unwrap();
+    static ref EMULATION_ITERATIONS: Histogram = register_histogram!(
+        "neon_emulation_iterations",
+        "Number of Solana iterations estimated by emulation",
+        exponential_buckets(1.0, 2.0, 12).unwrap()
+    )
+    .unwrap();
+}
+
+pub fn observe_request(method: &str, duration: Duration) {
+    REQUESTS.with_label_values(&[method]).inc();
+    REQUEST_DURATION
+        .with_label_values(&[method])
+        .observe(duration.as_secs_f64());
+}
+
+pub fn observe_error(error_code: u32) {
+    ERRORS.with_label_values(&[&error_code.to_string()]).inc();
+}
+
+pub fn observe_solana_rpc(method: &str, duration: Duration, success: bool) {
+    let status = if success { "success" } else { "error" };
+
+    SOLANA_RPC_REQUESTS
+        .with_label_values(&[method, status])
+        .inc();
+    SOLANA_RPC_DURATION
+        .with_label_values(&[method])
+        .observe(duration.as_secs_f64());
+}
+
+pub fn observe_clickhouse_query(query: &str, duration: Duration) {
+    CLICKHOUSE_QUERY_DURATION
+        .with_label_values(&[query])
+        .observe(duration.as_secs_f64());
+}
+
+#[allow(clippy::cast_precision_loss)]
+pub fn observe_emulation(steps_executed: u64, iterations: u64) {
+    EMULATION_STEPS.observe(steps_executed as f64);
+    EMULATION_ITERATIONS.observe(iterations as f64);
+}
+
+/// Metrics of the default registry in the Prometheus text format
+#[must_use]
+pub fn gather() -> String {
+    encode(&prometheus::gather())
+}
+
+/// Metrics of the default registry with the `neon_` prefix replaced by `neon_{namespace}_`.
+/// Used to export the metrics of the library next to the metrics of the host process.
+#[must_use]
+pub fn gather_namespaced(namespace: &str) -> String {
+    let mut families = prometheus::gather();
+    for family in &mut families {
+        let name = family.get_name();
+        let name = format!(
+            "neon_{namespace}_{}",
+            name.strip_prefix("neon_").unwrap_or(name)
+        );
+        family.set_name(name);
+    }
+
+    encode(&families)
+}
+
+fn encode(families: &[MetricFamily]) -> String {
+    TextEncoder::new()
+        .encode_to_string(families)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gather_namespaced() {
+        observe_request("emulate", Duration::from_millis(10));
+
+        let metrics = gather_namespaced("lib");
+        assert!(metrics.contains("# TYPE neon_lib_requests_total counter"));
+        assert!(metrics.contains("neon_lib_requests_total{method=\"emulate\"}"));
+        assert!(!metrics.contains("# TYPE neon_requests_total"));
+
+        assert!(gather().contains("# TYPE neon_requests_total counter"));
+    }
+}
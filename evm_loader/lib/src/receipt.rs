@@ -0,0 +1,624 @@
+//! Ethereum receipt of a Neon transaction decoded from the `log_data` events
+//! which `evm_loader` prints as `Program data:` lines of the Solana transaction logs.
+//!
+//! An iterative transaction spans many Solana transactions, their logs are applied
+//! one by one with [`Receipt::append_logs`] in the order of execution.
+
+use base64::Engine;
+use ethnum::U256;
+use evm_loader::types::Address;
+use serde::{Deserialize, Serialize};
+use serde_with::{hex::Hex, serde_as, skip_serializing_none};
+use solana_sdk::pubkey::Pubkey;
+use web3::types::{Bytes, H256};
+
+use crate::tracing::tracers::emulation::EmulationLog;
+
+const PROGRAM_DATA: &str = "Program data: ";
+
+pub const EXIT_STOP: u8 = 0x11;
+pub const EXIT_RETURN: u8 = 0x12;
+pub const EXIT_SELFDESTRUCT: u8 = 0x13;
+pub const EXIT_REVERT: u8 = 0xD0;
+
+/// Solana transaction which executed a part of the Neon transaction
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Iteration {
+    /// EVM steps executed in the iteration
+    pub steps: u64,
+    /// EVM steps executed since the beginning of the transaction
+    pub total_steps: u64,
+    pub gas_used: u64,
+    /// The iteration is a cancel of the transaction
+    pub is_cancel: bool,
+}
+
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Receipt {
+    #[serde_as(as = "Option<Hex>")]
+    pub transaction_hash: Option<[u8; 32]>,
+    /// Address of the operator balance which received the gas payment
+    pub miner: Option<Address>,
+    /// 1 - success, 0 - revert or canceled transaction
+    pub status: u8,
+    /// Code of the `RETURN` event, `None` if the transaction is not completed or canceled
+    pub exit_code: Option<u8>,
+    pub gas_used: u64,
+    /// Address of the contract deployed by the transaction
+    pub contract_address: Option<Address>,
+    pub logs: Vec<EmulationLog>,
+    pub iterations: Vec<Iteration>,
+    pub is_canceled: bool,
+    /// Logs of the call frames which are not exited yet
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    frames: Vec<Vec<EmulationLog>>,
+}
+
+impl Receipt {
+    #[must_use]
+    pub fn from_logs<'a>(program_id: &Pubkey, logs: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut receipt = Self::default();
+        receipt.append_logs(program_id, logs);
+
+        receipt
+    }
+
+    /// Applies logs of the next Solana transaction of the Neon transaction.
+    /// Events of a failed Solana transaction are ignored, it doesn't change the state.
+    pub fn append_logs<'a>(
+        &mut self,
+        program_id: &Pubkey,
+        logs: impl IntoIterator<Item = &'a str>,
+    ) {
+        let program_id = program_id.to_string();
+
+        // `true` for `evm_loader` invocations, events of the invoked programs are skipped
+        let mut invocations: Vec<bool> = Vec::new();
+        let mut events: Vec<Vec<Vec<u8>>> = Vec::new();
+
+        for line in logs {
+            if line.starts_with(PROGRAM_DATA) {
+                if invocations.last() == Some(&true) {
+                    events.extend(parse_program_data(line));
+                }
+                continue;
+            }
+
+            let mut words = line.split(' ');
+            let (Some("Program"), Some(id), Some(action)) =
+                (words.next(), words.next(), words.next())
+            else {
+                continue;
+            };
+            if id.ends_with(':') {
+                continue;
+            }
+
+            match action {
+                "invoke" => invocations.push(id == program_id),
+                "success" => {
+                    invocations.pop();
+                }
+                "failed:" => return,
+                _ => {}
+            }
+        }
+
+        self.apply_events(&events);
+    }
+
+    #[must_use]
+    pub const fn is_completed(&self) -> bool {
+        self.exit_code.is_some() || self.is_canceled
+    }
+
+    fn apply_events(&mut self, events: &[Vec<Vec<u8>>]) {
+        if self.is_restarted(events) {
+            self.restart();
+        }
+
+        let mut steps: Option<(u64, u64)> = None;
+
+        for event in events {
+            let Some((name, fields)) = event.split_first() else {
+                continue;
+            };
+
+            match name.as_slice() {
+                b"HASH" => {
+                    if let Some(hash) = fields
+                        .first()
+                        .and_then(|hash| hash.as_slice().try_into().ok())
+                    {
+                        self.transaction_hash = Some(hash);
+                    }
+                }
+                b"MINER" => {
+                    if let Some(address) = fields.first().map(Vec::as_slice).and_then(parse_address)
+                    {
+                        self.miner = Some(address);
+                    }
+                }
+                b"STEPS" => {
+                    steps = parse_u64(fields.first()).zip(parse_u64(fields.get(1)));
+                }
+                b"GAS" => {
+                    let (Some(gas_used), Some(total)) =
+                        (parse_gas(fields.first()), parse_gas(fields.get(1)))
+                    else {
+                        continue;
+                    };
+                    self.gas_used = total;
+
+                    // Cancel reports the gas without executing any steps
+                    let is_cancel = steps.is_none();
+                    let (steps, total_steps) = steps.take().unwrap_or_else(|| {
+                        let total_steps = self.iterations.last().map_or(0, |i| i.total_steps);
+                        (0, total_steps)
+                    });
+                    self.iterations.push(Iteration {
+                        steps,
+                        total_steps,
+                        gas_used,
+                        is_cancel,
+                    });
+
+                    if is_cancel {
+                        self.cancel();
+                    }
+                }
+                b"ENTER" => {
+                    let address = fields.get(1).map(Vec::as_slice).and_then(parse_address);
+                    if self.frames.is_empty()
+                        && fields.first().map(Vec::as_slice) == Some(b"CREATE")
+                    {
+                        self.contract_address = address;
+                    }
+                    self.frames.push(Vec::new());
+                }
+                b"EXIT" => {
+                    let Some(logs) = self.frames.pop() else {
+                        continue;
+                    };
+                    if fields.first().map(Vec::as_slice) != Some(b"REVERT") {
+                        self.frame_logs().extend(logs);
+                    }
+                }
+                b"RETURN" => {
+                    let code = fields.first().and_then(|code| code.first().copied());
+                    self.exit_code = code;
+                    self.status = u8::from(matches!(
+                        code,
+                        Some(EXIT_STOP | EXIT_RETURN | EXIT_SELFDESTRUCT)
+                    ));
+                    if self.status == 0 {
+                        self.logs.clear();
+                    }
+                    self.frames.clear();
+                }
+                b"LOG0" | b"LOG1" | b"LOG2" | b"LOG3" | b"LOG4" => {
+                    if let Some(log) = parse_log(fields) {
+                        self.frame_logs().push(log);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Logs of the current call frame
+    fn frame_logs(&mut self) -> &mut Vec<EmulationLog> {
+        self.frames.last_mut().unwrap_or(&mut self.logs)
+    }
+
+    /// The program executes the transaction from the beginning again
+    /// if it can't be continued, the total steps of the restarted iteration go backwards
+    fn is_restarted(&self, events: &[Vec<Vec<u8>>]) -> bool {
+        let Some(last) = self.iterations.last() else {
+            return false;
+        };
+
+        events
+            .iter()
+            .filter_map(|event| event.split_first())
+            .find(|(name, _)| name.as_slice() == b"STEPS")
+            .and_then(|(_, fields)| parse_u64(fields.get(1)))
+            .is_some_and(|total_steps| total_steps < last.total_steps)
+    }
+
+    /// Drops results of the execution before the restart, iterations and gas are kept
+    fn restart(&mut self) {
+        self.logs.clear();
+        self.frames.clear();
+        self.contract_address = None;
+    }
+
+    fn cancel(&mut self) {
+        self.is_canceled = true;
+        self.status = 0;
+        self.logs.clear();
+        self.frames.clear();
+    }
+}
+
+/// Splits `Program data:` line into the decoded `log_data` fields
+fn parse_program_data(line: &str) -> Option<Vec<Vec<u8>>> {
+    let data = line.strip_prefix(PROGRAM_DATA)?;
+
+    data.split(' ')
+        .map(|field| base64::engine::general_purpose::STANDARD.decode(field).ok())
+        .collect()
+}
+
+fn parse_address(field: &[u8]) -> Option<Address> {
+    <[u8; 20]>::try_from(field).ok().map(Address::from)
+}
+
+fn parse_u64(field: Option<&Vec<u8>>) -> Option<u64> {
+    let bytes = <[u8; 8]>::try_from(field?.as_slice()).ok()?;
+    Some(u64::from_le_bytes(bytes))
+}
+
+fn parse_gas(field: Option<&Vec<u8>>) -> Option<u64> {
+    let bytes = <[u8; 32]>::try_from(field?.as_slice()).ok()?;
+    let gas = U256::from_le_bytes(bytes);
+
+    Some(u64::try_from(gas).unwrap_or(u64::MAX))
+}
+
+/// `LOGn` fields: address, topics count, topics, data
+fn parse_log(fields: &[Vec<u8>]) -> Option<EmulationLog> {
+    let address = parse_address(fields.first()?)?;
+    let count = usize::from(*fields.get(1)?.first()?);
+
+    let topics = fields
+        .get(2..2 + count)?
+        .iter()
+        .map(|topic| <[u8; 32]>::try_from(topic.as_slice()).ok().map(H256::from))
+        .collect::<Option<Vec<_>>>()?;
+    let data = fields.get(2 + count).cloned().unwrap_or_default();
+
+    Some(EmulationLog {
+        address,
+        topics,
+        data: Bytes(data),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    const PROGRAM_ID: &str = "eeLSJgWzzxrqKv1UxtRVVH8FX3qCQWUs9QuAjJpETGU";
+
+    const DEPLOY: &[&str] = &[
+        "Program ComputeBudget111111111111111111111111111111 invoke [1]",
+        "Program ComputeBudget111111111111111111111111111111 success",
+        "Program ComputeBudget111111111111111111111111111111 invoke [1]",
+        "Program ComputeBudget111111111111111111111111111111 success",
+        "Program eeLSJgWzzxrqKv1UxtRVVH8FX3qCQWUs9QuAjJpETGU invoke [1]",
+        "Program log: Instruction: Execute Transaction from Instruction",
+        "Program data: SEFTSA== Wx8KPox9SyqebxwNO4p+X0wtGwqfjn1sW0o5KBcmNUQ=",
+        "Program data: TUlORVI= 4LGtSsil/Yu/PAsbjUvRw/PQ9qE=",
+        "Program data: RU5URVI= Q1JFQVRF MzMzMzMzMzMzMzMzMzMzMzMzMzM=",
+        "Program data: TE9HMQ== MzMzMzMzMzMzMzMzMzMzMzMzMzM= AQ== 3fJSrRviyJtpwrBo/DeNqpUrp/FjxKEWKPVaTfUjs+8= Kg==",
+        "Program data: RVhJVA== UkVUVVJO",
+        "Program data: U1RFUFM= eAAAAAAAAAA= eAAAAAAAAAA=",
+        "Program data: R0FT SO4AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA= SO4AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=",
+        "Program log: exit_status=0x12",
+        "Program data: UkVUVVJO Eg==",
+        "Program eeLSJgWzzxrqKv1UxtRVVH8FX3qCQWUs9QuAjJpETGU consumed 1000 of 1400000 compute units",
+        "Program eeLSJgWzzxrqKv1UxtRVVH8FX3qCQWUs9QuAjJpETGU success",
+    ];
+
+    const ITERATION_1: &[&str] = &[
+        "Program ComputeBudget111111111111111111111111111111 invoke [1]",
+        "Program ComputeBudget111111111111111111111111111111 success",
+        "Program ComputeBudget111111111111111111111111111111 invoke [1]",
+        "Program ComputeBudget111111111111111111111111111111 success",
+        "Program eeLSJgWzzxrqKv1UxtRVVH8FX3qCQWUs9QuAjJpETGU invoke [1]",
+        "Program log: Instruction: Execute Transaction from Account Data Iterative",
+        "Program data: SEFTSA== Wx8KPox9SyqebxwNO4p+X0wtGwqfjn1sW0o5KBcmNUQ=",
+        "Program data: TUlORVI= 4LGtSsil/Yu/PAsbjUvRw/PQ9qE=",
+        "Program data: RU5URVI= Q0FMTA== ERERERERERERERERERERERERERE=",
+        "Program data: TE9HMA== ERERERERERERERERERERERERERE= AA== Zmlyc3Q=",
+        "Program data: RU5URVI= Q0FMTA== IiIiIiIiIiIiIiIiIiIiIiIiIiI=",
+        "Program data: TE9HMQ== IiIiIiIiIiIiIiIiIiIiIiIiIiI= AQ== 3fJSrRviyJtpwrBo/DeNqpUrp/FjxKEWKPVaTfUjs+8= ",
+        "Program data: U1RFUFM= 9AEAAAAAAAA= 9AEAAAAAAAA=",
+        "Program data: R0FT iBMAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA= iBMAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=",
+        "Program eeLSJgWzzxrqKv1UxtRVVH8FX3qCQWUs9QuAjJpETGU consumed 1000 of 1400000 compute units",
+        "Program eeLSJgWzzxrqKv1UxtRVVH8FX3qCQWUs9QuAjJpETGU success",
+    ];
+
+    const ITERATION_2: &[&str] = &[
+        "Program ComputeBudget111111111111111111111111111111 invoke [1]",
+        "Program ComputeBudget111111111111111111111111111111 success",
+        "Program ComputeBudget111111111111111111111111111111 invoke [1]",
+        "Program ComputeBudget111111111111111111111111111111 success",
+        "Program eeLSJgWzzxrqKv1UxtRVVH8FX3qCQWUs9QuAjJpETGU invoke [1]",
+        "Program log: Instruction: Execute Transaction from Account Data Iterative",
+        "Program data: SEFTSA== Wx8KPox9SyqebxwNO4p+X0wtGwqfjn1sW0o5KBcmNUQ=",
+        "Program data: TUlORVI= 4LGtSsil/Yu/PAsbjUvRw/PQ9qE=",
+        "Program data: RVhJVA== UkVWRVJU b29wcw==",
+        "Program data: RU5URVI= Q0FMTA== MzMzMzMzMzMzMzMzMzMzMzMzMzM=",
+        "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA invoke [2]",
+        "Program data: TE9HMA== MzMzMzMzMzMzMzMzMzMzMzMzMzM= AA== aWdub3JlZA==",
+        "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA success",
+        "Program data: TE9HMg== MzMzMzMzMzMzMzMzMzMzMzMzMzM= Ag== 3fJSrRviyJtpwrBo/DeNqpUrp/FjxKEWKPVaTfUjs+8= AAAAAAAAAAAAAAAAERERERERERERERERERERERERERE= c2Vjb25k",
+        "Program data: RVhJVA== U1RPUA==",
+        "Program data: U1RFUFM= LAEAAAAAAAA= IAMAAAAAAAA=",
+        "Program data: R0FT iBMAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA= ECcAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=",
+        "Program eeLSJgWzzxrqKv1UxtRVVH8FX3qCQWUs9QuAjJpETGU consumed 1000 of 1400000 compute units",
+        "Program eeLSJgWzzxrqKv1UxtRVVH8FX3qCQWUs9QuAjJpETGU success",
+    ];
+
+    const ITERATION_FAILED: &[&str] = &[
+        "Program ComputeBudget111111111111111111111111111111 invoke [1]",
+        "Program ComputeBudget111111111111111111111111111111 success",
+        "Program ComputeBudget111111111111111111111111111111 invoke [1]",
+        "Program ComputeBudget111111111111111111111111111111 success",
+        "Program eeLSJgWzzxrqKv1UxtRVVH8FX3qCQWUs9QuAjJpETGU invoke [1]",
+        "Program log: Instruction: Execute Transaction from Account Data Iterative",
+        "Program data: SEFTSA== Wx8KPox9SyqebxwNO4p+X0wtGwqfjn1sW0o5KBcmNUQ=",
+        "Program data: TUlORVI= 4LGtSsil/Yu/PAsbjUvRw/PQ9qE=",
+        "Program data: RVhJVA== UkVUVVJO",
+        "Program data: U1RFUFM= CgAAAAAAAAA= KgMAAAAAAAA=",
+        "Program data: R0FT iBMAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA= mDoAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=",
+        "Program eeLSJgWzzxrqKv1UxtRVVH8FX3qCQWUs9QuAjJpETGU consumed 1000 of 1400000 compute units",
+        "Program eeLSJgWzzxrqKv1UxtRVVH8FX3qCQWUs9QuAjJpETGU failed: custom program error: 0x1",
+    ];
+
+    const ITERATION_3: &[&str] = &[
+        "Program ComputeBudget111111111111111111111111111111 invoke [1]",
+        "Program ComputeBudget111111111111111111111111111111 success",
+        "Program ComputeBudget111111111111111111111111111111 invoke [1]",
+        "Program ComputeBudget111111111111111111111111111111 success",
+        "Program eeLSJgWzzxrqKv1UxtRVVH8FX3qCQWUs9QuAjJpETGU invoke [1]",
+        "Program log: Instruction: Execute Transaction from Account Data Iterative",
+        "Program data: SEFTSA== Wx8KPox9SyqebxwNO4p+X0wtGwqfjn1sW0o5KBcmNUQ=",
+        "Program data: TUlORVI= 4LGtSsil/Yu/PAsbjUvRw/PQ9qE=",
+        "Program data: RVhJVA== UkVUVVJO",
+        "Program data: U1RFUFM= CgAAAAAAAAA= KgMAAAAAAAA=",
+        "Program data: R0FT iBMAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA= mDoAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=",
+        "Program log: exit_status=0x12",
+        "Program data: UkVUVVJO Eg==",
+        "Program eeLSJgWzzxrqKv1UxtRVVH8FX3qCQWUs9QuAjJpETGU consumed 1000 of 1400000 compute units",
+        "Program eeLSJgWzzxrqKv1UxtRVVH8FX3qCQWUs9QuAjJpETGU success",
+    ];
+
+    const ITERATION_CREATE: &[&str] = &[
+        "Program eeLSJgWzzxrqKv1UxtRVVH8FX3qCQWUs9QuAjJpETGU invoke [1]",
+        "Program log: Instruction: Execute Transaction from Account Data Iterative",
+        "Program data: SEFTSA== Wx8KPox9SyqebxwNO4p+X0wtGwqfjn1sW0o5KBcmNUQ=",
+        "Program data: TUlORVI= 4LGtSsil/Yu/PAsbjUvRw/PQ9qE=",
+        "Program data: RU5URVI= Q1JFQVRF MzMzMzMzMzMzMzMzMzMzMzMzMzM=",
+        "Program data: TE9HMA== MzMzMzMzMzMzMzMzMzMzMzMzMzM= AA== Zmlyc3Q=",
+        "Program data: U1RFUFM= 9AEAAAAAAAA= 9AEAAAAAAAA=",
+        "Program data: R0FT iBMAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA= iBMAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=",
+        "Program eeLSJgWzzxrqKv1UxtRVVH8FX3qCQWUs9QuAjJpETGU consumed 1000 of 1400000 compute units",
+        "Program eeLSJgWzzxrqKv1UxtRVVH8FX3qCQWUs9QuAjJpETGU success",
+    ];
+
+    // The transaction is executed from the beginning, total steps go backwards
+    const ITERATION_RESTARTED: &[&str] = &[
+        "Program eeLSJgWzzxrqKv1UxtRVVH8FX3qCQWUs9QuAjJpETGU invoke [1]",
+        "Program log: Instruction: Execute Transaction from Account Data Iterative",
+        "Program data: SEFTSA== Wx8KPox9SyqebxwNO4p+X0wtGwqfjn1sW0o5KBcmNUQ=",
+        "Program data: TUlORVI= 4LGtSsil/Yu/PAsbjUvRw/PQ9qE=",
+        "Program data: RU5URVI= Q0FMTA== ERERERERERERERERERERERERERE=",
+        "Program data: TE9HMA== ERERERERERERERERERERERERERE= AA== YWdhaW4=",
+        "Program data: U1RFUFM= yAAAAAAAAAA= yAAAAAAAAAA=",
+        "Program data: R0FT iBMAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA= ECcAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=",
+        "Program eeLSJgWzzxrqKv1UxtRVVH8FX3qCQWUs9QuAjJpETGU consumed 1000 of 1400000 compute units",
+        "Program eeLSJgWzzxrqKv1UxtRVVH8FX3qCQWUs9QuAjJpETGU success",
+    ];
+
+    const CANCEL: &[&str] = &[
+        "Program eeLSJgWzzxrqKv1UxtRVVH8FX3qCQWUs9QuAjJpETGU invoke [1]",
+        "Program log: Instruction: Cancel Transaction",
+        "Program data: SEFTSA== Wx8KPox9SyqebxwNO4p+X0wtGwqfjn1sW0o5KBcmNUQ=",
+        "Program data: TUlORVI= 4LGtSsil/Yu/PAsbjUvRw/PQ9qE=",
+        "Program data: R0FT AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA= ECcAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=",
+        "Program eeLSJgWzzxrqKv1UxtRVVH8FX3qCQWUs9QuAjJpETGU consumed 1000 of 1400000 compute units",
+        "Program eeLSJgWzzxrqKv1UxtRVVH8FX3qCQWUs9QuAjJpETGU success",
+    ];
+
+    const REVERT: &[&str] = &[
+        "Program ComputeBudget111111111111111111111111111111 invoke [1]",
+        "Program ComputeBudget111111111111111111111111111111 success",
+        "Program ComputeBudget111111111111111111111111111111 invoke [1]",
+        "Program ComputeBudget111111111111111111111111111111 success",
+        "Program eeLSJgWzzxrqKv1UxtRVVH8FX3qCQWUs9QuAjJpETGU invoke [1]",
+        "Program log: Instruction: Execute Transaction from Instruction",
+        "Program data: SEFTSA== Wx8KPox9SyqebxwNO4p+X0wtGwqfjn1sW0o5KBcmNUQ=",
+        "Program data: TUlORVI= 4LGtSsil/Yu/PAsbjUvRw/PQ9qE=",
+        "Program data: RU5URVI= Q0FMTA== ERERERERERERERERERERERERERE=",
+        "Program data: TE9HMA== ERERERERERERERERERERERERERE= AA== cmV2ZXJ0ZWQ=",
+        "Program data: RVhJVA== UkVWRVJU ",
+        "Program data: U1RFUFM= KAAAAAAAAAA= KAAAAAAAAAA=",
+        "Program data: R0FT qGEAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA= qGEAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=",
+        "Program log: exit_status=0xD0",
+        "Program data: UkVUVVJO 0A==",
+        "Program eeLSJgWzzxrqKv1UxtRVVH8FX3qCQWUs9QuAjJpETGU consumed 1000 of 1400000 compute units",
+        "Program eeLSJgWzzxrqKv1UxtRVVH8FX3qCQWUs9QuAjJpETGU success",
+    ];
+
+    fn program_id() -> Pubkey {
+        Pubkey::from_str(PROGRAM_ID).unwrap()
+    }
+
+    fn address(byte: u8) -> Address {
+        Address::from([byte; 20])
+    }
+
+    fn receipt(transactions: &[&[&str]]) -> Receipt {
+        let mut receipt = Receipt::default();
+        for logs in transactions {
+            receipt.append_logs(&program_id(), logs.iter().copied());
+        }
+
+        receipt
+    }
+
+    #[test]
+    fn test_deploy() {
+        let receipt = receipt(&[DEPLOY]);
+
+        assert!(receipt.is_completed());
+        assert_eq!(receipt.status, 1);
+        assert_eq!(receipt.exit_code, Some(EXIT_RETURN));
+        assert_eq!(receipt.gas_used, 61000);
+        assert_eq!(receipt.contract_address, Some(address(0x33)));
+        assert_eq!(
+            receipt.miner,
+            Some(Address::from_hex("0xe0b1ad4ac8a5fd8bbf3c0b1b8d4bd1c3f3d0f6a1").unwrap())
+        );
+        assert_eq!(
+            hex::encode(receipt.transaction_hash.unwrap()),
+            "5b1f0a3e8c7d4b2a9e6f1c0d3b8a7e5f4c2d1b0a9f8e7d6c5b4a392817263544"
+        );
+
+        assert_eq!(receipt.logs.len(), 1);
+        assert_eq!(receipt.logs[0].address, address(0x33));
+        assert_eq!(
+            receipt.logs[0].topics,
+            vec![H256::from_str(
+                "ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"
+            )
+            .unwrap()]
+        );
+        assert_eq!(receipt.logs[0].data.0, vec![0x2a]);
+
+        assert_eq!(
+            receipt.iterations,
+            vec![Iteration {
+                steps: 120,
+                total_steps: 120,
+                gas_used: 61000,
+                is_cancel: false,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_iterative() {
+        let receipt = receipt(&[ITERATION_1, ITERATION_2, ITERATION_FAILED, ITERATION_3]);
+
+        assert!(receipt.is_completed());
+        assert_eq!(receipt.status, 1);
+        assert_eq!(receipt.gas_used, 15000);
+        assert_eq!(receipt.contract_address, None);
+
+        // Log of the reverted frame and the log of the invoked program are skipped
+        let logs: Vec<_> = receipt
+            .logs
+            .iter()
+            .map(|log| (log.address, log.topics.len(), log.data.0.clone()))
+            .collect();
+        assert_eq!(
+            logs,
+            vec![
+                (address(0x11), 0, b"first".to_vec()),
+                (address(0x33), 2, b"second".to_vec()),
+            ]
+        );
+
+        let steps: Vec<_> = receipt
+            .iterations
+            .iter()
+            .map(|i| (i.steps, i.total_steps, i.gas_used))
+            .collect();
+        assert_eq!(
+            steps,
+            vec![(500, 500, 5000), (300, 800, 5000), (10, 810, 5000)]
+        );
+    }
+
+    #[test]
+    fn test_restarted() {
+        let receipt = receipt(&[ITERATION_CREATE, ITERATION_RESTARTED, ITERATION_3]);
+
+        assert!(receipt.is_completed());
+        assert_eq!(receipt.status, 1);
+        assert_eq!(receipt.gas_used, 15000);
+
+        // Contract and logs of the execution before the restart are dropped
+        assert_eq!(receipt.contract_address, None);
+        let logs: Vec<_> = receipt
+            .logs
+            .iter()
+            .map(|log| (log.address, log.data.0.clone()))
+            .collect();
+        assert_eq!(logs, vec![(address(0x11), b"again".to_vec())]);
+
+        let steps: Vec<_> = receipt
+            .iterations
+            .iter()
+            .map(|i| (i.steps, i.total_steps))
+            .collect();
+        assert_eq!(steps, vec![(500, 500), (200, 200), (10, 810)]);
+    }
+
+    #[test]
+    fn test_not_completed() {
+        let receipt = receipt(&[ITERATION_1, ITERATION_2]);
+
+        assert!(!receipt.is_completed());
+        assert_eq!(receipt.status, 0);
+        assert_eq!(receipt.exit_code, None);
+        assert_eq!(receipt.iterations.len(), 2);
+        assert!(receipt.logs.is_empty());
+
+        // Incomplete receipt can be stored and continued later
+        let json = serde_json::to_string(&receipt).unwrap();
+        let mut receipt: Receipt = serde_json::from_str(&json).unwrap();
+        receipt.append_logs(&program_id(), ITERATION_3.iter().copied());
+
+        assert_eq!(receipt.status, 1);
+        assert_eq!(receipt.logs.len(), 2);
+    }
+
+    #[test]
+    fn test_canceled() {
+        let receipt = receipt(&[ITERATION_1, ITERATION_2, CANCEL]);
+
+        assert!(receipt.is_completed());
+        assert!(receipt.is_canceled);
+        assert_eq!(receipt.status, 0);
+        assert_eq!(receipt.exit_code, None);
+        assert_eq!(receipt.gas_used, 10000);
+        assert!(receipt.logs.is_empty());
+        assert_eq!(
+            receipt.iterations.last(),
+            Some(&Iteration {
+                steps: 0,
+                total_steps: 800,
+                gas_used: 0,
+                is_cancel: true,
+            })
+        );
+    }
+
+    #[test]
+    fn test_revert() {
+        let receipt = receipt(&[REVERT]);
+
+        assert!(receipt.is_completed());
+        assert_eq!(receipt.status, 0);
+        assert_eq!(receipt.exit_code, Some(EXIT_REVERT));
+        assert_eq!(receipt.gas_used, 25000);
+        assert!(receipt.logs.is_empty());
+    }
+
+    #[test]
+    fn test_other_program() {
+        let receipt = Receipt::from_logs(&Pubkey::new_unique(), DEPLOY.iter().copied());
+
+        assert!(!receipt.is_completed());
+        assert!(receipt.iterations.is_empty());
+        assert!(receipt.logs.is_empty());
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{config::APIOptions, Config};
+use crate::{config::APIOptions, metrics, Config};
 
 use super::Rpc;
 use async_trait::async_trait;
@@ -15,7 +15,11 @@ use solana_sdk::{
     clock::{Slot, UnixTimestamp},
     pubkey::Pubkey,
 };
-use std::{error::Error, ops::Deref, time::Duration};
+use std::{
+    error::Error,
+    ops::Deref,
+    time::{Duration, Instant},
+};
 use std::{future::Future, sync::Arc};
 
 fn should_retry(e: &ClientError) -> bool {
@@ -46,7 +50,19 @@ fn should_retry(e: &ClientError) -> bool {
     io_error.kind() == std::io::ErrorKind::ConnectionReset
 }
 
-async fn with_retries<F, Fut, R>(max_retries: usize, request: F) -> ClientResult<R>
+async fn with_retries<F, Fut, R>(method: &str, max_retries: usize, request: F) -> ClientResult<R>
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = ClientResult<R>>,
+{
+    let time_start = Instant::now();
+    let result = retry(max_retries, request).await;
+    metrics::observe_solana_rpc(method, time_start.elapsed(), result.is_ok());
+
+    result
+}
+
+async fn retry<F, Fut, R>(max_retries: usize, request: F) -> ClientResult<R>
 where
     F: Fn() -> Fut,
     Fut: Future<Output = ClientResult<R>>,
@@ -125,7 +141,8 @@ impl Rpc for CloneRpcClient {
             self.send(RpcRequest::GetAccountInfo, params)
         };
 
-        let response: serde_json::Value = with_retries(self.max_retries, request).await?;
+        let response: serde_json::Value =
+            with_retries("getAccountInfo", self.max_retries, request).await?;
         let response: Response<Option<UiAccount>> = serde_json::from_value(response)?;
 
         let account = response.value.and_then(|v| v.decode());
@@ -149,7 +166,8 @@ impl Rpc for CloneRpcClient {
         for chunk in pubkeys.chunks(100) {
             let request = || self.rpc.get_multiple_accounts(chunk);
 
-            let mut accounts = with_retries(self.max_retries, request).await?;
+            let mut accounts =
+                with_retries("getMultipleAccounts", self.max_retries, request).await?;
             result.append(&mut accounts);
         }
 
@@ -157,15 +175,17 @@ impl Rpc for CloneRpcClient {
     }
 
     async fn get_block_time(&self, slot: Slot) -> ClientResult<UnixTimestamp> {
-        with_retries(self.max_retries, || self.rpc.get_block_time(slot)).await
+        with_retries("getBlockTime", self.max_retries, || {
+            self.rpc.get_block_time(slot)
+        })
+        .await
     }
 
     async fn get_slot(&self) -> ClientResult<Slot> {
-        with_retries(self.max_retries, || self.rpc.get_slot()).await
+        with_retries("getSlot", self.max_retries, || self.rpc.get_slot()).await
     }
 
     async fn get_deactivated_solana_features(&self) -> ClientResult<Vec<Pubkey>> {
-        use std::time::{Duration, Instant};
         use tokio::sync::Mutex;
 
         struct Cache {
@@ -1,3 +1,4 @@
+use std::path::Path;
 use std::sync::Arc;
 
 use solana_accounts_db::transaction_results::{
@@ -12,6 +13,7 @@ use solana_sdk::{
     address_lookup_table, bpf_loader, bpf_loader_upgradeable,
     hash::Hash,
     pubkey::Pubkey,
+    rent::Rent,
     signature::Keypair,
     sysvar::{Sysvar, SysvarId},
     transaction::{
@@ -140,6 +142,10 @@ impl SolanaSimulator {
         self.bank().slot()
     }
 
+    pub fn rent(&self) -> &Rent {
+        &self.bank().rent_collector().rent
+    }
+
     pub fn replace_blockhash(&mut self, blockhash: &Hash) {
         self.bank().register_recent_blockhash(blockhash);
     }
@@ -167,6 +173,18 @@ impl SolanaSimulator {
         );
     }
 
+    /// Replaces the program with the ELF file, so undeployed versions can be tested
+    pub fn load_program(
+        &mut self,
+        program_id: &Pubkey,
+        path: impl AsRef<Path>,
+    ) -> Result<(), Error> {
+        let data = std::fs::read(path)?;
+        self.set_program_account(program_id, data);
+
+        Ok(())
+    }
+
     pub fn set_account(&mut self, pubkey: &Pubkey, account: &Account) {
         self.bank().store_account(pubkey, account);
     }
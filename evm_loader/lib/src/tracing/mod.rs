@@ -6,6 +6,8 @@ use web3::types::{Bytes, H256};
 
 use evm_loader::types::Address;
 
+use crate::errors::NeonError;
+
 pub mod tracers;
 
 /// See <https://github.com/ethereum/go-ethereum/blob/master/internal/ethapi/api.go#L993>
@@ -13,17 +15,12 @@ pub mod tracers;
 #[serde(rename_all = "camelCase")]
 pub struct BlockOverrides {
     pub number: Option<u64>,
-    #[allow(unused)]
-    pub difficulty: Option<U256>, // NOT SUPPORTED by Neon EVM
+    pub difficulty: Option<U256>, // Used as `prevrandao` when `random` is not set
     pub time: Option<i64>,
-    #[allow(unused)]
-    pub gas_limit: Option<u64>, // NOT SUPPORTED BY Neon EVM
-    #[allow(unused)]
-    pub coinbase: Option<Address>, // NOT SUPPORTED BY Neon EVM
-    #[allow(unused)]
-    pub random: Option<U256>, // NOT SUPPORTED BY Neon EVM
-    #[allow(unused)]
-    pub base_fee: Option<U256>, // NOT SUPPORTED BY Neon EVM
+    pub gas_limit: Option<u64>,
+    pub coinbase: Option<Address>,
+    pub random: Option<U256>,
+    pub base_fee: Option<U256>,
 }
 
 /// See <https://github.com/ethereum/go-ethereum/blob/master/internal/ethapi/api.go#L942>
@@ -38,22 +35,33 @@ pub struct AccountOverride {
 }
 
 impl AccountOverride {
-    #[must_use]
-    pub fn storage(&self, index: U256) -> Option<[u8; 32]> {
-        match (&self.state, &self.state_diff) {
-            (None, None) => None,
-            (Some(_), Some(_)) => {
-                panic!("Account has both `state` and `stateDiff` overrides")
-            }
-            (Some(state), None) => {
-                return state
-                    .get(&H256::from(index.to_be_bytes()))
-                    .map(|value| value.to_fixed_bytes())
-            }
-            (None, Some(state_diff)) => state_diff
-                .get(&H256::from(index.to_be_bytes()))
-                .map(|v| v.to_fixed_bytes()),
+    /// geth rejects overrides which set both `state` and `stateDiff`
+    pub fn validate(&self, address: Address) -> Result<(), NeonError> {
+        if self.state.is_some() && self.state_diff.is_some() {
+            return Err(NeonError::ConflictingStateOverrides(address));
         }
+
+        Ok(())
+    }
+
+    /// `state` replaces the whole account storage, `stateDiff` patches individual slots
+    #[must_use]
+    pub const fn replaces_storage(&self) -> bool {
+        self.state.is_some()
+    }
+
+    /// Slots written by `state` or `stateDiff`
+    pub fn storage_slots(&self) -> impl Iterator<Item = (U256, [u8; 32])> + '_ {
+        self.state
+            .iter()
+            .chain(self.state_diff.iter())
+            .flatten()
+            .map(|(index, value)| {
+                (
+                    U256::from_be_bytes(index.to_fixed_bytes()),
+                    value.to_fixed_bytes(),
+                )
+            })
     }
 }
 
@@ -0,0 +1,253 @@
+use async_trait::async_trait;
+use evm_loader::evm::database::Database;
+use evm_loader::evm::opcode_table::{self, Opcode};
+use evm_loader::evm::tracing::{Event, EventListener};
+use evm_loader::evm::MAX_MEMORY_SIZE;
+use evm_loader::types::Address;
+use serde::{Deserialize, Serialize};
+use web3::types::{Bytes, H256, U256};
+
+use crate::tracing::tracers::prestate_tracer::{
+    build_prestate_tracer_diff_mode_result, PrestateTracerDiffModeResult,
+};
+use crate::tracing::tracers::state_diff::StateDiffTracer;
+use crate::types::TxParams;
+
+/// EVM log emitted by `LOG0`-`LOG4` opcodes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmulationLog {
+    pub address: Address,
+    pub topics: Vec<H256>,
+    pub data: Bytes,
+}
+
+/// Memory range read by `LOG` opcodes, the part past the end of memory is read as zeros.
+/// `None` if the range exceeds the EVM memory limit, the opcode fails in that case.
+fn read_memory(memory: &[u8], offset: U256, length: U256) -> Option<Vec<u8>> {
+    if length.is_zero() {
+        return Some(vec![]);
+    }
+
+    let offset = usize::try_from(offset).ok()?;
+    let length = usize::try_from(length).ok()?;
+    let end = offset
+        .checked_add(length)
+        .filter(|end| *end <= MAX_MEMORY_SIZE)?;
+
+    let mut data = vec![0; length];
+    if let Some(available) = memory.get(offset..end.min(memory.len())) {
+        data[..available.len()].copy_from_slice(available);
+    }
+
+    Some(data)
+}
+
+/// Collects logs of the call frames, logs of failed frames are discarded
+#[derive(Default)]
+struct LogCollector {
+    frames: Vec<Vec<EmulationLog>>,
+    logs: Vec<EmulationLog>,
+}
+
+impl LogCollector {
+    fn event(&mut self, event: &Event) {
+        match event {
+            Event::BeginVM { .. } => self.frames.push(vec![]),
+            Event::EndVM { status, .. } => {
+                let Some(mut frame) = self.frames.pop() else {
+                    return;
+                };
+
+                if !status.is_succeed().unwrap_or_default() {
+                    return;
+                }
+
+                match self.frames.last_mut() {
+                    Some(parent) => parent.append(&mut frame),
+                    None => self.logs.append(&mut frame),
+                }
+            }
+            Event::BeginStep {
+                context,
+                opcode,
+                stack,
+                memory,
+                ..
+            } => {
+                if !(opcode_table::LOG0.0..=opcode_table::LOG4.0).contains(&opcode.0) {
+                    return;
+                }
+
+                let topics_count = usize::from(opcode.0 - opcode_table::LOG0.0);
+                if stack.len() < 2 + topics_count {
+                    return;
+                }
+
+                let offset = U256::from(stack[stack.len() - 1]);
+                let length = U256::from(stack[stack.len() - 2]);
+                let Some(data) = read_memory(memory, offset, length) else {
+                    return;
+                };
+
+                let topics = (0..topics_count)
+                    .map(|i| H256::from(stack[stack.len() - 3 - i]))
+                    .collect();
+
+                if let Some(frame) = self.frames.last_mut() {
+                    frame.push(EmulationLog {
+                        address: context.contract,
+                        topics,
+                        data: data.into(),
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Event listener of `emulate`: feeds the requested tracer
+/// and collects logs and state diff for `EmulateResponse`
+pub struct EmulationTracer<T> {
+    tracer: Option<T>,
+    logs: Option<LogCollector>,
+    state_diff: Option<StateDiffTracer>,
+}
+
+impl<T> EmulationTracer<T> {
+    pub fn new(tracer: Option<T>, tx: &TxParams, with_logs: bool, with_state_diff: bool) -> Self {
+        Self {
+            tracer,
+            logs: with_logs.then(LogCollector::default),
+            state_diff: with_state_diff.then(|| StateDiffTracer::new(tx)),
+        }
+    }
+
+    /// Returns the tracer, collected logs and state diff
+    pub fn into_parts(
+        self,
+        emulator_gas_used: u64,
+    ) -> (
+        Option<T>,
+        Option<Vec<EmulationLog>>,
+        Option<PrestateTracerDiffModeResult>,
+    ) {
+        let logs = self.logs.map(|collector| collector.logs);
+        let state_diff = self.state_diff.map(|tracer| {
+            build_prestate_tracer_diff_mode_result(tracer.into_state_map(emulator_gas_used))
+        });
+
+        (self.tracer, logs, state_diff)
+    }
+}
+
+// Opcode is not `Clone`, so the event is rebuilt to pass it to several listeners
+fn copy_event(event: &Event) -> Event {
+    match event {
+        Event::BeginVM {
+            context,
+            chain_id,
+            input,
+            opcode,
+        } => Event::BeginVM {
+            context: *context,
+            chain_id: *chain_id,
+            input: input.clone(),
+            opcode: Opcode(opcode.0),
+        },
+        Event::EndVM {
+            context,
+            chain_id,
+            status,
+        } => Event::EndVM {
+            context: *context,
+            chain_id: *chain_id,
+            status: status.clone(),
+        },
+        Event::BeginStep {
+            context,
+            chain_id,
+            opcode,
+            pc,
+            stack,
+            memory,
+            return_data,
+        } => Event::BeginStep {
+            context: *context,
+            chain_id: *chain_id,
+            opcode: Opcode(opcode.0),
+            pc: *pc,
+            stack: stack.clone(),
+            memory: memory.clone(),
+            return_data: return_data.clone(),
+        },
+    }
+}
+
+#[async_trait(?Send)]
+impl<T: EventListener> EventListener for EmulationTracer<T> {
+    async fn event(
+        &mut self,
+        executor_state: &impl Database,
+        event: Event,
+    ) -> evm_loader::error::Result<()> {
+        if let Some(logs) = &mut self.logs {
+            logs.event(&event);
+        }
+
+        if let Some(state_diff) = &mut self.state_diff {
+            state_diff.event(executor_state, copy_event(&event)).await?;
+        }
+
+        if let Some(tracer) = &mut self.tracer {
+            tracer.event(executor_state, event).await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_memory() {
+        let memory = [1_u8, 2, 3, 4];
+
+        assert_eq!(
+            read_memory(&memory, U256::from(1), U256::from(2)),
+            Some(vec![2, 3])
+        );
+        // Zero length doesn't depend on the offset
+        assert_eq!(read_memory(&memory, U256::MAX, U256::zero()), Some(vec![]));
+        // Memory is expanded with zeros
+        assert_eq!(
+            read_memory(&memory, U256::from(2), U256::from(4)),
+            Some(vec![3, 4, 0, 0])
+        );
+        assert_eq!(
+            read_memory(&memory, U256::from(8), U256::from(2)),
+            Some(vec![0, 0])
+        );
+        assert_eq!(
+            read_memory(&memory, U256::zero(), U256::from(MAX_MEMORY_SIZE)).map(|data| data.len()),
+            Some(MAX_MEMORY_SIZE)
+        );
+    }
+
+    #[test]
+    fn test_read_memory_out_of_limits() {
+        let memory = [1_u8, 2, 3, 4];
+
+        assert_eq!(read_memory(&memory, U256::MAX, U256::one()), None);
+        assert_eq!(read_memory(&memory, U256::one(), U256::MAX), None);
+        assert_eq!(
+            read_memory(&memory, U256::from(usize::MAX), U256::from(2)),
+            None
+        );
+        assert_eq!(
+            read_memory(&memory, U256::one(), U256::from(MAX_MEMORY_SIZE)),
+            None
+        );
+    }
+}
@@ -12,6 +12,7 @@ use evm_loader::evm::tracing::EventListener;
 use serde_json::Value;
 
 pub mod call_tracer;
+pub mod emulation;
 pub mod openeth;
 pub mod prestate_tracer;
 pub mod state_diff;
@@ -1,4 +1,7 @@
 mod state_diff;
 pub mod tracer;
 
-pub use state_diff::{PrestateTracerAccount, PrestateTracerDiffModeResult, PrestateTracerState};
+pub use state_diff::{
+    build_prestate_tracer_diff_mode_result, PrestateTracerAccount, PrestateTracerDiffModeResult,
+    PrestateTracerState,
+};
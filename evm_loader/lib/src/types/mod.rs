@@ -16,8 +16,9 @@ use crate::tracing::TraceCallConfig;
 
 use ethnum::U256;
 use serde::{Deserialize, Serialize};
-use serde_with::{hex::Hex, serde_as, DisplayFromStr, OneOrMany};
+use serde_with::{base64::Base64, hex::Hex, serde_as, DisplayFromStr, OneOrMany};
 
+use crate::account_storage::SolanaOverrides;
 use crate::commands::get_config::ChainInfo;
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
@@ -128,6 +129,18 @@ pub struct SerializedAccount {
     pub data: Vec<u8>,
 }
 
+impl From<&Account> for SerializedAccount {
+    fn from(account: &Account) -> Self {
+        Self {
+            lamports: account.lamports,
+            owner: account.owner,
+            executable: account.executable,
+            rent_epoch: account.rent_epoch,
+            data: account.data.clone(),
+        }
+    }
+}
+
 impl From<&SerializedAccount> for Account {
     fn from(account: &SerializedAccount) -> Self {
         Self {
@@ -140,6 +153,19 @@ impl From<&SerializedAccount> for Account {
     }
 }
 
+/// `solana_overrides` of a request in the form used by `EmulatorAccountStorage`
+#[must_use]
+pub fn solana_overrides(
+    overrides: Option<&HashMap<Pubkey, Option<SerializedAccount>>>,
+) -> Option<SolanaOverrides> {
+    overrides.map(|overrides| {
+        overrides
+            .iter()
+            .map(|(pubkey, account)| (*pubkey, account.as_ref().map(Account::from)))
+            .collect()
+    })
+}
+
 #[serde_as]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EmulateRequest {
@@ -151,6 +177,31 @@ pub struct EmulateRequest {
     pub accounts: Vec<Pubkey>,
     #[serde_as(as = "Option<HashMap<DisplayFromStr,_>>")]
     pub solana_overrides: Option<HashMap<Pubkey, Option<SerializedAccount>>>,
+    pub exact: Option<ExactEmulateConfig>,
+    /// Return logs emitted by the transaction
+    #[serde(default)]
+    pub with_logs: bool,
+    /// Return the state diff in `prestateTracer` `diffMode` format
+    #[serde(default)]
+    pub with_state_diff: bool,
+}
+
+/// Parameters of the exact emulation through `SolanaSimulator`.
+/// Only `solana_overrides` are applied to the simulated accounts, state overrides are ignored.
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExactEmulateConfig {
+    /// Signed transaction in RLP encoding, `evm_loader` recovers the origin from it
+    #[serde_as(as = "Hex")]
+    pub raw_transaction: Vec<u8>,
+    /// Operator key, a fake operator by default. Operators missing in the registry are registered in the simulator
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub operator: Option<Pubkey>,
+    /// Address of the operator balance account, zero address by default
+    pub operator_address: Option<Address>,
+    pub steps_per_iteration: Option<u64>,
+    pub max_iterations: Option<u64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -161,6 +212,58 @@ pub struct EmulateApiRequest {
     pub tx_index_in_block: Option<u64>,
 }
 
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmulateBatchRequest {
+    pub txs: Vec<TxParams>,
+    pub step_limit: Option<u64>,
+    pub chains: Option<Vec<ChainInfo>>,
+    pub trace_config: Option<TraceCallConfig>,
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    pub accounts: Vec<Pubkey>,
+    #[serde_as(as = "Option<HashMap<DisplayFromStr,_>>")]
+    pub solana_overrides: Option<HashMap<Pubkey, Option<SerializedAccount>>>,
+    /// Collect traces of every transaction with `trace_config`
+    #[serde(default)]
+    pub with_traces: bool,
+    /// Return logs emitted by every transaction
+    #[serde(default)]
+    pub with_logs: bool,
+    /// Return the state diff of every transaction
+    #[serde(default)]
+    pub with_state_diff: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmulateBatchApiRequest {
+    #[serde(flatten)]
+    pub body: EmulateBatchRequest,
+    pub slot: Option<u64>,
+    pub tx_index_in_block: Option<u64>,
+}
+
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EstimateGasRequest {
+    /// `gas_limit` is the upper bound of the search, `u64::MAX` by default
+    pub tx: TxParams,
+    pub step_limit: Option<u64>,
+    pub chains: Option<Vec<ChainInfo>>,
+    pub trace_config: Option<TraceCallConfig>,
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    pub accounts: Vec<Pubkey>,
+    #[serde_as(as = "Option<HashMap<DisplayFromStr,_>>")]
+    pub solana_overrides: Option<HashMap<Pubkey, Option<SerializedAccount>>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EstimateGasApiRequest {
+    #[serde(flatten)]
+    pub body: EstimateGasRequest,
+    pub slot: Option<u64>,
+    pub tx_index_in_block: Option<u64>,
+}
+
 #[derive(Deserialize, Serialize, Debug, Default, Copy, Clone, Eq, PartialEq)]
 pub struct BalanceAddress {
     pub address: Address,
@@ -247,6 +350,22 @@ pub struct SimulateSolanaRequest {
     pub blockhash: [u8; 32],
     #[serde_as(as = "Vec<Hex>")]
     pub transactions: Vec<Vec<u8>>,
+    /// Account states used instead of the RPC ones, `null` removes the account
+    #[serde_as(as = "Option<HashMap<DisplayFromStr,_>>")]
+    pub solana_overrides: Option<HashMap<Pubkey, Option<SerializedAccount>>>,
+    /// Base64 encoded ELF of the programs loaded instead of the deployed ones
+    #[serde_as(as = "Option<HashMap<DisplayFromStr, Base64>>")]
+    pub programs: Option<HashMap<Pubkey, Vec<u8>>>,
+    /// Return states of writable accounts changed by transactions
+    #[serde(default)]
+    pub with_account_diffs: bool,
+    #[serde(default)]
+    pub with_return_data: bool,
+    #[serde(default)]
+    pub with_inner_instructions: bool,
+    /// Return compute units consumed by every instruction
+    #[serde(default)]
+    pub with_instruction_units: bool,
 }
 
 #[cfg(test)]
@@ -364,4 +483,34 @@ mod tests {
         let request: super::EmulateRequest = serde_json::from_str(txt).unwrap();
         println!("{request:?}");
     }
+
+    #[test]
+    fn test_deserialize_simulate_solana_overrides() {
+        let txt = r#"
+        {
+            "blockhash": "0000000000000000000000000000000000000000000000000000000000000000",
+            "transactions": [],
+            "solana_overrides": {
+                "EwJYd3UAFAgzodVeHprB2gMQ68r4ZEbbvpoVzCZ1dGq5": null,
+                "2duuuuhNJHUYqcnZ7LKfeufeeTBgSJdftf2zM3cZV6ym": {
+                    "lamports": 1000000000000,
+                    "owner": "So11111111111111111111111111111111111111112",
+                    "executable": false,
+                    "rent_epoch": 0,
+                    "data": "0102030405"
+                }
+            },
+            "programs": {
+                "53DfF883gyixYNXnM7s5xhdeyV8mVk9T4i2hGV9vG9io": "f0VMRgIBAQ=="
+            }
+        }
+        "#;
+
+        let request: super::SimulateSolanaRequest = serde_json::from_str(txt).unwrap();
+        assert_eq!(request.solana_overrides.unwrap().len(), 2);
+        let programs = request.programs.unwrap();
+        assert_eq!(programs.len(), 1);
+        assert!(programs.values().all(|elf| elf == b"\x7fELF\x02\x01\x01"));
+        assert!(!request.with_account_diffs);
+    }
 }
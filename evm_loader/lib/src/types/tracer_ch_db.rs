@@ -1,5 +1,6 @@
 use crate::{
     commands::get_neon_elf::get_elf_parameter,
+    metrics,
     types::tracer_ch_common::{AccountRow, ChError, RevisionRow, SlotParent, ROOT_BLOCK_DELAY},
 };
 
@@ -58,6 +59,7 @@ impl ClickHouseDb {
             .await
             .map_err(std::convert::Into::into);
         let execution_time = Instant::now().duration_since(time_start);
+        metrics::observe_clickhouse_query("get_block_time", execution_time);
         info!(
             "get_block_time sql time: {} sec",
             execution_time.as_secs_f64()
@@ -75,6 +77,7 @@ impl ClickHouseDb {
             .await
             .map_err(std::convert::Into::into);
         let execution_time = Instant::now().duration_since(time_start);
+        metrics::observe_clickhouse_query("get_earliest_rooted_slot", execution_time);
         info!(
             "get_earliest_rooted_slot sql returned {result:?}, time: {} sec",
             execution_time.as_secs_f64()
@@ -92,6 +95,7 @@ impl ClickHouseDb {
             .await
             .map_err(std::convert::Into::into);
         let execution_time = Instant::now().duration_since(time_start);
+        metrics::observe_clickhouse_query("get_latest_block", execution_time);
         info!(
             "get_latest_block sql returned {result:?}, time: {} sec",
             execution_time.as_secs_f64()
@@ -148,6 +152,7 @@ impl ClickHouseDb {
         };
 
         let execution_time = Instant::now().duration_since(time_start);
+        metrics::observe_clickhouse_query("get_branch_slots", execution_time);
         info!(
             "get_branch_slots {{ slot: {slot:?} }} sql(1) returned {} row(s), time: {} sec",
             rows.len(),
@@ -209,6 +214,7 @@ impl ClickHouseDb {
         )?;
 
         let execution_time = Instant::now().duration_since(time_start);
+        metrics::observe_clickhouse_query("get_account_rooted_slot", execution_time);
         info!(
             "get_account_rooted_slot {{ key: {key}, slot: {slot} }} sql(1) returned {slot_opt:?}, time: {} sec",
             execution_time.as_secs_f64(),
@@ -287,6 +293,7 @@ impl ClickHouseDb {
                 ChError::Db(e)
             })?;
             let execution_time = Instant::now().duration_since(time_start);
+            metrics::observe_clickhouse_query("get_account_at_slot", execution_time);
             info!(
                 "get_account_at_slot {{ pubkey: {pubkey}, slot: {slot} }} sql(1) returned {row:?}, time: {} sec",
                 execution_time.as_secs_f64()
@@ -299,6 +306,7 @@ impl ClickHouseDb {
             let time_start = Instant::now();
             row = self.get_older_account_row_at(&pubkey_str, slot).await?;
             let execution_time = Instant::now().duration_since(time_start);
+            metrics::observe_clickhouse_query("get_older_account_row_at", execution_time);
             info!(
                 "get_account_at {{ pubkey: {pubkey}, slot: {slot} }} sql(2) returned {row:?}, time: {} sec",
                 execution_time.as_secs_f64()
@@ -355,6 +363,7 @@ impl ClickHouseDb {
         .map_err(|e| ChError::Db(clickhouse::error::Error::Custom(e)))?;
 
         let execution_time = Instant::now().duration_since(time_start);
+        metrics::observe_clickhouse_query("get_account_at_index_in_block", execution_time);
         info!(
             "get_account_at_index_in_block {{ pubkey: {pubkey}, slot: {slot}, tx_index_in_block: {tx_index_in_block} }} sql(1) returned {account:?}, time: {} sec",
             execution_time.as_secs_f64()
@@ -456,6 +465,7 @@ impl ClickHouseDb {
         let time_start = Instant::now();
         let mut slot_opt = self.get_sol_sig_rooted_slot(sol_sig).await?;
         let execution_time = Instant::now().duration_since(time_start);
+        metrics::observe_clickhouse_query("get_sol_sig_rooted_slot", execution_time);
         info!(
             "get_sol_sig_rooted_slot({sol_sig_str}) -> {slot_opt:?}, time: {} sec",
             execution_time.as_secs_f64()
@@ -465,6 +475,7 @@ impl ClickHouseDb {
             let time_start = Instant::now();
             slot_opt = self.get_sol_sig_confirmed_slot(sol_sig).await?;
             let execution_time = Instant::now().duration_since(time_start);
+            metrics::observe_clickhouse_query("get_sol_sig_confirmed_slot", execution_time);
             info!(
                 "get_sol_sig_confirmed_slot({sol_sig_str}) -> {slot_opt:?}, time: {} sec",
                 execution_time.as_secs_f64()
@@ -494,6 +505,7 @@ impl ClickHouseDb {
             .fetch_all::<AccountRow>()
             .await?;
         let execution_time = Instant::now().duration_since(time_start);
+        metrics::observe_clickhouse_query("get_account_by_sol_sig", execution_time);
         info!(
             "get_account_by_sol_sig {{ pubkey: {pubkey}, sol_sig: {sol_sig_str} }} \
                 sql(1) returned {} row(s), time: {} sec",
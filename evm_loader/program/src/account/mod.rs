@@ -11,8 +11,11 @@ pub use ether_contract::{AllocateResult, ContractAccount, Header as ContractHead
 pub use ether_storage::{Header as StorageCellHeader, StorageCell, StorageCellAddress};
 pub use holder::{Header as HolderHeader, Holder};
 pub use incinerator::Incinerator;
-pub use operator::Operator;
+pub use operator::{is_whitelisted, Operator};
 pub use operator_balance::{OperatorBalanceAccount, OperatorBalanceValidator};
+#[cfg(test)]
+pub use operator_record::tests::TestAccount;
+pub use operator_record::{Header as OperatorRecordHeader, OperatorRecord};
 pub use state::{AccountsStatus, StateAccount};
 pub use state_finalized::{Header as StateFinalizedHeader, StateFinalizedAccount};
 pub use treasury::{MainTreasury, Treasury};
@@ -27,6 +30,7 @@ mod incinerator;
 pub mod legacy;
 mod operator;
 mod operator_balance;
+mod operator_record;
 pub mod program;
 mod state;
 mod state_finalized;
@@ -41,6 +45,7 @@ pub const TAG_HOLDER: u8 = 52;
 pub const TAG_ACCOUNT_BALANCE: u8 = 60;
 pub const TAG_ACCOUNT_CONTRACT: u8 = 70;
 pub const TAG_OPERATOR_BALANCE: u8 = 80;
+pub const TAG_OPERATOR_RECORD: u8 = 90;
 pub const TAG_STORAGE_CELL: u8 = 43;
 
 const TAG_OFFSET: usize = 0;
@@ -1,21 +1,42 @@
+use crate::account::OperatorRecord;
 use crate::error::Error;
 use solana_program::account_info::AccountInfo;
 use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
 use std::ops::Deref;
 
+/// Compiled whitelist of the operators, replaced by the operator registry
+#[must_use]
+pub fn is_whitelisted(key: &Pubkey) -> bool {
+    crate::config::AUTHORIZED_OPERATOR_LIST
+        .binary_search(key)
+        .is_ok()
+}
+
 #[derive(Clone)]
 pub struct Operator<'a> {
     pub info: &'a AccountInfo<'a>,
 }
 
 impl<'a> Operator<'a> {
-    pub fn from_account(info: &'a AccountInfo<'a>) -> Result<Self, ProgramError> {
-        let is_authorized = crate::config::AUTHORIZED_OPERATOR_LIST
-            .binary_search(info.key)
-            .is_ok();
-
-        if !is_authorized {
-            return Err!(Error::UnauthorizedOperator.into(); "Account {} - expected authorized operator", info.key);
+    /// Operator must be registered in the operator registry and not suspended.
+    /// `record` is the operator record PDA, passed at a fixed position of the instruction.
+    /// Operators of the compiled whitelist are accepted while their record is not created,
+    /// during the migration to the registry.
+    pub fn from_account(
+        program_id: &Pubkey,
+        info: &'a AccountInfo<'a>,
+        record: &'a AccountInfo<'a>,
+    ) -> Result<Self, ProgramError> {
+        match OperatorRecord::try_from_account(program_id, info.key, record)? {
+            Some(record) if record.is_suspended() => {
+                return Err!(Error::OperatorSuspended(*info.key).into(); "Account {} - operator is suspended", info.key);
+            }
+            Some(_) => {}
+            None if is_whitelisted(info.key) => {}
+            None => {
+                return Err!(Error::UnauthorizedOperator.into(); "Account {} - expected authorized operator", info.key);
+            }
         }
 
         unsafe { Self::from_account_not_whitelisted(info) }
@@ -23,7 +44,7 @@ impl<'a> Operator<'a> {
 
     /// # Safety
     /// Due to critical vulnerability, operator can destroy the world
-    /// We trust whitelisted operators to not do this
+    /// We trust registered operators to not do this
     pub unsafe fn from_account_not_whitelisted(
         info: &'a AccountInfo<'a>,
     ) -> Result<Self, ProgramError> {
@@ -50,3 +71,85 @@ impl<'a> Deref for Operator<'a> {
         self.info
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::account::TestAccount;
+    use crate::config::AUTHORIZED_OPERATOR_LIST;
+
+    fn check(operator: &mut TestAccount, record: &mut TestAccount) -> bool {
+        let accounts = [operator.info(), record.info()];
+
+        Operator::from_account(&crate::ID, &accounts[0], &accounts[1]).is_ok()
+    }
+
+    /// Operator record PDA which is not created
+    fn no_record(operator: &Pubkey) -> TestAccount {
+        TestAccount::system(OperatorRecord::address(&crate::ID, operator).0)
+    }
+
+    #[test]
+    fn test_registered_operator() {
+        let mut operator = TestAccount::operator(Pubkey::new_unique());
+        let mut record = TestAccount::record(&crate::ID, operator.key, false);
+        assert!(check(&mut operator, &mut record));
+
+        let mut suspended = TestAccount::record(&crate::ID, operator.key, true);
+        assert!(!check(&mut operator, &mut suspended));
+    }
+
+    #[test]
+    fn test_unregistered_operator() {
+        let mut operator = TestAccount::operator(Pubkey::new_unique());
+        let mut record = no_record(&operator.key);
+        assert!(!check(&mut operator, &mut record));
+
+        // Record of another operator
+        let mut record = TestAccount::record(&crate::ID, Pubkey::new_unique(), false);
+        assert!(!check(&mut operator, &mut record));
+
+        // Record owned by another program
+        let mut record = TestAccount::record(&Pubkey::new_unique(), operator.key, false);
+        assert!(!check(&mut operator, &mut record));
+    }
+
+    #[test]
+    fn test_whitelisted_operator() {
+        let key = AUTHORIZED_OPERATOR_LIST[0];
+
+        let mut operator = TestAccount::operator(key);
+        let mut record = no_record(&key);
+        assert!(check(&mut operator, &mut record));
+
+        // Suspension in the registry applies to whitelisted operators
+        let mut suspended = TestAccount::record(&crate::ID, key, true);
+        assert!(!check(&mut operator, &mut suspended));
+
+        // Record PDA with data is not an uninitialized record
+        let mut record = no_record(&key);
+        record.data = vec![0; OperatorRecord::required_account_size()];
+        assert!(!check(&mut operator, &mut record));
+    }
+
+    #[test]
+    fn test_whitelisted_operator_without_record() {
+        let key = AUTHORIZED_OPERATOR_LIST[0];
+        let mut operator = TestAccount::operator(key);
+
+        // Suspended operator passes another account in place of its record
+        let mut other = TestAccount::system(Pubkey::new_unique());
+        assert!(!check(&mut operator, &mut other));
+
+        let mut other_record = TestAccount::record(&crate::ID, Pubkey::new_unique(), false);
+        assert!(!check(&mut operator, &mut other_record));
+    }
+
+    #[test]
+    fn test_operator_signer() {
+        let mut operator = TestAccount::operator(Pubkey::new_unique());
+        operator.is_signer = false;
+        let mut record = TestAccount::record(&crate::ID, operator.key, false);
+        assert!(!check(&mut operator, &mut record));
+    }
+}
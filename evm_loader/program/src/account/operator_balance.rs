@@ -60,7 +60,7 @@ impl<'a> OperatorBalanceAccount<'a> {
         system: &program::System<'a>,
         rent: &Rent,
     ) -> Result<Self> {
-        let (pubkey, bump_seed) = address.find_operator_address(&crate::ID, chain_id, operator);
+        let (pubkey, bump_seed) = address.find_operator_address(&crate::ID, chain_id, operator.key);
 
         if account.key != &pubkey {
             return Err(Error::AccountInvalidKey(*account.key, pubkey));
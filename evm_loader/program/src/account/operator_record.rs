@@ -0,0 +1,246 @@
+use std::mem::size_of;
+
+use crate::error::{Error, Result};
+use solana_program::{account_info::AccountInfo, pubkey::Pubkey, rent::Rent, system_program};
+
+use super::{
+    program, AccountHeader, Operator, ACCOUNT_PREFIX_LEN, ACCOUNT_SEED_VERSION, TAG_OPERATOR_RECORD,
+};
+
+pub const OPERATOR_RECORD_SEED: &[u8] = b"OPERATOR";
+
+#[repr(C, packed)]
+pub struct Header {
+    pub operator: Pubkey,
+    pub suspended: u8,
+}
+impl AccountHeader for Header {
+    const VERSION: u8 = 0;
+}
+
+/// Entry of the operator registry: PDA[`ACCOUNT_SEED_VERSION`, "OPERATOR", operator]
+#[derive(Clone)]
+pub struct OperatorRecord<'a> {
+    account: &'a AccountInfo<'a>,
+}
+
+impl<'a> OperatorRecord<'a> {
+    #[must_use]
+    pub fn required_account_size() -> usize {
+        ACCOUNT_PREFIX_LEN + size_of::<Header>()
+    }
+
+    #[must_use]
+    pub fn address(program_id: &Pubkey, operator: &Pubkey) -> (Pubkey, u8) {
+        let seeds: &[&[u8]] = &[
+            &[ACCOUNT_SEED_VERSION],
+            OPERATOR_RECORD_SEED,
+            operator.as_ref(),
+        ];
+        Pubkey::find_program_address(seeds, program_id)
+    }
+
+    pub fn from_account(program_id: &Pubkey, account: &'a AccountInfo<'a>) -> Result<Self> {
+        super::validate_tag(program_id, account, TAG_OPERATOR_RECORD)?;
+
+        Ok(Self { account })
+    }
+
+    /// Record of the `operator` at its PDA, `None` if the record is not created
+    pub fn try_from_account(
+        program_id: &Pubkey,
+        operator: &Pubkey,
+        account: &'a AccountInfo<'a>,
+    ) -> Result<Option<Self>> {
+        let (pubkey, _) = Self::address(program_id, operator);
+        if account.key != &pubkey {
+            return Err(Error::AccountInvalidKey(*account.key, pubkey));
+        }
+
+        if system_program::check_id(account.owner) && account.data_is_empty() {
+            Ok(None)
+        } else {
+            let record = Self::from_account(program_id, account)?;
+            Ok(Some(record))
+        }
+    }
+
+    pub fn create(
+        program_id: &Pubkey,
+        operator: Pubkey,
+        account: &'a AccountInfo<'a>,
+        payer: &Operator<'a>,
+        system: &program::System<'a>,
+        rent: &Rent,
+    ) -> Result<Self> {
+        let (pubkey, bump_seed) = Self::address(program_id, &operator);
+
+        if account.key != &pubkey {
+            return Err(Error::AccountInvalidKey(*account.key, pubkey));
+        }
+
+        if !system_program::check_id(account.owner) {
+            return Err(Error::AccountAlreadyInitialized(*account.key));
+        }
+
+        let program_seeds: &[&[u8]] = &[
+            &[ACCOUNT_SEED_VERSION],
+            OPERATOR_RECORD_SEED,
+            operator.as_ref(),
+            &[bump_seed],
+        ];
+
+        system.create_pda_account(
+            program_id,
+            payer,
+            account,
+            program_seeds,
+            Self::required_account_size(),
+            rent,
+        )?;
+
+        Self::initialize(program_id, account, operator)
+    }
+
+    /// Writes an active record into the allocated program account
+    pub fn initialize(
+        program_id: &Pubkey,
+        account: &'a AccountInfo<'a>,
+        operator: Pubkey,
+    ) -> Result<Self> {
+        super::set_tag(program_id, account, TAG_OPERATOR_RECORD, Header::VERSION)?;
+        {
+            let mut header = super::header_mut::<Header>(account);
+            header.operator = operator;
+            header.suspended = 0;
+        }
+
+        Ok(Self { account })
+    }
+
+    #[must_use]
+    pub fn pubkey(&self) -> &'a Pubkey {
+        self.account.key
+    }
+
+    #[must_use]
+    pub fn operator(&self) -> Pubkey {
+        let header = super::header::<Header>(self.account);
+        header.operator
+    }
+
+    #[must_use]
+    pub fn is_suspended(&self) -> bool {
+        let header = super::header::<Header>(self.account);
+        header.suspended != 0
+    }
+
+    pub fn set_suspended(&mut self, suspended: bool) {
+        let mut header = super::header_mut::<Header>(self.account);
+        header.suspended = u8::from(suspended);
+    }
+
+    /// # Safety
+    /// Permanently deletes Operator Record account and all data in it
+    pub unsafe fn suicide(self, receiver: &Operator) {
+        crate::account::delete(self.account, receiver);
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use solana_program::bpf_loader_upgradeable::{self, UpgradeableLoaderState};
+
+    /// Account state owned by the test, `info` borrows it as the instruction account
+    pub struct TestAccount {
+        pub key: Pubkey,
+        pub lamports: u64,
+        pub data: Vec<u8>,
+        pub owner: Pubkey,
+        pub is_signer: bool,
+    }
+
+    impl TestAccount {
+        pub fn info(&mut self) -> AccountInfo<'_> {
+            AccountInfo::new(
+                &self.key,
+                self.is_signer,
+                true,
+                &mut self.lamports,
+                &mut self.data,
+                &self.owner,
+                false,
+                0,
+            )
+        }
+
+        pub fn system(key: Pubkey) -> Self {
+            Self {
+                key,
+                lamports: 0,
+                data: vec![],
+                owner: system_program::ID,
+                is_signer: false,
+            }
+        }
+
+        pub fn operator(key: Pubkey) -> Self {
+            Self {
+                lamports: 1_000_000_000,
+                is_signer: true,
+                ..Self::system(key)
+            }
+        }
+
+        pub fn record(program_id: &Pubkey, operator: Pubkey, suspended: bool) -> Self {
+            let mut account = Self {
+                key: OperatorRecord::address(program_id, &operator).0,
+                lamports: 1_000_000,
+                data: vec![0; OperatorRecord::required_account_size()],
+                owner: *program_id,
+                is_signer: false,
+            };
+
+            {
+                let info = account.info();
+                let mut record = OperatorRecord::initialize(program_id, &info, operator).unwrap();
+                record.set_suspended(suspended);
+            }
+
+            account
+        }
+
+        /// Program data and upgrade authority accounts of the program.
+        /// The authority account doesn't match the program data if not `authorized`.
+        pub fn upgrade_authority(
+            program_id: &Pubkey,
+            authorized: bool,
+            is_signer: bool,
+        ) -> (Self, Self) {
+            let mut authority = Self::operator(Pubkey::new_unique());
+            authority.is_signer = is_signer;
+
+            let upgrade_authority_address = if authorized {
+                authority.key
+            } else {
+                Pubkey::new_unique()
+            };
+            let state = UpgradeableLoaderState::ProgramData {
+                slot: 0,
+                upgrade_authority_address: Some(upgrade_authority_address),
+            };
+            let (key, _) =
+                Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::id());
+            let program_data = Self {
+                key,
+                lamports: 1_000_000,
+                data: bincode::serialize(&state).unwrap(),
+                owner: bpf_loader_upgradeable::id(),
+                is_signer: false,
+            };
+
+            (program_data, authority)
+        }
+    }
+}
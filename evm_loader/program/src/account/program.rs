@@ -1,5 +1,7 @@
 use super::Operator;
+use crate::error::Error;
 use solana_program::account_info::AccountInfo;
+use solana_program::bpf_loader_upgradeable::{self, UpgradeableLoaderState};
 use solana_program::program::{invoke_signed_unchecked, invoke_unchecked};
 use solana_program::program_error::ProgramError;
 use solana_program::pubkey::Pubkey;
@@ -166,7 +168,7 @@ pub struct Token<'a>(&'a AccountInfo<'a>);
 
 impl<'a> Token<'a> {
     pub fn from_account(info: &'a AccountInfo<'a>) -> Result<Self, ProgramError> {
-        if !spl_token::check_id(info.key) {
+        if !super::token::is_token_program(info.key) {
             return Err!(ProgramError::InvalidArgument; "Account {} - is not token program", info.key);
         }
 
@@ -180,7 +182,7 @@ impl<'a> Token<'a> {
         owner: &AccountInfo<'a>,
     ) -> Result<(), ProgramError> {
         invoke_unchecked(
-            &spl_token::instruction::initialize_account3(
+            &spl_token_2022::instruction::initialize_account3(
                 self.0.key,
                 account.key,
                 mint.key,
@@ -198,3 +200,56 @@ impl<'a> Deref for Token<'a> {
         self.0
     }
 }
+
+/// Signer which is the upgrade authority of the program
+pub struct UpgradeAuthority<'a>(&'a AccountInfo<'a>);
+
+impl<'a> UpgradeAuthority<'a> {
+    pub fn from_account(
+        program_id: &Pubkey,
+        program_data: &AccountInfo<'a>,
+        info: &'a AccountInfo<'a>,
+    ) -> crate::error::Result<Self> {
+        let expected_key = Self::read(program_id, program_data)?;
+        if *info.key != expected_key {
+            return Err(Error::AccountInvalidKey(*info.key, expected_key));
+        }
+
+        if !info.is_signer {
+            return Err(Error::AccountNotSigner(*info.key));
+        }
+
+        Ok(Self(info))
+    }
+
+    fn read(program_id: &Pubkey, program_data: &AccountInfo<'a>) -> crate::error::Result<Pubkey> {
+        let (expected_program_data_key, _) =
+            Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::id());
+
+        if *program_data.key != expected_program_data_key {
+            return Err(Error::AccountInvalidKey(
+                *program_data.key,
+                expected_program_data_key,
+            ));
+        }
+
+        let unpacked_program_data: UpgradeableLoaderState =
+            bincode::deserialize(&program_data.data.borrow())?;
+
+        match unpacked_program_data {
+            UpgradeableLoaderState::ProgramData {
+                slot: _,
+                upgrade_authority_address,
+            } => upgrade_authority_address.ok_or_else(|| Error::from("Not upgradeable program")),
+            _ => Err(Error::from("Not ProgramData")),
+        }
+    }
+}
+
+impl<'a> Deref for UpgradeAuthority<'a> {
+    type Target = AccountInfo<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
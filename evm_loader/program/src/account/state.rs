@@ -97,19 +97,27 @@ pub struct StateAccount<'a> {
 const BUFFER_OFFSET: usize = ACCOUNT_PREFIX_LEN + size_of::<Header>();
 
 impl<'a> StateAccount<'a> {
+    /// Length of the account data up to the end of the header
+    pub const HEADER_LEN: usize = BUFFER_OFFSET;
+
     #[must_use]
     pub fn into_account(self) -> AccountInfo<'a> {
         self.account
     }
 
+    /// Offset of the serialized state data, which begins with the owner.
+    /// Only the first `HEADER_LEN` bytes of the account data are read.
+    #[must_use]
+    pub fn data_offset(account: &AccountInfo) -> usize {
+        let header = super::header::<Header>(account);
+        BUFFER_OFFSET + header.evm_state_len + header.evm_machine_len
+    }
+
     pub fn from_account(program_id: &Pubkey, account: AccountInfo<'a>) -> Result<Self> {
         super::validate_tag(program_id, &account, TAG_STATE)?;
 
-        let (offset, len) = {
-            let header = super::header::<Header>(&account);
-            let offset = BUFFER_OFFSET + header.evm_state_len + header.evm_machine_len;
-            (offset, header.data_len)
-        };
+        let offset = Self::data_offset(&account);
+        let len = super::header::<Header>(&account).data_len;
 
         let data = {
             let account_data = account.try_borrow_data()?;
@@ -1,8 +1,106 @@
 use solana_program::account_info::AccountInfo;
+use solana_program::instruction::{AccountMeta, Instruction};
 use solana_program::program_error::ProgramError;
 use solana_program::program_pack::{IsInitialized, Pack};
+use solana_program::pubkey::Pubkey;
+use spl_tlv_account_resolution::state::ExtraAccountMetaList;
+use spl_token_2022::extension::{
+    transfer_hook, BaseStateWithExtensions, ExtensionType, StateWithExtensions,
+};
+use spl_transfer_hook_interface::instruction::{ExecuteInstruction, TransferHookInstruction};
+use spl_type_length_value::state::TlvStateBorrowed;
 use std::ops::Deref;
 
+/// SPL Token or SPL Token-2022 program
+#[must_use]
+pub fn is_token_program(program_id: &Pubkey) -> bool {
+    spl_token::check_id(program_id) || spl_token_2022::check_id(program_id)
+}
+
+/// Unpacks the base state of the token account or mint.
+/// Token-2022 keeps the SPL Token layout in front of the extensions.
+pub fn unpack<T: Pack + IsInitialized>(owner: &Pubkey, data: &[u8]) -> Result<T, ProgramError> {
+    if spl_token_2022::check_id(owner) && (data.len() > T::LEN) {
+        return T::unpack(&data[..T::LEN]);
+    }
+
+    T::unpack(data)
+}
+
+/// Size of the associated token account for the mint, including extensions required by the mint
+pub fn account_len(token_program: &Pubkey, mint_data: &[u8]) -> Result<usize, ProgramError> {
+    if !spl_token_2022::check_id(token_program) {
+        return Ok(spl_token::state::Account::LEN);
+    }
+
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(mint_data)?;
+    let mut extensions =
+        ExtensionType::get_required_init_account_extensions(&mint.get_extension_types()?);
+    extensions.push(ExtensionType::ImmutableOwner);
+
+    ExtensionType::try_calculate_account_len::<spl_token_2022::state::Account>(&extensions)
+}
+
+/// Program of the Token-2022 transfer hook, `None` if the mint doesn't have one
+pub fn transfer_hook_program_id(
+    token_program: &Pubkey,
+    mint_data: &[u8],
+) -> Result<Option<Pubkey>, ProgramError> {
+    if !spl_token_2022::check_id(token_program) {
+        return Ok(None);
+    }
+
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(mint_data)?;
+    Ok(transfer_hook::get_program_id(&mint))
+}
+
+/// Appends accounts of the transfer hook to the `transfer_checked` instruction:
+/// extra accounts from the validation account, the hook program and the validation account itself.
+/// Extra accounts derived from the account data are not supported.
+pub fn add_transfer_hook_accounts(
+    instruction: &mut Instruction,
+    hook_program_id: &Pubkey,
+    validation: &Pubkey,
+    validation_data: &[u8],
+    amount: u64,
+) -> Result<(), ProgramError> {
+    // Hook without the validation account doesn't require extra accounts
+    if !validation_data.is_empty() {
+        let state = TlvStateBorrowed::unpack(validation_data)?;
+        let extra_metas =
+            ExtraAccountMetaList::unpack_with_tlv_state::<ExecuteInstruction>(&state)?;
+
+        let execute_data = TransferHookInstruction::Execute { amount }.pack();
+
+        // Execute instruction accounts: source, mint, destination, authority, validation, extra accounts
+        let mut keys: Vec<Pubkey> = instruction
+            .accounts
+            .iter()
+            .take(4)
+            .map(|meta| meta.pubkey)
+            .collect();
+        keys.push(*validation);
+
+        for extra in extra_metas.data() {
+            let meta = extra.resolve(&execute_data, hook_program_id, |index| {
+                keys.get(index).map(|key| (key, None))
+            })?;
+
+            keys.push(meta.pubkey);
+            instruction.accounts.push(meta);
+        }
+    }
+
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(*hook_program_id, false));
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(*validation, false));
+
+    Ok(())
+}
+
 pub struct Account<'a, T: Pack + IsInitialized> {
     pub info: &'a AccountInfo<'a>,
     data: T,
@@ -10,16 +108,22 @@ pub struct Account<'a, T: Pack + IsInitialized> {
 
 impl<'a, T: Pack + IsInitialized> Account<'a, T> {
     pub fn from_account(info: &'a AccountInfo<'a>) -> Result<Self, ProgramError> {
-        if !spl_token::check_id(info.owner) {
+        if !is_token_program(info.owner) {
             return Err!(ProgramError::InvalidArgument; "Account {} - is not spl token owned", info.key);
         }
 
         let data = info.try_borrow_data()?;
-        let data = T::unpack(&data)?;
+        let data = unpack::<T>(info.owner, &data)?;
 
         Ok(Self { info, data })
     }
 
+    /// Token program which owns the account
+    #[must_use]
+    pub fn token_program(&self) -> &'a Pubkey {
+        self.info.owner
+    }
+
     pub fn into_data(self) -> T {
         self.data
     }
@@ -35,3 +139,207 @@ impl<'a, T: Pack + IsInitialized> Deref for Account<'a, T> {
 
 pub type State<'a> = Account<'a, spl_token::state::Account>;
 pub type Mint<'a> = Account<'a, spl_token::state::Mint>;
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use solana_program::program_option::COption;
+    use spl_tlv_account_resolution::account::ExtraAccountMeta;
+    use spl_token_2022::extension::{
+        immutable_owner::ImmutableOwner, transfer_hook::TransferHook, BaseStateWithExtensionsMut,
+        StateWithExtensionsMut,
+    };
+    use spl_token_2022::state::{Account as Account2022, AccountState, Mint as Mint2022};
+
+    /// Token-2022 mint, with the transfer hook extension if `transfer_hook` is set
+    pub fn mint_2022(decimals: u8, transfer_hook: Option<Pubkey>) -> Vec<u8> {
+        let base = Mint2022 {
+            mint_authority: COption::None,
+            supply: 1_000_000_000,
+            decimals,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        };
+
+        let Some(program_id) = transfer_hook else {
+            let mut data = vec![0; Mint2022::LEN];
+            Mint2022::pack(base, &mut data).unwrap();
+            return data;
+        };
+
+        let len =
+            ExtensionType::try_calculate_account_len::<Mint2022>(&[ExtensionType::TransferHook])
+                .unwrap();
+        let mut data = vec![0; len];
+        let mut state =
+            StateWithExtensionsMut::<Mint2022>::unpack_uninitialized(&mut data).unwrap();
+        let extension = state.init_extension::<TransferHook>(true).unwrap();
+        extension.program_id = Some(program_id).try_into().unwrap();
+        state.base = base;
+        state.pack_base();
+        state.init_account_type().unwrap();
+
+        data
+    }
+
+    /// Token-2022 account with the immutable owner, as created by the associated token program
+    pub fn account_2022(
+        mint: Pubkey,
+        owner: Pubkey,
+        amount: u64,
+        delegate: Option<Pubkey>,
+    ) -> Vec<u8> {
+        let len = ExtensionType::try_calculate_account_len::<Account2022>(&[
+            ExtensionType::ImmutableOwner,
+        ])
+        .unwrap();
+        let mut data = vec![0; len];
+        let mut state =
+            StateWithExtensionsMut::<Account2022>::unpack_uninitialized(&mut data).unwrap();
+        state.init_extension::<ImmutableOwner>(true).unwrap();
+        state.base = Account2022 {
+            mint,
+            owner,
+            amount,
+            delegate: delegate.into(),
+            state: AccountState::Initialized,
+            is_native: COption::None,
+            delegated_amount: delegate.map_or(0, |_| amount),
+            close_authority: COption::None,
+        };
+        state.pack_base();
+        state.init_account_type().unwrap();
+
+        data
+    }
+
+    /// Validation account of the transfer hook with fixed extra accounts
+    pub fn validation_data(extra_accounts: &[AccountMeta]) -> Vec<u8> {
+        let metas: Vec<ExtraAccountMeta> = extra_accounts
+            .iter()
+            .map(|meta| {
+                ExtraAccountMeta::new_with_pubkey(&meta.pubkey, meta.is_signer, meta.is_writable)
+                    .unwrap()
+            })
+            .collect();
+
+        let mut data = vec![0; ExtraAccountMetaList::size_of(metas.len()).unwrap()];
+        ExtraAccountMetaList::init::<ExecuteInstruction>(&mut data, &metas).unwrap();
+
+        data
+    }
+
+    #[test]
+    fn test_unpack() {
+        let mint = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+
+        let data = account_2022(mint, owner, 42, None);
+        assert!(data.len() > spl_token::state::Account::LEN);
+
+        let account: spl_token::state::Account = unpack(&spl_token_2022::ID, &data).unwrap();
+        assert_eq!(account.mint, mint);
+        assert_eq!(account.owner, owner);
+        assert_eq!(account.amount, 42);
+
+        // SPL Token accounts don't have extensions
+        assert!(unpack::<spl_token::state::Account>(&spl_token::ID, &data).is_err());
+        let data = &data[..spl_token::state::Account::LEN];
+        let account: spl_token::state::Account = unpack(&spl_token::ID, data).unwrap();
+        assert_eq!(account.amount, 42);
+
+        let data = mint_2022(6, Some(Pubkey::new_unique()));
+        let mint: spl_token::state::Mint = unpack(&spl_token_2022::ID, &data).unwrap();
+        assert_eq!(mint.decimals, 6);
+    }
+
+    #[test]
+    fn test_account_len() {
+        let mint = mint_2022(6, None);
+        assert_eq!(
+            account_len(&spl_token::ID, &mint).unwrap(),
+            spl_token::state::Account::LEN
+        );
+
+        // Account type and the immutable owner
+        let len = account_len(&spl_token_2022::ID, &mint).unwrap();
+        assert_eq!(len, spl_token::state::Account::LEN + 1 + 4);
+
+        // Transfer hook account extension is required by the mint
+        let mint = mint_2022(6, Some(Pubkey::new_unique()));
+        let len = account_len(&spl_token_2022::ID, &mint).unwrap();
+        assert_eq!(len, spl_token::state::Account::LEN + 1 + 4 + 4 + 1);
+    }
+
+    #[test]
+    fn test_transfer_hook_program_id() {
+        let hook = Pubkey::new_unique();
+        let mint = mint_2022(6, Some(hook));
+
+        assert_eq!(
+            transfer_hook_program_id(&spl_token_2022::ID, &mint).unwrap(),
+            Some(hook)
+        );
+        assert_eq!(
+            transfer_hook_program_id(&spl_token_2022::ID, &mint_2022(6, None)).unwrap(),
+            None
+        );
+        assert_eq!(
+            transfer_hook_program_id(&spl_token::ID, &mint).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_add_transfer_hook_accounts() {
+        let hook = Pubkey::new_unique();
+        let validation = Pubkey::new_unique();
+        let transfer = || {
+            spl_token_2022::instruction::transfer_checked(
+                &spl_token_2022::ID,
+                &Pubkey::new_unique(),
+                &Pubkey::new_unique(),
+                &Pubkey::new_unique(),
+                &Pubkey::new_unique(),
+                &[],
+                100,
+                6,
+            )
+            .unwrap()
+        };
+
+        // Hook without the validation account
+        let mut instruction = transfer();
+        add_transfer_hook_accounts(&mut instruction, &hook, &validation, &[], 100).unwrap();
+        assert_eq!(
+            &instruction.accounts[4..],
+            &[
+                AccountMeta::new_readonly(hook, false),
+                AccountMeta::new_readonly(validation, false),
+            ]
+        );
+
+        let extra = [
+            AccountMeta::new(Pubkey::new_unique(), false),
+            AccountMeta::new_readonly(Pubkey::new_unique(), false),
+        ];
+        let data = validation_data(&extra);
+
+        let mut instruction = transfer();
+        add_transfer_hook_accounts(&mut instruction, &hook, &validation, &data, 100).unwrap();
+        assert_eq!(instruction.accounts.len(), 4 + 2 + 2);
+        assert_eq!(&instruction.accounts[4..6], &extra);
+        assert_eq!(
+            &instruction.accounts[6..],
+            &[
+                AccountMeta::new_readonly(hook, false),
+                AccountMeta::new_readonly(validation, false),
+            ]
+        );
+
+        assert!(
+            add_transfer_hook_accounts(&mut transfer(), &hook, &validation, &[1, 2, 3], 100)
+                .is_err()
+        );
+    }
+}
@@ -28,6 +28,22 @@ impl<'a> AccountStorage for ProgramAccountStorage<'a> {
             .expect("Timestamp is positive")
     }
 
+    fn block_coinbase(&self) -> Address {
+        Address::default()
+    }
+
+    fn block_gas_limit(&self) -> U256 {
+        U256::MAX
+    }
+
+    fn block_base_fee(&self) -> U256 {
+        U256::ZERO
+    }
+
+    fn block_prevrandao(&self) -> U256 {
+        U256::ZERO
+    }
+
     fn rent(&self) -> &Rent {
         &self.rent
     }
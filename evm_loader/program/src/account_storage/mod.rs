@@ -46,6 +46,14 @@ pub trait AccountStorage {
     fn block_number(&self) -> U256;
     /// Get block timestamp
     fn block_timestamp(&self) -> U256;
+    /// Get block coinbase
+    fn block_coinbase(&self) -> Address;
+    /// Get block gas limit
+    fn block_gas_limit(&self) -> U256;
+    /// Get block base fee
+    fn block_base_fee(&self) -> U256;
+    /// Get block `prevrandao` (difficulty before the merge)
+    fn block_prevrandao(&self) -> U256;
     /// Get block hash
     async fn block_hash(&self, number: u64) -> [u8; 32];
 
@@ -48,6 +48,9 @@ fn process_instruction<'a>(
         EvmInstruction::ConfigGetVersion => {
             instruction::config_get_version::process(program_id, accounts, instruction)
         }
+        EvmInstruction::ConfigGetOperatorStatus => {
+            instruction::config_get_operator_status::process(program_id, accounts, instruction)
+        }
         _ => {
             log_msg!("Emergency image: all instructions are rejected");
             Err(ProgramError::InvalidInstructionData.into())
@@ -166,6 +169,9 @@ fn process_instruction<'a>(
         EvmInstruction::ConfigGetVersion => {
             instruction::config_get_version::process(program_id, accounts, instruction)
         }
+        EvmInstruction::ConfigGetOperatorStatus => {
+            instruction::config_get_operator_status::process(program_id, accounts, instruction)
+        }
         EvmInstruction::OperatorBalanceCreate => {
             instruction::operator_create_balance::process(program_id, accounts, instruction)
         }
@@ -175,6 +181,15 @@ fn process_instruction<'a>(
         EvmInstruction::OperatorBalanceWithdraw => {
             instruction::operator_withdraw_balance::process(program_id, accounts, instruction)
         }
+        EvmInstruction::OperatorRegistryAdd => {
+            instruction::operator_registry_add::process(program_id, accounts, instruction)
+        }
+        EvmInstruction::OperatorRegistryRemove => {
+            instruction::operator_registry_remove::process(program_id, accounts, instruction)
+        }
+        EvmInstruction::OperatorRegistrySuspend => {
+            instruction::operator_registry_suspend::process(program_id, accounts, instruction)
+        }
     }
     .map_err(ProgramError::from)
 }
@@ -88,6 +88,9 @@ pub enum Error {
     #[error("Operator is not authorized")]
     UnauthorizedOperator,
 
+    #[error("Operator {0} is suspended")]
+    OperatorSuspended(Pubkey),
+
     #[error("Storage Account is uninitialized")]
     StorageAccountUninitialized,
 
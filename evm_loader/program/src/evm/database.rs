@@ -48,6 +48,10 @@ pub trait Database {
     async fn block_hash(&self, number: U256) -> Result<[u8; 32]>;
     fn block_number(&self) -> Result<U256>;
     fn block_timestamp(&self) -> Result<U256>;
+    fn block_coinbase(&self) -> Result<Address>;
+    fn block_gas_limit(&self) -> Result<U256>;
+    fn block_base_fee(&self) -> Result<U256>;
+    fn block_prevrandao(&self) -> Result<U256>;
     fn rent(&self) -> &Rent;
     fn return_data(&self) -> Option<(Pubkey, Vec<u8>)>;
     fn set_return_data(&mut self, data: &[u8]);
@@ -8,7 +8,7 @@ use crate::error::Error;
 use super::utils::checked_next_multiple_of_32;
 use super::Buffer;
 
-const MAX_MEMORY_SIZE: usize = 64 * 1024;
+pub const MAX_MEMORY_SIZE: usize = 64 * 1024;
 const MEMORY_CAPACITY: usize = 1024;
 const MEMORY_ALIGN: usize = 1;
 
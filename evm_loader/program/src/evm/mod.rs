@@ -10,6 +10,7 @@ use maybe_async::maybe_async;
 use serde::{Deserialize, Serialize};
 
 pub use buffer::Buffer;
+pub use memory::MAX_MEMORY_SIZE;
 
 use crate::evm::tracing::EventListener;
 #[cfg(target_os = "solana")]
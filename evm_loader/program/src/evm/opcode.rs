@@ -679,10 +679,11 @@ impl<B: Database, T: EventListener> Machine<B, T> {
     }
 
     /// address of the current block's miner
-    /// NOT SUPPORTED
     #[maybe_async]
-    pub async fn opcode_coinbase(&mut self, _backend: &mut B) -> Result<Action> {
-        self.stack.push_zero()?;
+    pub async fn opcode_coinbase(&mut self, backend: &mut B) -> Result<Action> {
+        let coinbase = backend.block_coinbase()?;
+
+        self.stack.push_address(&coinbase)?;
 
         Ok(Action::Continue)
     }
@@ -707,20 +708,22 @@ impl<B: Database, T: EventListener> Machine<B, T> {
         Ok(Action::Continue)
     }
 
-    /// current block's difficulty
-    /// NOT SUPPORTED
+    /// current block's difficulty, `prevrandao` since the merge
     #[maybe_async]
-    pub async fn opcode_difficulty(&mut self, _backend: &mut B) -> Result<Action> {
-        self.stack.push_zero()?;
+    pub async fn opcode_difficulty(&mut self, backend: &mut B) -> Result<Action> {
+        let prevrandao = backend.block_prevrandao()?;
+
+        self.stack.push_u256(prevrandao)?;
 
         Ok(Action::Continue)
     }
 
     /// current block's gas limit
-    /// NOT SUPPORTED
     #[maybe_async]
-    pub async fn opcode_gaslimit(&mut self, _backend: &mut B) -> Result<Action> {
-        self.stack.push_u256(U256::MAX)?;
+    pub async fn opcode_gaslimit(&mut self, backend: &mut B) -> Result<Action> {
+        let gas_limit = backend.block_gas_limit()?;
+
+        self.stack.push_u256(gas_limit)?;
 
         Ok(Action::Continue)
     }
@@ -748,10 +751,11 @@ impl<B: Database, T: EventListener> Machine<B, T> {
     }
 
     /// London hardfork, EIP-3198: current block's base fee
-    /// NOT SUPPORTED
     #[maybe_async]
-    pub async fn opcode_basefee(&mut self, _backend: &mut B) -> Result<Action> {
-        self.stack.push_zero()?;
+    pub async fn opcode_basefee(&mut self, backend: &mut B) -> Result<Action> {
+        let base_fee = backend.block_base_fee()?;
+
+        self.stack.push_u256(base_fee)?;
 
         Ok(Action::Continue)
     }
@@ -13,7 +13,7 @@ use mpl_token_metadata::{
 use solana_program::pubkey::Pubkey;
 
 use crate::{
-    account::ACCOUNT_SEED_VERSION,
+    account::{token, ACCOUNT_SEED_VERSION},
     error::{Error, Result},
     evm::database::Database,
     types::Address,
@@ -252,6 +252,12 @@ async fn create_master_edition<State: Database>(
     let (metadata_pubkey, _) = Metadata::find_pda(&mint);
     let (edition_pubkey, _) = MasterEdition::find_pda(&mint);
 
+    // Master edition takes over the mint authority from the token program of the mint
+    let token_program = state.external_account(mint).await?.owner;
+    if !token::is_token_program(&token_program) {
+        return Err(Error::AccountInvalidOwner(mint, spl_token::ID));
+    }
+
     let mut instruction_builder = CreateMasterEditionV3Builder::new();
     instruction_builder
         .metadata(metadata_pubkey)
@@ -259,7 +265,8 @@ async fn create_master_edition<State: Database>(
         .mint(mint)
         .mint_authority(signer_pubkey)
         .update_authority(signer_pubkey)
-        .payer(state.operator());
+        .payer(state.operator())
+        .token_program(token_program);
 
     if let Some(max_supply) = max_supply {
         instruction_builder.max_supply(max_supply);
@@ -296,7 +303,12 @@ async fn is_nft<State: Database>(
 ) -> Result<Vec<u8>> {
     let is_nft = metadata(context, state, mint).await?.map_or_else(
         || false,
-        |m| m.token_standard == Some(TokenStandard::NonFungible),
+        |m| {
+            matches!(
+                m.token_standard,
+                Some(TokenStandard::NonFungible | TokenStandard::ProgrammableNonFungible)
+            )
+        },
     );
 
     Ok(to_solidity_bool(is_nft))
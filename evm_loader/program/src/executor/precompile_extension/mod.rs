@@ -1,15 +1,17 @@
 use crate::{
+    account::token,
     error::Result,
     evm::{database::Database, Context},
     types::Address,
 };
 use maybe_async::maybe_async;
-use solana_program::{pubkey::Pubkey, system_instruction};
+use solana_program::{instruction::Instruction, pubkey::Pubkey, system_instruction};
+use spl_transfer_hook_interface::get_extra_account_metas_address;
 
 use super::OwnedAccountInfo;
 
 mod call_solana;
-// mod metaplex;
+mod metaplex;
 mod neon_token;
 mod query_account;
 mod spl_token;
@@ -64,9 +66,9 @@ impl PrecompiledContracts {
             Self::SYSTEM_ACCOUNT_SPL_TOKEN => {
                 Some(spl_token::spl_token(state, address, input, context, is_static).await)
             }
-            // Self::SYSTEM_ACCOUNT_METAPLEX => {
-            //     Some(metaplex::metaplex(state, address, input, context, is_static).await)
-            // }
+            Self::SYSTEM_ACCOUNT_METAPLEX => {
+                Some(metaplex::metaplex(state, address, input, context, is_static).await)
+            }
             Self::SYSTEM_ACCOUNT_CALL_SOLANA => {
                 Some(call_solana::call_solana(state, address, input, context, is_static).await)
             }
@@ -107,3 +109,143 @@ pub async fn create_account<State: Database>(
 
     Ok(())
 }
+
+/// `transfer_checked` of the token program which owns the `mint`.
+/// Accounts of the Token-2022 transfer hook are resolved from the validation account of the hook.
+#[maybe_async]
+pub async fn transfer_checked<State: Database>(
+    state: &State,
+    mint: &OwnedAccountInfo,
+    source: &Pubkey,
+    destination: &Pubkey,
+    authority: &Pubkey,
+    amount: u64,
+    decimals: u8,
+) -> Result<Instruction> {
+    let hook = match token::transfer_hook_program_id(&mint.owner, &mint.data)? {
+        Some(hook_program_id) => {
+            let validation = get_extra_account_metas_address(&mint.key, &hook_program_id);
+            let validation_account = state.external_account(validation).await?;
+            Some((hook_program_id, validation_account))
+        }
+        None => None,
+    };
+
+    transfer_checked_instruction(
+        mint,
+        source,
+        destination,
+        authority,
+        amount,
+        decimals,
+        hook.as_ref(),
+    )
+}
+
+/// `hook` is the transfer hook program of the mint and its validation account
+fn transfer_checked_instruction(
+    mint: &OwnedAccountInfo,
+    source: &Pubkey,
+    destination: &Pubkey,
+    authority: &Pubkey,
+    amount: u64,
+    decimals: u8,
+    hook: Option<&(Pubkey, OwnedAccountInfo)>,
+) -> Result<Instruction> {
+    let mut transfer = spl_token_2022::instruction::transfer_checked(
+        &mint.owner,
+        source,
+        &mint.key,
+        destination,
+        authority,
+        &[],
+        amount,
+        decimals,
+    )?;
+
+    if let Some((hook_program_id, validation)) = hook {
+        token::add_transfer_hook_accounts(
+            &mut transfer,
+            hook_program_id,
+            &validation.key,
+            &validation.data,
+            amount,
+        )?;
+    }
+
+    Ok(transfer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::account::token::tests::{mint_2022, validation_data};
+    use solana_program::{instruction::AccountMeta, program_pack::Pack};
+
+    fn owned(key: Pubkey, owner: Pubkey, data: Vec<u8>) -> OwnedAccountInfo {
+        OwnedAccountInfo {
+            key,
+            is_signer: false,
+            is_writable: false,
+            lamports: 1_000_000_000,
+            data,
+            owner,
+            executable: false,
+            rent_epoch: 0,
+        }
+    }
+
+    #[test]
+    fn test_transfer_checked() {
+        let (source, destination, authority) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let transfer = |mint: &OwnedAccountInfo, hook: Option<&(Pubkey, OwnedAccountInfo)>| {
+            transfer_checked_instruction(mint, &source, &destination, &authority, 100, 6, hook)
+                .unwrap()
+        };
+
+        let mut data = vec![0; ::spl_token::state::Mint::LEN];
+        let mint = ::spl_token::state::Mint {
+            decimals: 6,
+            is_initialized: true,
+            ..::spl_token::state::Mint::default()
+        };
+        Pack::pack(mint, &mut data).unwrap();
+        let mint = owned(Pubkey::new_unique(), ::spl_token::ID, data);
+        let instruction = transfer(&mint, None);
+        assert_eq!(instruction.program_id, ::spl_token::ID);
+        assert_eq!(instruction.accounts.len(), 4);
+
+        let mint = owned(Pubkey::new_unique(), spl_token_2022::ID, mint_2022(6, None));
+        let instruction = transfer(&mint, None);
+        assert_eq!(instruction.program_id, spl_token_2022::ID);
+        assert_eq!(instruction.accounts[1].pubkey, mint.key);
+        assert_eq!(instruction.accounts.len(), 4);
+
+        let hook_program_id = Pubkey::new_unique();
+        let mint = owned(
+            Pubkey::new_unique(),
+            spl_token_2022::ID,
+            mint_2022(6, Some(hook_program_id)),
+        );
+        let extra = AccountMeta::new(Pubkey::new_unique(), false);
+        let validation = owned(
+            get_extra_account_metas_address(&mint.key, &hook_program_id),
+            hook_program_id,
+            validation_data(&[extra.clone()]),
+        );
+        let instruction = transfer(&mint, Some(&(hook_program_id, validation.clone())));
+        assert_eq!(instruction.program_id, spl_token_2022::ID);
+        assert_eq!(
+            &instruction.accounts[4..],
+            &[
+                extra,
+                AccountMeta::new_readonly(hook_program_id, false),
+                AccountMeta::new_readonly(validation.key, false),
+            ]
+        );
+    }
+}
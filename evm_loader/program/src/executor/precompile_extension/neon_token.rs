@@ -3,11 +3,13 @@ use std::convert::TryInto;
 use arrayref::array_ref;
 use ethnum::U256;
 use maybe_async::maybe_async;
-use solana_program::{account_info::IntoAccountInfo, program_pack::Pack, pubkey::Pubkey};
-use spl_associated_token_account::get_associated_token_address;
+use solana_program::pubkey::Pubkey;
+use spl_associated_token_account::get_associated_token_address_with_program_id;
 
+use super::{transfer_checked, OwnedAccountInfo};
 use crate::{
     account::token,
+    config::DEPOSIT_AUTHORITY_SEED,
     error::{Error, Result},
     evm::database::Database,
     types::Address,
@@ -62,6 +64,58 @@ pub async fn neon_token<State: Database>(
     Err(Error::UnknownPrecompileMethodSelector(*address, *method_id))
 }
 
+/// Transfer from the deposit pool to the associated token account of the target
+struct Withdrawal {
+    token_program: Pubkey,
+    decimals: u8,
+    /// Amount in the mint units
+    amount: u64,
+    target_token: Pubkey,
+}
+
+impl Withdrawal {
+    fn new(mint: &OwnedAccountInfo, target: &Pubkey, value: U256) -> Result<Self> {
+        if !token::is_token_program(&mint.owner) {
+            return Err(Error::Custom(std::format!(
+                "Neon Withdraw: mint {} is not owned by a token program",
+                mint.key
+            )));
+        }
+        let token_program = mint.owner;
+        let mint_data: spl_token::state::Mint = token::unpack(&token_program, &mint.data)?;
+
+        assert!(mint_data.decimals < 18);
+
+        let additional_decimals: u32 = (18 - mint_data.decimals).into();
+        let min_amount: u128 = u128::pow(10, additional_decimals);
+
+        let spl_amount = value / min_amount;
+        let remainder = value % min_amount;
+
+        if spl_amount > U256::from(u64::MAX) {
+            return Err(Error::Custom(
+                "Neon Withdraw: value exceeds u64::max".to_string(),
+            ));
+        }
+
+        if remainder != 0 {
+            return Err(Error::Custom(std::format!(
+                "Neon Withdraw: value must be divisible by 10^{additional_decimals}"
+            )));
+        }
+
+        let target_token =
+            get_associated_token_address_with_program_id(target, &mint.key, &token_program);
+
+        Ok(Self {
+            token_program,
+            decimals: mint_data.decimals,
+            amount: spl_amount.as_u64(),
+            target_token,
+        })
+    }
+}
+
 #[maybe_async]
 async fn withdraw<State: Database>(
     state: &mut State,
@@ -75,65 +129,50 @@ async fn withdraw<State: Database>(
     }
 
     let mint_address = state.chain_id_to_token(chain_id);
+    let mint_account = state.external_account(mint_address).await?;
 
-    let mut mint_account = state.external_account(mint_address).await?;
-    let mint_data = {
-        let info = mint_account.into_account_info();
-        token::Mint::from_account(&info)?.into_data()
-    };
-
-    assert!(mint_data.decimals < 18);
-
-    let additional_decimals: u32 = (18 - mint_data.decimals).into();
-    let min_amount: u128 = u128::pow(10, additional_decimals);
-
-    let spl_amount = value / min_amount;
-    let remainder = value % min_amount;
+    let Withdrawal {
+        token_program,
+        decimals,
+        amount,
+        target_token,
+    } = Withdrawal::new(&mint_account, &target, value)?;
 
-    if spl_amount > U256::from(u64::MAX) {
-        return Err(Error::Custom(
-            "Neon Withdraw: value exceeds u64::max".to_string(),
-        ));
-    }
-
-    if remainder != 0 {
-        return Err(Error::Custom(std::format!(
-            "Neon Withdraw: value must be divisible by 10^{additional_decimals}"
-        )));
-    }
-
-    let target_token = get_associated_token_address(&target, &mint_address);
     let account = state.external_account(target_token).await?;
-    if !spl_token::check_id(&account.owner) {
+    if account.owner != token_program {
         use spl_associated_token_account::instruction::create_associated_token_account;
 
         let create_associated = create_associated_token_account(
             &state.operator(),
             &target,
             &mint_address,
-            &spl_token::ID,
+            &token_program,
         );
 
-        let fee = state.rent().minimum_balance(spl_token::state::Account::LEN);
+        let account_len = token::account_len(&token_program, &mint_account.data)?;
+        let fee = state.rent().minimum_balance(account_len);
         state
             .queue_external_instruction(create_associated, vec![], fee, true)
             .await?;
     }
 
-    let (authority, bump_seed) = Pubkey::find_program_address(&[b"Deposit"], state.program_id());
-    let pool = get_associated_token_address(&authority, &mint_address);
+    let (authority, bump_seed) =
+        Pubkey::find_program_address(&[DEPOSIT_AUTHORITY_SEED.as_bytes()], state.program_id());
+    let pool =
+        get_associated_token_address_with_program_id(&authority, &mint_address, &token_program);
 
-    let transfer = spl_token::instruction::transfer_checked(
-        &spl_token::ID,
+    // Token-2022 transfer fee is withheld from the amount received by the target
+    let transfer = transfer_checked(
+        state,
+        &mint_account,
         &pool,
-        &mint_address,
         &target_token,
         &authority,
-        &[],
-        spl_amount.as_u64(),
-        mint_data.decimals,
-    )?;
-    let transfer_seeds = vec![b"Deposit".to_vec(), vec![bump_seed]];
+        amount,
+        decimals,
+    )
+    .await?;
+    let transfer_seeds = vec![DEPOSIT_AUTHORITY_SEED.as_bytes().to_vec(), vec![bump_seed]];
 
     state.burn(source, chain_id, value).await?;
     state
@@ -142,3 +181,57 @@ async fn withdraw<State: Database>(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::account::token::tests::mint_2022;
+
+    fn mint(owner: Pubkey, data: Vec<u8>) -> OwnedAccountInfo {
+        OwnedAccountInfo {
+            key: Pubkey::new_unique(),
+            is_signer: false,
+            is_writable: false,
+            lamports: 1_000_000_000,
+            data,
+            owner,
+            executable: false,
+            rent_epoch: 0,
+        }
+    }
+
+    #[test]
+    fn test_withdrawal_token_2022() {
+        let target = Pubkey::new_unique();
+        let mint = mint(spl_token_2022::ID, mint_2022(6, Some(Pubkey::new_unique())));
+
+        let withdrawal = Withdrawal::new(&mint, &target, U256::new(5 * 10_u128.pow(12))).unwrap();
+        assert_eq!(withdrawal.token_program, spl_token_2022::ID);
+        assert_eq!(withdrawal.decimals, 6);
+        assert_eq!(withdrawal.amount, 5);
+        assert_eq!(
+            withdrawal.target_token,
+            get_associated_token_address_with_program_id(&target, &mint.key, &spl_token_2022::ID)
+        );
+        assert_ne!(
+            withdrawal.target_token,
+            spl_associated_token_account::get_associated_token_address(&target, &mint.key)
+        );
+
+        // Value below the mint precision
+        assert!(Withdrawal::new(&mint, &target, U256::new(5 * 10_u128.pow(12) + 1)).is_err());
+    }
+
+    #[test]
+    fn test_withdrawal_invalid_mint() {
+        let target = Pubkey::new_unique();
+        let value = U256::new(10_u128.pow(12));
+
+        let not_token = mint(Pubkey::new_unique(), mint_2022(6, None));
+        assert!(Withdrawal::new(&not_token, &target, value).is_err());
+
+        // Token-2022 mint with extensions is not a valid SPL Token mint
+        let extended = mint(spl_token::ID, mint_2022(6, Some(Pubkey::new_unique())));
+        assert!(Withdrawal::new(&extended, &target, value).is_err());
+    }
+}
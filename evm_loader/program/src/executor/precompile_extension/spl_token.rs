@@ -3,12 +3,13 @@ use std::convert::{Into, TryInto};
 use ethnum::U256;
 use maybe_async::maybe_async;
 use solana_program::{
-    program_error::ProgramError, program_pack::Pack, pubkey::Pubkey, system_program,
+    instruction::Instruction, program_error::ProgramError, program_pack::Pack, pubkey::Pubkey,
+    system_program,
 };
 
-use super::create_account;
+use super::{create_account, transfer_checked};
 use crate::{
-    account::ACCOUNT_SEED_VERSION,
+    account::{token, ACCOUNT_SEED_VERSION},
     error::{Error, Result},
     evm::database::Database,
     types::Address,
@@ -349,17 +350,25 @@ async fn initialize_account<State: Database>(
         vec![bump_seed],
     ];
 
-    create_account(
-        state,
-        &account,
-        spl_token::state::Account::LEN,
-        &spl_token::ID,
-        seeds,
-    )
-    .await?;
+    let mint_account = state.external_account(mint).await?;
+    let token_program = mint_account.owner;
+    if !token::is_token_program(&token_program) {
+        return Err(Error::AccountInvalidOwner(mint, spl_token::ID));
+    }
 
-    let initialize_mint = spl_token::instruction::initialize_account2(
-        &spl_token::ID,
+    let space = token::account_len(&token_program, &mint_account.data)?;
+    create_account(state, &account, space, &token_program, seeds).await?;
+
+    if spl_token_2022::check_id(&token_program) {
+        let immutable_owner =
+            spl_token_2022::instruction::initialize_immutable_owner(&token_program, &account_key)?;
+        state
+            .queue_external_instruction(immutable_owner, vec![], 0, true)
+            .await?;
+    }
+
+    let initialize_mint = spl_token_2022::instruction::initialize_account2(
+        &token_program,
         &account_key,
         &mint,
         &owner.unwrap_or(signer_pubkey),
@@ -386,8 +395,9 @@ async fn close_account<State: Database>(
         vec![bump_seed],
     ];
 
-    let close_account = spl_token::instruction::close_account(
-        &spl_token::ID,
+    let token_program = token_program(state, account).await?;
+    let close_account = spl_token_2022::instruction::close_account(
+        &token_program,
         &account,
         &state.operator(),
         &signer_pubkey,
@@ -417,8 +427,9 @@ async fn approve<State: Database>(
         vec![bump_seed],
     ];
 
-    let approve = spl_token::instruction::approve(
-        &spl_token::ID,
+    let token_program = token_program(state, source).await?;
+    let approve = spl_token_2022::instruction::approve(
+        &token_program,
         &source,
         &target,
         &signer_pubkey,
@@ -447,7 +458,9 @@ async fn revoke<State: Database>(
         vec![bump_seed],
     ];
 
-    let revoke = spl_token::instruction::revoke(&spl_token::ID, &account, &signer_pubkey, &[])?;
+    let token_program = token_program(state, account).await?;
+    let revoke =
+        spl_token_2022::instruction::revoke(&token_program, &account, &signer_pubkey, &[])?;
     state
         .queue_external_instruction(revoke, vec![seeds], 0, true)
         .await?;
@@ -476,14 +489,7 @@ async fn transfer<State: Database>(
         vec![bump_seed],
     ];
 
-    let transfer = spl_token::instruction::transfer(
-        &spl_token::ID,
-        &source,
-        &target,
-        &signer_pubkey,
-        &[],
-        amount,
-    )?;
+    let transfer = transfer_instruction(state, source, target, signer_pubkey, amount).await?;
     state
         .queue_external_instruction(transfer, vec![seeds], 0, true)
         .await?;
@@ -520,14 +526,7 @@ async fn transfer_with_seed<State: Database>(
         vec![signer_seed],
     ];
 
-    let transfer = spl_token::instruction::transfer(
-        &spl_token::ID,
-        &source,
-        &target,
-        &signer_pubkey,
-        &[],
-        amount,
-    )?;
+    let transfer = transfer_instruction(state, source, target, signer_pubkey, amount).await?;
     state
         .queue_external_instruction(transfer, vec![seeds], 0, true)
         .await?;
@@ -556,8 +555,9 @@ async fn mint_to<State: Database>(
         vec![bump_seed],
     ];
 
-    let mint_to = spl_token::instruction::mint_to(
-        &spl_token::ID,
+    let token_program = token_program(state, mint).await?;
+    let mint_to = spl_token_2022::instruction::mint_to(
+        &token_program,
         &mint,
         &target,
         &signer_pubkey,
@@ -592,9 +592,10 @@ async fn burn<State: Database>(
         vec![bump_seed],
     ];
 
+    let token_program = token_program(state, mint).await?;
     #[rustfmt::skip]
-    let burn = spl_token::instruction::burn(
-        &spl_token::ID,
+    let burn = spl_token_2022::instruction::burn(
+        &token_program,
         &source,
         &mint,
         &signer_pubkey,
@@ -624,8 +625,9 @@ async fn freeze<State: Database>(
         vec![bump_seed],
     ];
 
-    let freeze = spl_token::instruction::freeze_account(
-        &spl_token::ID,
+    let token_program = token_program(state, mint).await?;
+    let freeze = spl_token_2022::instruction::freeze_account(
+        &token_program,
         &target,
         &mint,
         &signer_pubkey,
@@ -654,9 +656,10 @@ async fn thaw<State: Database>(
         vec![bump_seed],
     ];
 
+    let token_program = token_program(state, mint).await?;
     #[rustfmt::skip]
-    let thaw = spl_token::instruction::thaw_account(
-        &spl_token::ID,
+    let thaw = spl_token_2022::instruction::thaw_account(
+        &token_program,
         &target,
         &mint,
         &signer_pubkey,
@@ -669,6 +672,56 @@ async fn thaw<State: Database>(
     Ok(vec![])
 }
 
+/// Token program of the account, SPL Token for accounts not owned by Token-2022
+#[maybe_async]
+async fn token_program<State: Database>(state: &State, account: Pubkey) -> Result<Pubkey> {
+    let account = state.external_account(account).await?;
+    if spl_token_2022::check_id(&account.owner) {
+        Ok(spl_token_2022::ID)
+    } else {
+        Ok(spl_token::ID)
+    }
+}
+
+/// Token-2022 transfers go through `transfer_checked` to apply the transfer fee and the transfer hook of the mint
+#[maybe_async]
+async fn transfer_instruction<State: Database>(
+    state: &State,
+    source: Pubkey,
+    target: Pubkey,
+    authority: Pubkey,
+    amount: u64,
+) -> Result<Instruction> {
+    let source_account = state.external_account(source).await?;
+    if !spl_token_2022::check_id(&source_account.owner) {
+        let transfer = spl_token::instruction::transfer(
+            &spl_token::ID,
+            &source,
+            &target,
+            &authority,
+            &[],
+            amount,
+        )?;
+        return Ok(transfer);
+    }
+
+    let source_token: spl_token::state::Account =
+        token::unpack(&source_account.owner, &source_account.data)?;
+    let mint_account = state.external_account(source_token.mint).await?;
+    let mint: spl_token::state::Mint = token::unpack(&mint_account.owner, &mint_account.data)?;
+
+    transfer_checked(
+        state,
+        &mint_account,
+        &source,
+        &target,
+        &authority,
+        amount,
+        mint.decimals,
+    )
+    .await
+}
+
 #[allow(clippy::unnecessary_wraps)]
 fn find_account<State: Database>(
     context: &crate::evm::Context,
@@ -714,8 +767,8 @@ async fn get_account<State: Database>(
     account: Pubkey,
 ) -> Result<Vec<u8>> {
     let account = state.external_account(account).await?;
-    let token = if spl_token::check_id(&account.owner) {
-        spl_token::state::Account::unpack(&account.data)?
+    let token = if token::is_token_program(&account.owner) {
+        token::unpack::<spl_token::state::Account>(&account.owner, &account.data)?
     } else if system_program::check_id(&account.owner) {
         spl_token::state::Account::default()
     } else {
@@ -749,8 +802,8 @@ async fn get_mint<State: Database>(
     account: Pubkey,
 ) -> Result<Vec<u8>> {
     let account = state.external_account(account).await?;
-    let mint = if spl_token::check_id(&account.owner) {
-        spl_token::state::Mint::unpack(&account.data)?
+    let mint = if token::is_token_program(&account.owner) {
+        token::unpack::<spl_token::state::Mint>(&account.owner, &account.data)?
     } else if system_program::check_id(&account.owner) {
         spl_token::state::Mint::default()
     } else {
@@ -3,7 +3,7 @@ use std::collections::BTreeMap;
 
 use ethnum::{AsU256, U256};
 use maybe_async::maybe_async;
-// use mpl_token_metadata::programs::MPL_TOKEN_METADATA_ID;
+use mpl_token_metadata::programs::MPL_TOKEN_METADATA_ID;
 use solana_program::instruction::Instruction;
 use solana_program::pubkey::Pubkey;
 use solana_program::rent::Rent;
@@ -419,6 +419,22 @@ impl<'a, B: AccountStorage> Database for ExecutorState<'a, B> {
         Ok(cache.block_timestamp)
     }
 
+    fn block_coinbase(&self) -> Result<Address> {
+        Ok(self.backend.block_coinbase())
+    }
+
+    fn block_gas_limit(&self) -> Result<U256> {
+        Ok(self.backend.block_gas_limit())
+    }
+
+    fn block_base_fee(&self) -> Result<U256> {
+        Ok(self.backend.block_base_fee())
+    }
+
+    fn block_prevrandao(&self) -> Result<U256> {
+        Ok(self.backend.block_prevrandao())
+    }
+
     async fn external_account(&self, address: Pubkey) -> Result<OwnedAccountInfo> {
         self.touch_solana(address);
 
@@ -469,6 +485,13 @@ impl<'a, B: AccountStorage> Database for ExecutorState<'a, B> {
                     program_id if spl_token::check_id(program_id) => {
                         crate::external_programs::spl_token::emulate(data, meta, &mut accounts)?;
                     }
+                    program_id if spl_token_2022::check_id(program_id) => {
+                        crate::external_programs::spl_token_2022::emulate(
+                            data,
+                            meta,
+                            &mut accounts,
+                        )?;
+                    }
                     program_id if spl_associated_token_account::check_id(program_id) => {
                         crate::external_programs::spl_associated_token::emulate(
                             data,
@@ -477,14 +500,14 @@ impl<'a, B: AccountStorage> Database for ExecutorState<'a, B> {
                             self.rent(),
                         )?;
                     }
-                    // program_id if &MPL_TOKEN_METADATA_ID == program_id => {
-                    //     crate::external_programs::metaplex::emulate(
-                    //         data,
-                    //         meta,
-                    //         &mut accounts,
-                    //         self.rent(),
-                    //     )?;
-                    // }
+                    program_id if &MPL_TOKEN_METADATA_ID == program_id => {
+                        crate::external_programs::metaplex::emulate(
+                            data,
+                            meta,
+                            &mut accounts,
+                            self.rent(),
+                        )?;
+                    }
                     _ => {
                         return Err(Error::Custom(format!(
                             "Unknown external program for emulate: {program_id}"
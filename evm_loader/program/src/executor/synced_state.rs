@@ -205,6 +205,22 @@ impl<'a, B: AccountStorage + SyncedAccountStorage> Database for SyncedExecutorSt
         Ok(self.backend.block_timestamp())
     }
 
+    fn block_coinbase(&self) -> Result<Address> {
+        Ok(self.backend.block_coinbase())
+    }
+
+    fn block_gas_limit(&self) -> Result<U256> {
+        Ok(self.backend.block_gas_limit())
+    }
+
+    fn block_base_fee(&self) -> Result<U256> {
+        Ok(self.backend.block_base_fee())
+    }
+
+    fn block_prevrandao(&self) -> Result<U256> {
+        Ok(self.backend.block_prevrandao())
+    }
+
     async fn external_account(&self, address: Pubkey) -> Result<OwnedAccountInfo> {
         let account = self.backend.clone_solana_account(&address).await;
         return Ok(account);
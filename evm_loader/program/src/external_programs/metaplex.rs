@@ -154,8 +154,9 @@ fn create_master_edition_v3(
             mint.freeze_authority = COption::Some(*edition_account_key);
         }
 
+        // Token-2022 mint extensions follow the base state
         let mint_account = accounts.get_mut(mint_key).unwrap();
-        mint.pack_into_slice(&mut mint_account.data);
+        mint.pack_into_slice(&mut mint_account.data[..spl_token::state::Mint::LEN]);
     }
 
     Ok(())
@@ -1,4 +1,5 @@
-// pub mod metaplex;
+pub mod metaplex;
 pub mod spl_associated_token;
 pub mod spl_token;
+pub mod spl_token_2022;
 pub mod system;
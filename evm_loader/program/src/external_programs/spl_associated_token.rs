@@ -4,7 +4,7 @@ use crate::executor::OwnedAccountInfo;
 use borsh::BorshDeserialize;
 use solana_program::{
     entrypoint::ProgramResult, instruction::AccountMeta, program_error::ProgramError,
-    program_pack::Pack, pubkey::Pubkey, rent::Rent,
+    pubkey::Pubkey, rent::Rent,
 };
 use spl_associated_token_account::instruction::AssociatedTokenAccountInstruction;
 
@@ -32,9 +32,14 @@ pub fn emulate(
     // let system_program_key = &meta[4].pubkey;
     let spl_token_program_key = &meta[5].pubkey;
 
+    let account_len = crate::account::token::account_len(
+        spl_token_program_key,
+        &accounts[spl_token_mint_key].data,
+    )?;
+
     let required_lamports = {
         let associated_token_account = &accounts[associated_token_account_key];
-        rent.minimum_balance(spl_token::state::Account::LEN)
+        rent.minimum_balance(account_len)
             .max(1)
             .saturating_sub(associated_token_account.lamports)
     };
@@ -55,13 +60,19 @@ pub fn emulate(
         }
 
         associated_token_account.lamports += required_lamports;
-        associated_token_account.owner = spl_token::ID;
-        associated_token_account
-            .data
-            .resize(spl_token::state::Account::LEN, 0);
+        associated_token_account.owner = *spl_token_program_key;
+        associated_token_account.data.resize(account_len, 0);
     }
 
-    let initialize_account = spl_token::instruction::initialize_account3(
+    if spl_token_2022::check_id(spl_token_program_key) {
+        let immutable_owner = spl_token_2022::instruction::initialize_immutable_owner(
+            spl_token_program_key,
+            associated_token_account_key,
+        )?;
+        super::spl_token_2022::emulate(&immutable_owner.data, &immutable_owner.accounts, accounts)?;
+    }
+
+    let initialize_account = spl_token_2022::instruction::initialize_account3(
         spl_token_program_key,
         associated_token_account_key,
         spl_token_mint_key,
@@ -70,5 +81,9 @@ pub fn emulate(
 
     let instruction: &[u8] = &initialize_account.data;
     let meta: Vec<AccountMeta> = initialize_account.accounts;
-    super::spl_token::emulate(instruction, &meta, accounts)
+    if spl_token_2022::check_id(spl_token_program_key) {
+        super::spl_token_2022::emulate(instruction, &meta, accounts)
+    } else {
+        super::spl_token::emulate(instruction, &meta, accounts)
+    }
 }
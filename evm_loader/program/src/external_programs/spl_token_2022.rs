@@ -0,0 +1,237 @@
+use std::collections::BTreeMap;
+
+use crate::executor::OwnedAccountInfo;
+use solana_program::{
+    account_info::{AccountInfo, IntoAccountInfo},
+    entrypoint::ProgramResult,
+    instruction::AccountMeta,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+use spl_token_2022::extension::{
+    transfer_hook::TransferHook, BaseStateWithExtensionsMut, StateWithExtensionsMut,
+};
+use spl_token_2022::instruction::TokenInstruction;
+use spl_token_2022::processor::Processor;
+
+#[rustfmt::skip]
+pub fn emulate(
+    instruction: &[u8],
+    meta: &[AccountMeta],
+    accounts: &mut BTreeMap<Pubkey, OwnedAccountInfo>,
+) -> ProgramResult {
+    let accounts_info = accounts
+        .iter_mut()
+        .map(|(key, a)| (*key, a.into_account_info()))
+        .collect::<BTreeMap<Pubkey, AccountInfo>>();
+
+    let instruction_accounts: Vec<AccountInfo> = meta
+        .iter()
+        .map(|a| {
+            let mut info = accounts_info[&a.pubkey].clone();
+            info.is_writable = a.is_writable;
+            info.is_signer = a.is_signer;
+            info
+        })
+        .collect();
+
+    let program_id = &spl_token_2022::ID;
+    let instruction = TokenInstruction::unpack(instruction)?;
+
+    match instruction {
+        TokenInstruction::InitializeMint { decimals, mint_authority, freeze_authority } => {
+            Processor::process_initialize_mint(&instruction_accounts, decimals, mint_authority, freeze_authority)
+        }
+        TokenInstruction::InitializeMint2 { decimals, mint_authority, freeze_authority } => {
+            Processor::process_initialize_mint2(&instruction_accounts, decimals, mint_authority, freeze_authority)
+        }
+        TokenInstruction::InitializeAccount => {
+            Processor::process_initialize_account(&instruction_accounts)
+        }
+        TokenInstruction::InitializeAccount2 { owner } => {
+            Processor::process_initialize_account2(&instruction_accounts, owner)
+        }
+        TokenInstruction::InitializeAccount3 { owner } => {
+            Processor::process_initialize_account3(&instruction_accounts, owner)
+        }
+        TokenInstruction::InitializeMultisig { m } => {
+            Processor::process_initialize_multisig(&instruction_accounts, m)
+        }
+        TokenInstruction::InitializeMultisig2 { m } => {
+            Processor::process_initialize_multisig2(&instruction_accounts, m)
+        }
+        TokenInstruction::InitializeImmutableOwner => {
+            Processor::process_initialize_immutable_owner(&instruction_accounts)
+        }
+        #[allow(deprecated)]
+        TokenInstruction::Transfer { amount } => {
+            Processor::process_transfer(program_id, &instruction_accounts, amount, None, None)
+        }
+        TokenInstruction::Approve { amount } => {
+            Processor::process_approve(program_id, &instruction_accounts, amount, None)
+        }
+        TokenInstruction::Revoke => {
+            Processor::process_revoke(program_id, &instruction_accounts)
+        }
+        TokenInstruction::SetAuthority { authority_type, new_authority } => {
+            Processor::process_set_authority(program_id, &instruction_accounts, authority_type, new_authority)
+        }
+        TokenInstruction::MintTo { amount } => {
+            Processor::process_mint_to(program_id, &instruction_accounts, amount, None)
+        }
+        TokenInstruction::Burn { amount } => {
+            Processor::process_burn(program_id, &instruction_accounts, amount, None)
+        }
+        TokenInstruction::CloseAccount => {
+            Processor::process_close_account(program_id, &instruction_accounts)
+        }
+        TokenInstruction::FreezeAccount => {
+            Processor::process_toggle_freeze_account(program_id, &instruction_accounts, true)
+        }
+        TokenInstruction::ThawAccount => {
+            Processor::process_toggle_freeze_account(program_id, &instruction_accounts, false)
+        }
+        TokenInstruction::TransferChecked { amount, decimals } => {
+            transfer_checked(&instruction_accounts, amount, decimals)
+        }
+        TokenInstruction::ApproveChecked { amount, decimals } => {
+            Processor::process_approve(program_id, &instruction_accounts, amount, Some(decimals))
+        }
+        TokenInstruction::MintToChecked { amount, decimals } => {
+            Processor::process_mint_to(program_id, &instruction_accounts, amount, Some(decimals))
+        }
+        TokenInstruction::BurnChecked { amount, decimals } => {
+            Processor::process_burn(program_id, &instruction_accounts, amount, Some(decimals))
+        }
+        TokenInstruction::SyncNative => {
+            Processor::process_sync_native(&instruction_accounts)
+        }
+        _ => {
+            Err!(ProgramError::InvalidInstructionData; "SPL Token-2022: unknown instruction")
+        }
+    }
+}
+
+/// The processor invokes the transfer hook of the mint, the emulation only moves the tokens.
+/// The hook is disabled in the copy of the mint for the time of the transfer.
+fn transfer_checked(accounts: &[AccountInfo], amount: u64, decimals: u8) -> ProgramResult {
+    let mint = accounts.get(1).ok_or(ProgramError::NotEnoughAccountKeys)?;
+    let hook = replace_transfer_hook(mint, TransferHook::default())?;
+
+    let result =
+        Processor::process_transfer(&spl_token_2022::ID, accounts, amount, Some(decimals), None);
+
+    if let Some(hook) = hook {
+        replace_transfer_hook(mint, hook)?;
+    }
+
+    result
+}
+
+/// Returns the previous extension, `None` if the mint doesn't have the transfer hook
+fn replace_transfer_hook(
+    mint: &AccountInfo,
+    hook: TransferHook,
+) -> Result<Option<TransferHook>, ProgramError> {
+    let mut data = mint.try_borrow_mut_data()?;
+    let mut mint = StateWithExtensionsMut::<spl_token_2022::state::Mint>::unpack(&mut data)?;
+
+    let Ok(extension) = mint.get_extension_mut::<TransferHook>() else {
+        return Ok(None);
+    };
+
+    Ok(Some(std::mem::replace(extension, hook)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::account::token::{
+        self,
+        tests::{account_2022, mint_2022},
+    };
+    use solana_program::system_program;
+
+    fn owned(key: Pubkey, owner: Pubkey, data: Vec<u8>) -> OwnedAccountInfo {
+        OwnedAccountInfo {
+            key,
+            is_signer: false,
+            is_writable: false,
+            lamports: 1_000_000_000,
+            data,
+            owner,
+            executable: false,
+            rent_epoch: 0,
+        }
+    }
+
+    #[test]
+    fn test_transfer_checked_with_hook() {
+        let hook = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let source = Pubkey::new_unique();
+        let destination = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+
+        let mut accounts = BTreeMap::new();
+        for (key, owner, data) in [
+            (mint, spl_token_2022::ID, mint_2022(6, Some(hook))),
+            (
+                source,
+                spl_token_2022::ID,
+                account_2022(mint, authority, 100, None),
+            ),
+            (
+                destination,
+                spl_token_2022::ID,
+                account_2022(mint, hook, 0, None),
+            ),
+            (authority, system_program::ID, vec![]),
+        ] {
+            accounts.insert(key, owned(key, owner, data));
+        }
+
+        let instruction = spl_token_2022::instruction::transfer_checked(
+            &spl_token_2022::ID,
+            &source,
+            &mint,
+            &destination,
+            &authority,
+            &[],
+            40,
+            6,
+        )
+        .unwrap();
+        emulate(&instruction.data, &instruction.accounts, &mut accounts).unwrap();
+
+        let amount = |key: Pubkey| {
+            token::unpack::<spl_token::state::Account>(&spl_token_2022::ID, &accounts[&key].data)
+                .unwrap()
+                .amount
+        };
+        assert_eq!(amount(source), 60);
+        assert_eq!(amount(destination), 40);
+
+        // The hook is not executed and is kept in the mint
+        let mint_data = &accounts[&mint].data;
+        assert_eq!(
+            token::transfer_hook_program_id(&spl_token_2022::ID, mint_data).unwrap(),
+            Some(hook)
+        );
+    }
+
+    #[test]
+    fn test_unsupported_instruction() {
+        let mint = Pubkey::new_unique();
+        let mut accounts = BTreeMap::new();
+        accounts.insert(mint, owned(mint, spl_token_2022::ID, mint_2022(6, None)));
+
+        let instruction = spl_token_2022::instruction::initialize_permanent_delegate(
+            &spl_token_2022::ID,
+            &mint,
+            &mint,
+        )
+        .unwrap();
+        assert!(emulate(&instruction.data, &instruction.accounts, &mut accounts).is_err());
+    }
+}
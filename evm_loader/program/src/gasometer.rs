@@ -12,6 +12,9 @@ const WRITE_TO_HOLDER_TRX_COST: u64 = LAMPORTS_PER_SIGNATURE;
 pub const CANCEL_TRX_COST: u64 = LAMPORTS_PER_SIGNATURE;
 pub const LAST_ITERATION_COST: u64 = LAMPORTS_PER_SIGNATURE;
 
+pub const MIN_ACCOUNTS_TO_USE_ALT: usize = 30;
+pub const ACCOUNTS_PER_ALT_EXTEND: usize = 30;
+
 pub struct Gasometer {
     paid_gas: U256,
     gas: u64,
@@ -54,26 +57,34 @@ impl Gasometer {
     }
 
     pub fn record_write_to_holder(&mut self, trx: &Transaction) {
-        let size: u64 = trx.rlp_len().try_into().expect("usize is 8 bytes");
-        let cost: u64 = ((size + (HOLDER_MSG_SIZE - 1)) / HOLDER_MSG_SIZE)
-            .saturating_mul(WRITE_TO_HOLDER_TRX_COST);
+        let cost = write_to_holder_cost(trx.rlp_len());
 
         self.gas = self.gas.saturating_add(cost);
     }
 
     pub fn record_address_lookup_table(&mut self, accounts: &[AccountInfo]) {
-        const MIN_ACCOUNTS_TO_USE_ALT: usize = 30;
-        const ACCOUNTS_PER_ALT_EXTEND: usize = 30;
+        let cost = address_lookup_table_cost(accounts.len());
+
+        self.gas = self.gas.saturating_add(cost);
+    }
+}
 
-        if accounts.len() < MIN_ACCOUNTS_TO_USE_ALT {
-            return;
-        }
+/// Cost of writing the transaction of `rlp_len` bytes into a holder account
+#[must_use]
+pub fn write_to_holder_cost(rlp_len: usize) -> u64 {
+    let size: u64 = rlp_len.try_into().expect("usize is 8 bytes");
 
-        let extend_count =
-            (accounts.len() + (ACCOUNTS_PER_ALT_EXTEND - 1)) / ACCOUNTS_PER_ALT_EXTEND;
-        // create_alt + extend_alt + deactivate_alt + close_alt
-        let cost = (extend_count + 3) as u64 * LAMPORTS_PER_SIGNATURE;
+    ((size + (HOLDER_MSG_SIZE - 1)) / HOLDER_MSG_SIZE).saturating_mul(WRITE_TO_HOLDER_TRX_COST)
+}
 
-        self.gas = self.gas.saturating_add(cost);
+/// Cost of the address lookup table for the instruction with `accounts_count` accounts
+#[must_use]
+pub fn address_lookup_table_cost(accounts_count: usize) -> u64 {
+    if accounts_count < MIN_ACCOUNTS_TO_USE_ALT {
+        return 0;
     }
+
+    let extend_count = (accounts_count + (ACCOUNTS_PER_ALT_EXTEND - 1)) / ACCOUNTS_PER_ALT_EXTEND;
+    // create_alt + extend_alt + deactivate_alt + close_alt
+    (extend_count + 3) as u64 * LAMPORTS_PER_SIGNATURE
 }
@@ -0,0 +1,35 @@
+use arrayref::array_ref;
+use solana_program::{account_info::AccountInfo, pubkey::Pubkey};
+
+use crate::account::OperatorRecord;
+use crate::error::{Error, Result};
+
+pub const OPERATOR_STATUS_NOT_REGISTERED: u8 = 0;
+pub const OPERATOR_STATUS_ACTIVE: u8 = 1;
+pub const OPERATOR_STATUS_SUSPENDED: u8 = 2;
+
+pub fn process<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    instruction: &[u8],
+) -> Result<()> {
+    log_msg!("Instruction: Config Get Operator Status");
+
+    let operator = Pubkey::new_from_array(*array_ref![instruction, 0, 32]);
+    let (expected_key, _) = OperatorRecord::address(program_id, &operator);
+
+    let info = &accounts[0];
+    if *info.key != expected_key {
+        return Err(Error::AccountInvalidKey(*info.key, expected_key));
+    }
+
+    let status = match OperatorRecord::from_account(program_id, info) {
+        Ok(record) if record.is_suspended() => OPERATOR_STATUS_SUSPENDED,
+        Ok(_) => OPERATOR_STATUS_ACTIVE,
+        Err(_) => OPERATOR_STATUS_NOT_REGISTERED,
+    };
+
+    solana_program::program::set_return_data(&[status]);
+
+    Ok(())
+}
@@ -3,7 +3,10 @@
 
 use solana_program::program_error::ProgramError;
 
-/// `EvmInstruction` serialized in instruction data
+use crate::instruction_builder::tag;
+
+/// `EvmInstruction` serialized in instruction data.
+/// Instructions are built by `crate::instruction_builder`.
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum EvmInstruction {
     /// Deposits spl-tokens to an Ether account.
@@ -16,9 +19,10 @@ pub enum EvmInstruction {
     ///  `[WRITE]` spl-token pool (destination) account.
     ///  `[WRITE]` NeonEVM user balance account
     ///  `[WRITE]` NeonEVM user contract account
-    ///  `[]` SPL Token program id.
+    ///  `[]` SPL Token or SPL Token-2022 program id, the owner of the mint.
     ///  `[writeable,signer]` Funding account (must be a system account).
     ///  `[]` System program.
+    ///  `[]` Token-2022 transfer hook accounts (optional): extra accounts, hook program, validation account.
     /// Instruction data:
     ///  0..20  - destination address
     ///  20..28 - chain id in little endian
@@ -98,11 +102,13 @@ pub enum EvmInstruction {
     ///  `[WRITE]` Treasury
     ///  `[WRITE]` Operator Balance
     ///  `[]` System program
+    ///  `[]` Operator record: PDA[ACCOUNT_SEED_VERSION, "OPERATOR", operator]
     ///  `[WRITE]`  Other accounts
     /// Instruction data:
     ///  0..4 - treasury index in little endian
     ///  4..8 - step count in little endian
-    ///  8..  - transaction data
+    ///  8..12 - unique index (ignored, makes iterations distinct transactions)
+    ///  12..  - transaction data
     TransactionStepFromInstruction,
 
     /// Execute Iterative Transaction from Account
@@ -113,6 +119,7 @@ pub enum EvmInstruction {
     ///  `[WRITE]` Treasury
     ///  `[WRITE]` Operator Balance
     ///  `[]` System program
+    ///  `[]` Operator record: PDA[ACCOUNT_SEED_VERSION, "OPERATOR", operator]
     ///  `[WRITE]`  Other accounts
     /// Instruction data:
     ///  0..4 - treasury index in little endian
@@ -127,6 +134,7 @@ pub enum EvmInstruction {
     ///  `[WRITE]` Treasury
     ///  `[WRITE]` Operator Balance
     ///  `[]` System program
+    ///  `[]` Operator record: PDA[ACCOUNT_SEED_VERSION, "OPERATOR", operator]
     ///  `[WRITE]`  Other accounts
     /// Instruction data:
     ///  0..4 - treasury index in little endian
@@ -139,6 +147,8 @@ pub enum EvmInstruction {
     ///  `[WRITE]` State
     ///  `[SIGNER]` Operator
     ///  `[WRITE]` Operator Balance
+    ///  `[]` Operator record: PDA[ACCOUNT_SEED_VERSION, "OPERATOR", operator]
+    ///  `[WRITE]` Other accounts
     /// Instruction data:
     ///   0..32 - transaction hash
     Cancel,
@@ -195,6 +205,40 @@ pub enum EvmInstruction {
     ///  0..4 - treasury index in little-endian
     TransactionExecuteFromAccountWithSolanaCall,
 
+    /// Add an operator to the operator registry
+    ///
+    /// Accounts:
+    ///  `[WRITE]` Operator record: PDA[ACCOUNT_SEED_VERSION, "OPERATOR", operator]
+    ///  `[]` Program data (to get program upgrade-authority)
+    ///  `[SIGNER]` Program upgrade authority
+    ///  `[WRITE,SIGNER]` Payer
+    ///  `[]` System program
+    /// Instruction data:
+    ///  0..32 - operator pubkey
+    OperatorRegistryAdd,
+
+    /// Remove an operator from the operator registry
+    ///
+    /// Accounts:
+    ///  `[WRITE]` Operator record
+    ///  `[]` Program data (to get program upgrade-authority)
+    ///  `[SIGNER]` Program upgrade authority
+    ///  `[WRITE,SIGNER]` Receiver of the record lamports
+    /// Instruction data:
+    ///   None
+    /// Records of whitelisted operators are kept suspended, so the whitelist doesn't accept them again.
+    OperatorRegistryRemove,
+
+    /// Suspend or resume an operator in the operator registry
+    ///
+    /// Accounts:
+    ///  `[WRITE]` Operator record
+    ///  `[]` Program data (to get program upgrade-authority)
+    ///  `[SIGNER]` Program upgrade authority
+    /// Instruction data:
+    ///  0 - 1 to suspend, 0 to resume
+    OperatorRegistrySuspend,
+
     ConfigGetChainCount,
     ConfigGetChainInfo,
     ConfigGetEnvironment,
@@ -203,6 +247,13 @@ pub enum EvmInstruction {
     ConfigGetPropertyByName,
     ConfigGetStatus,
     ConfigGetVersion,
+    /// Returns operator status: 0 - not registered, 1 - active, 2 - suspended
+    ///
+    /// Accounts:
+    ///  `[]` Operator record
+    /// Instruction data:
+    ///  0..32 - operator pubkey
+    ConfigGetOperatorStatus,
 
     OperatorBalanceCreate,
     OperatorBalanceDelete,
@@ -215,36 +266,46 @@ impl EvmInstruction {
     /// # Errors
     /// Will return `ProgramError::InvalidInstructionData` if can't parse `tag`
     pub const fn parse(tag: &u8) -> Result<Self, ProgramError> {
-        Ok(match tag {
-            0x1e => Self::CollectTreasure,    // 30
-            0x24 => Self::HolderCreate,       // 36
-            0x25 => Self::HolderDelete,       // 37
-            0x26 => Self::HolderWrite,        // 38
-            0x29 => Self::CreateMainTreasury, // 41
+        Ok(match *tag {
+            tag::COLLECT_TREASURE => Self::CollectTreasure,
+            tag::HOLDER_CREATE => Self::HolderCreate,
+            tag::HOLDER_DELETE => Self::HolderDelete,
+            tag::HOLDER_WRITE => Self::HolderWrite,
+            tag::CREATE_MAIN_TREASURY => Self::CreateMainTreasury,
 
-            0x30 => Self::AccountCreateBalance,              // 48
-            0x31 => Self::Deposit,                           // 49
-            0x32 => Self::TransactionExecuteFromInstruction, // 50
-            0x33 => Self::TransactionExecuteFromAccount,     // 51
-            0x34 => Self::TransactionStepFromInstruction,    // 52
-            0x35 => Self::TransactionStepFromAccount,        // 53
-            0x36 => Self::TransactionStepFromAccountNoChainId, // 54
-            0x37 => Self::Cancel,                            // 55
-            0x38 => Self::TransactionExecuteFromInstructionWithSolanaCall, // 56
-            0x39 => Self::TransactionExecuteFromAccountWithSolanaCall, // 57
+            tag::ACCOUNT_CREATE_BALANCE => Self::AccountCreateBalance,
+            tag::DEPOSIT => Self::Deposit,
+            tag::TRANSACTION_EXECUTE_FROM_INSTRUCTION => Self::TransactionExecuteFromInstruction,
+            tag::TRANSACTION_EXECUTE_FROM_ACCOUNT => Self::TransactionExecuteFromAccount,
+            tag::TRANSACTION_STEP_FROM_INSTRUCTION => Self::TransactionStepFromInstruction,
+            tag::TRANSACTION_STEP_FROM_ACCOUNT => Self::TransactionStepFromAccount,
+            tag::TRANSACTION_STEP_FROM_ACCOUNT_NO_CHAINID => {
+                Self::TransactionStepFromAccountNoChainId
+            }
+            tag::CANCEL => Self::Cancel,
+            tag::TRANSACTION_EXECUTE_FROM_INSTRUCTION_WITH_SOLANA_CALL => {
+                Self::TransactionExecuteFromInstructionWithSolanaCall
+            }
+            tag::TRANSACTION_EXECUTE_FROM_ACCOUNT_WITH_SOLANA_CALL => {
+                Self::TransactionExecuteFromAccountWithSolanaCall
+            }
 
-            0x3A => Self::OperatorBalanceCreate,   // 58
-            0x3B => Self::OperatorBalanceDelete,   // 59
-            0x3C => Self::OperatorBalanceWithdraw, // 60
+            tag::OPERATOR_BALANCE_CREATE => Self::OperatorBalanceCreate,
+            tag::OPERATOR_BALANCE_DELETE => Self::OperatorBalanceDelete,
+            tag::OPERATOR_BALANCE_WITHDRAW => Self::OperatorBalanceWithdraw,
+            tag::OPERATOR_REGISTRY_ADD => Self::OperatorRegistryAdd,
+            tag::OPERATOR_REGISTRY_REMOVE => Self::OperatorRegistryRemove,
+            tag::OPERATOR_REGISTRY_SUSPEND => Self::OperatorRegistrySuspend,
 
-            0xA0 => Self::ConfigGetChainCount, // 160
-            0xA1 => Self::ConfigGetChainInfo,
-            0xA2 => Self::ConfigGetEnvironment,
-            0xA3 => Self::ConfigGetPropertyCount,
-            0xA4 => Self::ConfigGetPropertyByIndex,
-            0xA5 => Self::ConfigGetPropertyByName,
-            0xA6 => Self::ConfigGetStatus,
-            0xA7 => Self::ConfigGetVersion,
+            tag::CONFIG_GET_CHAIN_COUNT => Self::ConfigGetChainCount,
+            tag::CONFIG_GET_CHAIN_INFO => Self::ConfigGetChainInfo,
+            tag::CONFIG_GET_ENVIRONMENT => Self::ConfigGetEnvironment,
+            tag::CONFIG_GET_PROPERTY_COUNT => Self::ConfigGetPropertyCount,
+            tag::CONFIG_GET_PROPERTY_BY_INDEX => Self::ConfigGetPropertyByIndex,
+            tag::CONFIG_GET_PROPERTY_BY_NAME => Self::ConfigGetPropertyByName,
+            tag::CONFIG_GET_STATUS => Self::ConfigGetStatus,
+            tag::CONFIG_GET_VERSION => Self::ConfigGetVersion,
+            tag::CONFIG_GET_OPERATOR_STATUS => Self::ConfigGetOperatorStatus,
 
             _ => return Err(ProgramError::InvalidInstructionData),
         })
@@ -259,6 +320,7 @@ pub mod collect_treasury;
 pub mod config_get_chain_count;
 pub mod config_get_chain_info;
 pub mod config_get_environment;
+pub mod config_get_operator_status;
 pub mod config_get_property_by_index;
 pub mod config_get_property_by_name;
 pub mod config_get_property_count;
@@ -268,6 +330,9 @@ pub mod create_main_treasury;
 pub mod neon_tokens_deposit;
 pub mod operator_create_balance;
 pub mod operator_delete_balance;
+pub mod operator_registry_add;
+pub mod operator_registry_remove;
+pub mod operator_registry_suspend;
 pub mod operator_withdraw_balance;
 pub mod transaction_cancel;
 pub mod transaction_execute;
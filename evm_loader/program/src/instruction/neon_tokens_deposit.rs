@@ -1,11 +1,10 @@
 use arrayref::array_ref;
 use ethnum::U256;
-use solana_program::program::invoke_signed;
 use solana_program::{account_info::AccountInfo, pubkey::Pubkey, rent::Rent, sysvar::Sysvar};
-use spl_associated_token_account::get_associated_token_address;
+use spl_associated_token_account::get_associated_token_address_with_program_id;
 
 use crate::account::{program, token, AccountsDB, BalanceAccount, Operator, ACCOUNT_SEED_VERSION};
-use crate::config::{CHAIN_ID_LIST, DEFAULT_CHAIN_ID};
+use crate::config::{CHAIN_ID_LIST, DEFAULT_CHAIN_ID, DEPOSIT_AUTHORITY_SEED};
 use crate::error::{Error, Result};
 use crate::types::Address;
 
@@ -18,10 +17,10 @@ struct Accounts<'a> {
     token_program: program::Token<'a>,
     operator: Operator<'a>,
     system_program: program::System<'a>,
+    /// Accounts of the Token-2022 transfer hook
+    transfer_hook_accounts: &'a [AccountInfo<'a>],
 }
 
-const AUTHORITY_SEED: &[u8] = b"Deposit";
-
 impl<'a> Accounts<'a> {
     pub fn from_slice(accounts: &'a [AccountInfo<'a>]) -> Result<Accounts<'a>> {
         Ok(Accounts {
@@ -33,6 +32,7 @@ impl<'a> Accounts<'a> {
             token_program: program::Token::from_account(&accounts[5])?,
             operator: unsafe { Operator::from_account_not_whitelisted(&accounts[6]) }?,
             system_program: program::System::from_account(&accounts[7])?,
+            transfer_hook_accounts: &accounts[8..],
         })
     }
 }
@@ -66,6 +66,7 @@ fn validate(
     let contract_account = *accounts.contract_account.key;
     let pool = *accounts.pool.info.key;
     let mint = *accounts.mint.info.key;
+    let token_program = *accounts.mint.token_program();
 
     let (expected_pubkey, _) = address.find_balance_address(program_id, chain_id);
     if expected_pubkey != balance_account {
@@ -86,12 +87,27 @@ fn validate(
         return Err(Error::AccountInvalidKey(mint, expected_mint));
     }
 
-    let (authority_address, _) = Pubkey::find_program_address(&[AUTHORITY_SEED], program_id);
-    let expected_pool = get_associated_token_address(&authority_address, &mint);
+    if *accounts.token_program.key != token_program {
+        return Err(Error::AccountInvalidKey(
+            *accounts.token_program.key,
+            token_program,
+        ));
+    }
+
+    let (authority_address, _) =
+        Pubkey::find_program_address(&[DEPOSIT_AUTHORITY_SEED.as_bytes()], program_id);
+    let expected_pool =
+        get_associated_token_address_with_program_id(&authority_address, &mint, &token_program);
     if pool != expected_pool {
         return Err(Error::AccountInvalidKey(pool, expected_pool));
     }
 
+    if (accounts.pool.token_program() != &token_program)
+        || (accounts.source.token_program() != &token_program)
+    {
+        return Err(Error::from("Invalid token program"));
+    }
+
     if (accounts.pool.mint != mint) || (accounts.source.mint != mint) {
         return Err(Error::from("Invalid token mint"));
     }
@@ -121,29 +137,30 @@ fn execute(program_id: &Pubkey, accounts: Accounts, address: Address, chain_id:
         &[bump_seed],
     ];
 
-    let instruction = spl_token::instruction::transfer(
-        accounts.token_program.key,
-        accounts.source.info.key,
-        accounts.pool.info.key,
-        accounts.balance_account.key,
-        &[],
-        accounts.source.delegated_amount,
-    )?;
+    let token_decimals = accounts.mint.decimals;
+    assert!(token_decimals <= 18);
 
-    let account_infos: &[AccountInfo] = &[
+    // Transfer checked works for both token programs and invokes the Token-2022 transfer hook
+    let pool_amount = accounts.pool.amount;
+    spl_token_2022::onchain::invoke_transfer_checked(
+        accounts.token_program.key,
         accounts.source.info.clone(),
+        accounts.mint.info.clone(),
         accounts.pool.info.clone(),
         accounts.balance_account.clone(),
-        accounts.token_program.clone(),
-    ];
+        accounts.transfer_hook_accounts,
+        accounts.source.delegated_amount,
+        token_decimals,
+        &[signer_seeds],
+    )?;
 
-    invoke_signed(&instruction, account_infos, &[signer_seeds])?;
-
-    let token_decimals = accounts.mint.decimals;
-    assert!(token_decimals <= 18);
+    // Token-2022 transfer fee is withheld in the pool, only the received amount is deposited
+    let received = token::State::from_account(accounts.pool.info)?
+        .amount
+        .saturating_sub(pool_amount);
 
     let additional_decimals: u32 = (18 - token_decimals).into();
-    let deposit = U256::from(accounts.source.delegated_amount) * 10_u128.pow(additional_decimals);
+    let deposit = U256::from(received) * 10_u128.pow(additional_decimals);
 
     let accounts_db = AccountsDB::new(
         &[
@@ -172,3 +189,78 @@ fn execute(program_id: &Pubkey, accounts: Accounts, address: Address, chain_id:
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::account::token::tests::{account_2022, mint_2022};
+    use crate::account::TestAccount;
+    use solana_program::system_program;
+
+    fn token_account(key: Pubkey, data: Vec<u8>) -> TestAccount {
+        TestAccount {
+            key,
+            lamports: 1_000_000_000,
+            data,
+            owner: spl_token_2022::ID,
+            is_signer: false,
+        }
+    }
+
+    /// Deposit accounts of the Token-2022 mint with the pool of the `pool_token_program`
+    fn deposit_accounts(address: Address, pool_token_program: &Pubkey) -> Vec<TestAccount> {
+        let program_id = crate::ID;
+        let mint = CHAIN_ID_LIST
+            .iter()
+            .find(|chain| chain.0 == DEFAULT_CHAIN_ID)
+            .unwrap()
+            .2;
+        let (authority, _) =
+            Pubkey::find_program_address(&[DEPOSIT_AUTHORITY_SEED.as_bytes()], &program_id);
+        let pool =
+            get_associated_token_address_with_program_id(&authority, &mint, pool_token_program);
+        let (balance, _) = address.find_balance_address(&program_id, DEFAULT_CHAIN_ID);
+        let (contract, _) = address.find_solana_address(&program_id);
+        let source = account_2022(mint, Pubkey::new_unique(), 100, Some(balance));
+
+        vec![
+            token_account(mint, mint_2022(9, Some(Pubkey::new_unique()))),
+            token_account(Pubkey::new_unique(), source),
+            token_account(pool, account_2022(mint, authority, 0, None)),
+            TestAccount::system(balance),
+            TestAccount::system(contract),
+            TestAccount::system(spl_token_2022::ID),
+            TestAccount::operator(Pubkey::new_unique()),
+            TestAccount::system(system_program::ID),
+        ]
+    }
+
+    fn validate_accounts(accounts: &mut [TestAccount], address: Address) -> Result<()> {
+        let infos: Vec<AccountInfo> = accounts.iter_mut().map(TestAccount::info).collect();
+        let parsed = Accounts::from_slice(&infos)?;
+
+        validate(&crate::ID, &parsed, address, DEFAULT_CHAIN_ID)
+    }
+
+    #[test]
+    fn test_validate_token_2022() {
+        let address = Address::from([0x11; 20]);
+
+        let mut accounts = deposit_accounts(address, &spl_token_2022::ID);
+        assert!(validate_accounts(&mut accounts, address).is_ok());
+
+        // Token program must be the owner of the mint
+        let mut accounts = deposit_accounts(address, &spl_token_2022::ID);
+        accounts[5].key = spl_token::ID;
+        assert!(validate_accounts(&mut accounts, address).is_err());
+
+        // Pool is the associated token account of the Token-2022 program
+        let mut accounts = deposit_accounts(address, &spl_token::ID);
+        assert!(validate_accounts(&mut accounts, address).is_err());
+
+        // Tokens must be delegated to the balance account
+        let mut accounts = deposit_accounts(address, &spl_token_2022::ID);
+        let other = Address::from([0x22; 20]);
+        assert!(validate_accounts(&mut accounts, other).is_err());
+    }
+}
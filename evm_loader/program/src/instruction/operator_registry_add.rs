@@ -0,0 +1,58 @@
+use arrayref::array_ref;
+use solana_program::{account_info::AccountInfo, pubkey::Pubkey, rent::Rent, sysvar::Sysvar};
+
+use crate::account::program::{System, UpgradeAuthority};
+use crate::account::{Operator, OperatorRecord};
+use crate::error::Result;
+
+pub fn process<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    instruction: &[u8],
+) -> Result<()> {
+    log_msg!("Instruction: Add Operator to Registry");
+
+    let record = &accounts[0];
+    let _authority = UpgradeAuthority::from_account(program_id, &accounts[1], &accounts[2])?;
+    let payer = unsafe { Operator::from_account_not_whitelisted(&accounts[3]) }?;
+    let system = System::from_account(&accounts[4])?;
+
+    let operator = Pubkey::new_from_array(*array_ref![instruction, 0, 32]);
+    log_msg!("Operator: {}", operator);
+
+    let rent = Rent::get()?;
+    OperatorRecord::create(program_id, operator, record, &payer, &system, &rent)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::account::TestAccount;
+
+    // Successful registration creates the record through the system program,
+    // which is not available outside of the runtime
+    #[test]
+    fn test_add_requires_upgrade_authority() {
+        for (authorized, is_signer) in [(false, true), (true, false)] {
+            let operator = Pubkey::new_unique();
+            let (record_key, _) = OperatorRecord::address(&crate::ID, &operator);
+            let mut record = TestAccount::system(record_key);
+            let (mut program_data, mut authority) =
+                TestAccount::upgrade_authority(&crate::ID, authorized, is_signer);
+            let mut payer = TestAccount::operator(Pubkey::new_unique());
+            let mut system = TestAccount::system(solana_program::system_program::ID);
+            let accounts = [
+                record.info(),
+                program_data.info(),
+                authority.info(),
+                payer.info(),
+                system.info(),
+            ];
+
+            assert!(process(&crate::ID, &accounts, operator.as_ref()).is_err());
+            assert!(OperatorRecord::from_account(&crate::ID, &accounts[0]).is_err());
+        }
+    }
+}
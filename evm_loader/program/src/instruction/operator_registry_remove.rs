@@ -0,0 +1,102 @@
+use solana_program::{account_info::AccountInfo, pubkey::Pubkey};
+
+use crate::account::program::UpgradeAuthority;
+use crate::account::{is_whitelisted, Operator, OperatorRecord};
+use crate::error::Result;
+
+pub fn process<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    _instruction: &[u8],
+) -> Result<()> {
+    log_msg!("Instruction: Remove Operator from Registry");
+
+    let mut record = OperatorRecord::from_account(program_id, &accounts[0])?;
+    let _authority = UpgradeAuthority::from_account(program_id, &accounts[1], &accounts[2])?;
+    let receiver = unsafe { Operator::from_account_not_whitelisted(&accounts[3]) }?;
+
+    log_msg!("Operator: {}", record.operator());
+
+    // Whitelisted operators are accepted without the record
+    if is_whitelisted(&record.operator()) {
+        log_msg!("Operator is whitelisted, the record is kept suspended");
+        record.set_suspended(true);
+        return Ok(());
+    }
+
+    unsafe {
+        record.suicide(&receiver);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::account::TestAccount;
+
+    #[test]
+    fn test_remove() {
+        let mut record = TestAccount::record(&crate::ID, Pubkey::new_unique(), false);
+        let record_lamports = record.lamports;
+        let (mut program_data, mut authority) =
+            TestAccount::upgrade_authority(&crate::ID, true, true);
+        let mut receiver = TestAccount::operator(Pubkey::new_unique());
+        let receiver_lamports = receiver.lamports;
+        let accounts = [
+            record.info(),
+            program_data.info(),
+            authority.info(),
+            receiver.info(),
+        ];
+
+        process(&crate::ID, &accounts, &[]).unwrap();
+
+        assert_eq!(accounts[0].lamports(), 0);
+        assert!(OperatorRecord::from_account(&crate::ID, &accounts[0]).is_err());
+        assert_eq!(accounts[3].lamports(), receiver_lamports + record_lamports);
+    }
+
+    #[test]
+    fn test_remove_requires_upgrade_authority() {
+        for (authorized, is_signer) in [(false, true), (true, false)] {
+            let mut record = TestAccount::record(&crate::ID, Pubkey::new_unique(), false);
+            let (mut program_data, mut authority) =
+                TestAccount::upgrade_authority(&crate::ID, authorized, is_signer);
+            let mut receiver = TestAccount::operator(Pubkey::new_unique());
+            let accounts = [
+                record.info(),
+                program_data.info(),
+                authority.info(),
+                receiver.info(),
+            ];
+
+            assert!(process(&crate::ID, &accounts, &[]).is_err());
+            assert!(OperatorRecord::from_account(&crate::ID, &accounts[0]).is_ok());
+            assert!(accounts[0].lamports() > 0);
+        }
+    }
+
+    #[test]
+    fn test_remove_whitelisted() {
+        let operator = crate::config::AUTHORIZED_OPERATOR_LIST[0];
+        let mut record = TestAccount::record(&crate::ID, operator, false);
+        let record_lamports = record.lamports;
+        let (mut program_data, mut authority) =
+            TestAccount::upgrade_authority(&crate::ID, true, true);
+        let mut receiver = TestAccount::operator(Pubkey::new_unique());
+        let accounts = [
+            record.info(),
+            program_data.info(),
+            authority.info(),
+            receiver.info(),
+        ];
+
+        process(&crate::ID, &accounts, &[]).unwrap();
+
+        let record = OperatorRecord::from_account(&crate::ID, &accounts[0]).unwrap();
+        assert!(record.is_suspended());
+        assert_eq!(accounts[0].lamports(), record_lamports);
+    }
+}
@@ -0,0 +1,71 @@
+use solana_program::{account_info::AccountInfo, pubkey::Pubkey};
+
+use crate::account::program::UpgradeAuthority;
+use crate::account::OperatorRecord;
+use crate::error::{Error, Result};
+
+pub fn process<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    instruction: &[u8],
+) -> Result<()> {
+    log_msg!("Instruction: Suspend Operator");
+
+    let mut record = OperatorRecord::from_account(program_id, &accounts[0])?;
+    let _authority = UpgradeAuthority::from_account(program_id, &accounts[1], &accounts[2])?;
+
+    let suspended = match instruction.first() {
+        Some(0) => false,
+        Some(1) => true,
+        _ => return Err(Error::Custom("Expected suspended flag 0 or 1".to_string())),
+    };
+
+    log_msg!("Operator: {}, suspended: {}", record.operator(), suspended);
+    record.set_suspended(suspended);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::account::TestAccount;
+
+    #[test]
+    fn test_suspend() {
+        let mut record = TestAccount::record(&crate::ID, Pubkey::new_unique(), false);
+        let (mut program_data, mut authority) =
+            TestAccount::upgrade_authority(&crate::ID, true, true);
+        let accounts = [record.info(), program_data.info(), authority.info()];
+        let is_suspended = || {
+            OperatorRecord::from_account(&crate::ID, &accounts[0])
+                .unwrap()
+                .is_suspended()
+        };
+
+        process(&crate::ID, &accounts, &[1]).unwrap();
+        assert!(is_suspended());
+
+        process(&crate::ID, &accounts, &[0]).unwrap();
+        assert!(!is_suspended());
+
+        assert!(process(&crate::ID, &accounts, &[2]).is_err());
+        assert!(process(&crate::ID, &accounts, &[]).is_err());
+        assert!(!is_suspended());
+    }
+
+    #[test]
+    fn test_suspend_requires_upgrade_authority() {
+        for (authorized, is_signer) in [(false, true), (true, false)] {
+            let mut record = TestAccount::record(&crate::ID, Pubkey::new_unique(), false);
+            let (mut program_data, mut authority) =
+                TestAccount::upgrade_authority(&crate::ID, authorized, is_signer);
+            let accounts = [record.info(), program_data.info(), authority.info()];
+
+            assert!(process(&crate::ID, &accounts, &[1]).is_err());
+
+            let record = OperatorRecord::from_account(&crate::ID, &accounts[0]).unwrap();
+            assert!(!record.is_suspended());
+        }
+    }
+}
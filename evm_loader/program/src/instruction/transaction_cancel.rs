@@ -19,7 +19,7 @@ pub fn process<'a>(
     let transaction_hash = array_ref![instruction, 0, 32];
 
     let storage_info = accounts[0].clone();
-    let operator = Operator::from_account(&accounts[1])?;
+    let operator = Operator::from_account(program_id, &accounts[1], &accounts[3])?;
     let operator_balance = OperatorBalanceAccount::from_account(program_id, &accounts[2])?;
 
     operator_balance.validate_owner(&operator)?;
@@ -27,7 +27,7 @@ pub fn process<'a>(
     log_data(&[b"HASH", transaction_hash]);
     log_data(&[b"MINER", operator_balance.address().as_bytes()]);
 
-    let accounts_db = AccountsDB::new(&accounts[3..], operator, Some(operator_balance), None, None);
+    let accounts_db = AccountsDB::new(&accounts[4..], operator, Some(operator_balance), None, None);
     let (storage, _) = StateAccount::restore(program_id, storage_info, &accounts_db)?;
 
     validate(&storage, transaction_hash)?;
@@ -26,7 +26,7 @@ pub fn process<'a>(
 
     let storage_info = accounts[0].clone();
 
-    let operator = Operator::from_account(&accounts[1])?;
+    let operator = Operator::from_account(program_id, &accounts[1], &accounts[5])?;
     let treasury = Treasury::from_account(program_id, treasury_index, &accounts[2])?;
     let operator_balance = OperatorBalanceAccount::try_from_account(program_id, &accounts[3])?;
     let system = program::System::from_account(&accounts[4])?;
@@ -34,7 +34,7 @@ pub fn process<'a>(
     operator_balance.validate_owner(&operator)?;
 
     let accounts_db = AccountsDB::new(
-        &accounts[5..],
+        &accounts[6..],
         operator.clone(),
         operator_balance.clone(),
         Some(system),
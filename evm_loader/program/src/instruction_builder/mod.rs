@@ -0,0 +1,633 @@
+//! Builders of `EvmInstruction` Solana instructions.
+//!
+//! Account lists and data layouts follow the documentation of `EvmInstruction`.
+//! The module doesn't depend on the program entrypoint and can be used off-chain.
+
+use solana_program::{
+    bpf_loader_upgradeable,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_program,
+};
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+
+use crate::account::{MainTreasury, OperatorRecord, Treasury};
+use crate::config::DEPOSIT_AUTHORITY_SEED;
+use crate::types::Address;
+
+pub use parser::ParsedInstruction;
+
+mod parser;
+
+/// Tags of `EvmInstruction` (the first byte of the instruction data)
+pub mod tag {
+    pub const COLLECT_TREASURE: u8 = 0x1E;
+    pub const HOLDER_CREATE: u8 = 0x24;
+    pub const HOLDER_DELETE: u8 = 0x25;
+    pub const HOLDER_WRITE: u8 = 0x26;
+    pub const CREATE_MAIN_TREASURY: u8 = 0x29;
+
+    pub const ACCOUNT_CREATE_BALANCE: u8 = 0x30;
+    pub const DEPOSIT: u8 = 0x31;
+    pub const TRANSACTION_EXECUTE_FROM_INSTRUCTION: u8 = 0x32;
+    pub const TRANSACTION_EXECUTE_FROM_ACCOUNT: u8 = 0x33;
+    pub const TRANSACTION_STEP_FROM_INSTRUCTION: u8 = 0x34;
+    pub const TRANSACTION_STEP_FROM_ACCOUNT: u8 = 0x35;
+    pub const TRANSACTION_STEP_FROM_ACCOUNT_NO_CHAINID: u8 = 0x36;
+    pub const CANCEL: u8 = 0x37;
+    pub const TRANSACTION_EXECUTE_FROM_INSTRUCTION_WITH_SOLANA_CALL: u8 = 0x38;
+    pub const TRANSACTION_EXECUTE_FROM_ACCOUNT_WITH_SOLANA_CALL: u8 = 0x39;
+
+    pub const OPERATOR_BALANCE_CREATE: u8 = 0x3A;
+    pub const OPERATOR_BALANCE_DELETE: u8 = 0x3B;
+    pub const OPERATOR_BALANCE_WITHDRAW: u8 = 0x3C;
+    pub const OPERATOR_REGISTRY_ADD: u8 = 0x3D;
+    pub const OPERATOR_REGISTRY_REMOVE: u8 = 0x3E;
+    pub const OPERATOR_REGISTRY_SUSPEND: u8 = 0x3F;
+
+    pub const CONFIG_GET_CHAIN_COUNT: u8 = 0xA0;
+    pub const CONFIG_GET_CHAIN_INFO: u8 = 0xA1;
+    pub const CONFIG_GET_ENVIRONMENT: u8 = 0xA2;
+    pub const CONFIG_GET_PROPERTY_COUNT: u8 = 0xA3;
+    pub const CONFIG_GET_PROPERTY_BY_INDEX: u8 = 0xA4;
+    pub const CONFIG_GET_PROPERTY_BY_NAME: u8 = 0xA5;
+    pub const CONFIG_GET_STATUS: u8 = 0xA6;
+    pub const CONFIG_GET_VERSION: u8 = 0xA7;
+    pub const CONFIG_GET_OPERATOR_STATUS: u8 = 0xA8;
+}
+
+/// Accounts of the instructions executing a Neon transaction
+#[derive(Debug, Clone)]
+pub struct ExecuteAccounts<'a> {
+    pub operator: Pubkey,
+    /// Operator balance account, or any system account if the operator doesn't have one
+    pub operator_balance: Pubkey,
+    pub treasury_index: u32,
+    /// Accounts used by the transaction
+    pub accounts: &'a [AccountMeta],
+}
+
+impl<'a> ExecuteAccounts<'a> {
+    fn metas(&self, program_id: &Pubkey, holder: Option<Pubkey>) -> Vec<AccountMeta> {
+        let mut metas = self.fixed_metas(program_id, holder);
+        metas.extend_from_slice(self.accounts);
+
+        metas
+    }
+
+    /// Step instructions take the operator record after the fixed accounts
+    fn metas_with_record(&self, program_id: &Pubkey, holder: Pubkey) -> Vec<AccountMeta> {
+        let (record, _) = OperatorRecord::address(program_id, &self.operator);
+
+        let mut metas = self.fixed_metas(program_id, Some(holder));
+        metas.push(AccountMeta::new_readonly(record, false));
+        metas.extend_from_slice(self.accounts);
+
+        metas
+    }
+
+    fn fixed_metas(&self, program_id: &Pubkey, holder: Option<Pubkey>) -> Vec<AccountMeta> {
+        let (treasury, _) = Treasury::address(program_id, self.treasury_index);
+
+        let mut metas = Vec::with_capacity(self.accounts.len() + 6);
+        if let Some(holder) = holder {
+            metas.push(AccountMeta::new(holder, false));
+        }
+        metas.extend([
+            AccountMeta::new(self.operator, true),
+            AccountMeta::new(treasury, false),
+            AccountMeta::new(self.operator_balance, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ]);
+
+        metas
+    }
+}
+
+fn data(tag: u8, parts: &[&[u8]]) -> Vec<u8> {
+    let mut data = vec![tag];
+    for part in parts {
+        data.extend_from_slice(part);
+    }
+
+    data
+}
+
+#[must_use]
+pub fn holder_address(program_id: &Pubkey, owner: &Pubkey, seed: &str) -> Pubkey {
+    Pubkey::create_with_seed(owner, seed, program_id).expect("seed is not longer than 32 bytes")
+}
+
+#[must_use]
+pub fn collect_treasury(program_id: &Pubkey, treasury_index: u32) -> Instruction {
+    let (main_treasury, _) = MainTreasury::address(program_id);
+    let (treasury, _) = Treasury::address(program_id, treasury_index);
+
+    Instruction::new_with_bytes(
+        *program_id,
+        &data(tag::COLLECT_TREASURE, &[&treasury_index.to_le_bytes()]),
+        vec![
+            AccountMeta::new(main_treasury, false),
+            AccountMeta::new(treasury, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+#[must_use]
+pub fn create_main_treasury(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    payer: &Pubkey,
+) -> Instruction {
+    let (main_treasury, _) = MainTreasury::address(program_id);
+    let program_data = bpf_loader_upgradeable::get_program_data_address(program_id);
+
+    Instruction::new_with_bytes(
+        *program_id,
+        &[tag::CREATE_MAIN_TREASURY],
+        vec![
+            AccountMeta::new(main_treasury, false),
+            AccountMeta::new_readonly(program_data, false),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(spl_token::native_mint::id(), false),
+            AccountMeta::new(*payer, true),
+        ],
+    )
+}
+
+#[must_use]
+pub fn holder_create(
+    program_id: &Pubkey,
+    holder: &Pubkey,
+    owner: &Pubkey,
+    seed: &str,
+) -> Instruction {
+    let seed_len = seed.len() as u64;
+
+    Instruction::new_with_bytes(
+        *program_id,
+        &data(
+            tag::HOLDER_CREATE,
+            &[&seed_len.to_le_bytes(), seed.as_bytes()],
+        ),
+        vec![
+            AccountMeta::new(*holder, false),
+            AccountMeta::new_readonly(*owner, true),
+        ],
+    )
+}
+
+#[must_use]
+pub fn holder_delete(program_id: &Pubkey, holder: &Pubkey, owner: &Pubkey) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &[tag::HOLDER_DELETE],
+        vec![
+            AccountMeta::new(*holder, false),
+            AccountMeta::new(*owner, true),
+        ],
+    )
+}
+
+#[must_use]
+pub fn holder_write(
+    program_id: &Pubkey,
+    holder: &Pubkey,
+    owner: &Pubkey,
+    transaction_hash: &[u8; 32],
+    offset: usize,
+    chunk: &[u8],
+) -> Instruction {
+    let offset = offset as u64;
+
+    Instruction::new_with_bytes(
+        *program_id,
+        &data(
+            tag::HOLDER_WRITE,
+            &[transaction_hash, &offset.to_le_bytes(), chunk],
+        ),
+        vec![
+            AccountMeta::new(*holder, false),
+            AccountMeta::new_readonly(*owner, true),
+        ],
+    )
+}
+
+#[must_use]
+pub fn account_create_balance(
+    program_id: &Pubkey,
+    operator: &Pubkey,
+    address: Address,
+    chain_id: u64,
+) -> Instruction {
+    let (balance, _) = address.find_balance_address(program_id, chain_id);
+    let (contract, _) = address.find_solana_address(program_id);
+
+    Instruction::new_with_bytes(
+        *program_id,
+        &data(
+            tag::ACCOUNT_CREATE_BALANCE,
+            &[address.as_bytes(), &chain_id.to_le_bytes()],
+        ),
+        vec![
+            AccountMeta::new(*operator, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new(balance, false),
+            AccountMeta::new(contract, false),
+        ],
+    )
+}
+
+/// Deposits tokens of the chain `mint` from the `source` token account.
+/// The deposit amount must be delegated to the balance account of `address` beforehand.
+/// `token_program` is the owner of the mint, accounts of the Token-2022 transfer hook
+/// have to be appended by the caller.
+#[must_use]
+pub fn deposit(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    source: &Pubkey,
+    address: Address,
+    chain_id: u64,
+    payer: &Pubkey,
+) -> Instruction {
+    let (authority, _) =
+        Pubkey::find_program_address(&[DEPOSIT_AUTHORITY_SEED.as_bytes()], program_id);
+    let pool = get_associated_token_address_with_program_id(&authority, mint, token_program);
+    let (balance, _) = address.find_balance_address(program_id, chain_id);
+    let (contract, _) = address.find_solana_address(program_id);
+
+    Instruction::new_with_bytes(
+        *program_id,
+        &data(tag::DEPOSIT, &[address.as_bytes(), &chain_id.to_le_bytes()]),
+        vec![
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(*source, false),
+            AccountMeta::new(pool, false),
+            AccountMeta::new(balance, false),
+            AccountMeta::new(contract, false),
+            AccountMeta::new_readonly(*token_program, false),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+#[must_use]
+pub fn transaction_execute_from_instruction(
+    program_id: &Pubkey,
+    accounts: &ExecuteAccounts,
+    transaction: &[u8],
+) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &data(
+            tag::TRANSACTION_EXECUTE_FROM_INSTRUCTION,
+            &[&accounts.treasury_index.to_le_bytes(), transaction],
+        ),
+        accounts.metas(program_id, None),
+    )
+}
+
+#[must_use]
+pub fn transaction_execute_from_account(
+    program_id: &Pubkey,
+    holder: &Pubkey,
+    accounts: &ExecuteAccounts,
+) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &data(
+            tag::TRANSACTION_EXECUTE_FROM_ACCOUNT,
+            &[&accounts.treasury_index.to_le_bytes()],
+        ),
+        accounts.metas(program_id, Some(*holder)),
+    )
+}
+
+#[must_use]
+pub fn transaction_execute_from_instruction_with_solana_call(
+    program_id: &Pubkey,
+    accounts: &ExecuteAccounts,
+    transaction: &[u8],
+) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &data(
+            tag::TRANSACTION_EXECUTE_FROM_INSTRUCTION_WITH_SOLANA_CALL,
+            &[&accounts.treasury_index.to_le_bytes(), transaction],
+        ),
+        accounts.metas(program_id, None),
+    )
+}
+
+#[must_use]
+pub fn transaction_execute_from_account_with_solana_call(
+    program_id: &Pubkey,
+    holder: &Pubkey,
+    accounts: &ExecuteAccounts,
+) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &data(
+            tag::TRANSACTION_EXECUTE_FROM_ACCOUNT_WITH_SOLANA_CALL,
+            &[&accounts.treasury_index.to_le_bytes()],
+        ),
+        accounts.metas(program_id, Some(*holder)),
+    )
+}
+
+/// Iteration of the transaction passed in the instruction data.
+/// `unique_index` makes every iteration a distinct Solana transaction.
+#[must_use]
+pub fn transaction_step_from_instruction(
+    program_id: &Pubkey,
+    holder: &Pubkey,
+    accounts: &ExecuteAccounts,
+    step_count: u32,
+    unique_index: u32,
+    transaction: &[u8],
+) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &data(
+            tag::TRANSACTION_STEP_FROM_INSTRUCTION,
+            &[
+                &accounts.treasury_index.to_le_bytes(),
+                &step_count.to_le_bytes(),
+                &unique_index.to_le_bytes(),
+                transaction,
+            ],
+        ),
+        accounts.metas_with_record(program_id, *holder),
+    )
+}
+
+/// Iteration of the transaction stored in the `holder`.
+/// `unique_index` makes every iteration a distinct Solana transaction.
+#[must_use]
+pub fn transaction_step_from_account(
+    program_id: &Pubkey,
+    holder: &Pubkey,
+    accounts: &ExecuteAccounts,
+    step_count: u32,
+    unique_index: u32,
+) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &data(
+            tag::TRANSACTION_STEP_FROM_ACCOUNT,
+            &[
+                &accounts.treasury_index.to_le_bytes(),
+                &step_count.to_le_bytes(),
+                &unique_index.to_le_bytes(),
+            ],
+        ),
+        accounts.metas_with_record(program_id, *holder),
+    )
+}
+
+#[must_use]
+pub fn transaction_step_from_account_no_chain_id(
+    program_id: &Pubkey,
+    holder: &Pubkey,
+    accounts: &ExecuteAccounts,
+    step_count: u32,
+    unique_index: u32,
+) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &data(
+            tag::TRANSACTION_STEP_FROM_ACCOUNT_NO_CHAINID,
+            &[
+                &accounts.treasury_index.to_le_bytes(),
+                &step_count.to_le_bytes(),
+                &unique_index.to_le_bytes(),
+            ],
+        ),
+        accounts.metas_with_record(program_id, *holder),
+    )
+}
+
+/// Cancels the transaction in the `storage`.
+/// `accounts` must contain the accounts locked by the transaction.
+#[must_use]
+pub fn cancel(
+    program_id: &Pubkey,
+    storage: &Pubkey,
+    operator: &Pubkey,
+    operator_balance: &Pubkey,
+    transaction_hash: &[u8; 32],
+    accounts: &[AccountMeta],
+) -> Instruction {
+    let (record, _) = OperatorRecord::address(program_id, operator);
+
+    let mut metas = Vec::with_capacity(accounts.len() + 4);
+    metas.extend([
+        AccountMeta::new(*storage, false),
+        AccountMeta::new_readonly(*operator, true),
+        AccountMeta::new(*operator_balance, false),
+        AccountMeta::new_readonly(record, false),
+    ]);
+    metas.extend_from_slice(accounts);
+
+    Instruction::new_with_bytes(*program_id, &data(tag::CANCEL, &[transaction_hash]), metas)
+}
+
+#[must_use]
+pub fn operator_balance_create(
+    program_id: &Pubkey,
+    operator: &Pubkey,
+    address: Address,
+    chain_id: u64,
+) -> Instruction {
+    let (operator_balance, _) = address.find_operator_address(program_id, chain_id, operator);
+
+    Instruction::new_with_bytes(
+        *program_id,
+        &data(
+            tag::OPERATOR_BALANCE_CREATE,
+            &[address.as_bytes(), &chain_id.to_le_bytes()],
+        ),
+        vec![
+            AccountMeta::new(*operator, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new(operator_balance, false),
+        ],
+    )
+}
+
+#[must_use]
+pub fn operator_balance_delete(
+    program_id: &Pubkey,
+    operator: &Pubkey,
+    address: Address,
+    chain_id: u64,
+) -> Instruction {
+    let (operator_balance, _) = address.find_operator_address(program_id, chain_id, operator);
+
+    Instruction::new_with_bytes(
+        *program_id,
+        &[tag::OPERATOR_BALANCE_DELETE],
+        vec![
+            AccountMeta::new(*operator, true),
+            AccountMeta::new(operator_balance, false),
+        ],
+    )
+}
+
+/// Moves the operator balance to the user balance account of the same address and chain
+#[must_use]
+pub fn operator_balance_withdraw(
+    program_id: &Pubkey,
+    operator: &Pubkey,
+    address: Address,
+    chain_id: u64,
+) -> Instruction {
+    let (operator_balance, _) = address.find_operator_address(program_id, chain_id, operator);
+    let (balance, _) = address.find_balance_address(program_id, chain_id);
+
+    Instruction::new_with_bytes(
+        *program_id,
+        &[tag::OPERATOR_BALANCE_WITHDRAW],
+        vec![
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new(*operator, true),
+            AccountMeta::new(operator_balance, false),
+            AccountMeta::new(balance, false),
+        ],
+    )
+}
+
+#[must_use]
+pub fn operator_registry_add(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    payer: &Pubkey,
+    operator: &Pubkey,
+) -> Instruction {
+    let (record, _) = OperatorRecord::address(program_id, operator);
+    let program_data = bpf_loader_upgradeable::get_program_data_address(program_id);
+
+    Instruction::new_with_bytes(
+        *program_id,
+        &data(tag::OPERATOR_REGISTRY_ADD, &[operator.as_ref()]),
+        vec![
+            AccountMeta::new(record, false),
+            AccountMeta::new_readonly(program_data, false),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+#[must_use]
+pub fn operator_registry_remove(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    receiver: &Pubkey,
+    operator: &Pubkey,
+) -> Instruction {
+    let (record, _) = OperatorRecord::address(program_id, operator);
+    let program_data = bpf_loader_upgradeable::get_program_data_address(program_id);
+
+    Instruction::new_with_bytes(
+        *program_id,
+        &[tag::OPERATOR_REGISTRY_REMOVE],
+        vec![
+            AccountMeta::new(record, false),
+            AccountMeta::new_readonly(program_data, false),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(*receiver, true),
+        ],
+    )
+}
+
+#[must_use]
+pub fn operator_registry_suspend(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    operator: &Pubkey,
+    suspended: bool,
+) -> Instruction {
+    let (record, _) = OperatorRecord::address(program_id, operator);
+    let program_data = bpf_loader_upgradeable::get_program_data_address(program_id);
+
+    Instruction::new_with_bytes(
+        *program_id,
+        &[tag::OPERATOR_REGISTRY_SUSPEND, u8::from(suspended)],
+        vec![
+            AccountMeta::new(record, false),
+            AccountMeta::new_readonly(program_data, false),
+            AccountMeta::new_readonly(*authority, true),
+        ],
+    )
+}
+
+#[must_use]
+pub fn config_get_chain_count(program_id: &Pubkey) -> Instruction {
+    Instruction::new_with_bytes(*program_id, &[tag::CONFIG_GET_CHAIN_COUNT], vec![])
+}
+
+#[must_use]
+pub fn config_get_chain_info(program_id: &Pubkey, index: usize) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &data(tag::CONFIG_GET_CHAIN_INFO, &[&index.to_le_bytes()]),
+        vec![],
+    )
+}
+
+#[must_use]
+pub fn config_get_environment(program_id: &Pubkey) -> Instruction {
+    Instruction::new_with_bytes(*program_id, &[tag::CONFIG_GET_ENVIRONMENT], vec![])
+}
+
+#[must_use]
+pub fn config_get_property_count(program_id: &Pubkey) -> Instruction {
+    Instruction::new_with_bytes(*program_id, &[tag::CONFIG_GET_PROPERTY_COUNT], vec![])
+}
+
+#[must_use]
+pub fn config_get_property_by_index(program_id: &Pubkey, index: usize) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &data(tag::CONFIG_GET_PROPERTY_BY_INDEX, &[&index.to_le_bytes()]),
+        vec![],
+    )
+}
+
+#[must_use]
+pub fn config_get_property_by_name(program_id: &Pubkey, name: &str) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &data(tag::CONFIG_GET_PROPERTY_BY_NAME, &[name.as_bytes()]),
+        vec![],
+    )
+}
+
+#[must_use]
+pub fn config_get_status(program_id: &Pubkey) -> Instruction {
+    Instruction::new_with_bytes(*program_id, &[tag::CONFIG_GET_STATUS], vec![])
+}
+
+#[must_use]
+pub fn config_get_version(program_id: &Pubkey) -> Instruction {
+    Instruction::new_with_bytes(*program_id, &[tag::CONFIG_GET_VERSION], vec![])
+}
+
+#[must_use]
+pub fn config_get_operator_status(program_id: &Pubkey, operator: &Pubkey) -> Instruction {
+    let (record, _) = OperatorRecord::address(program_id, operator);
+
+    Instruction::new_with_bytes(
+        *program_id,
+        &data(tag::CONFIG_GET_OPERATOR_STATUS, &[operator.as_ref()]),
+        vec![AccountMeta::new_readonly(record, false)],
+    )
+}
@@ -0,0 +1,406 @@
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+use super::tag;
+use crate::types::Address;
+
+/// `EvmInstruction` with the decoded instruction data
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ParsedInstruction<'a> {
+    CollectTreasure {
+        treasury_index: u32,
+    },
+    HolderCreate {
+        seed: &'a str,
+    },
+    HolderDelete,
+    HolderWrite {
+        transaction_hash: [u8; 32],
+        offset: usize,
+        data: &'a [u8],
+    },
+    CreateMainTreasury,
+    AccountCreateBalance {
+        address: Address,
+        chain_id: u64,
+    },
+    Deposit {
+        address: Address,
+        chain_id: u64,
+    },
+    TransactionExecuteFromInstruction {
+        treasury_index: u32,
+        transaction: &'a [u8],
+    },
+    TransactionExecuteFromAccount {
+        treasury_index: u32,
+    },
+    TransactionStepFromInstruction {
+        treasury_index: u32,
+        step_count: u32,
+        unique_index: u32,
+        transaction: &'a [u8],
+    },
+    TransactionStepFromAccount {
+        treasury_index: u32,
+        step_count: u32,
+        unique_index: u32,
+    },
+    TransactionStepFromAccountNoChainId {
+        treasury_index: u32,
+        step_count: u32,
+        unique_index: u32,
+    },
+    Cancel {
+        transaction_hash: [u8; 32],
+    },
+    TransactionExecuteFromInstructionWithSolanaCall {
+        treasury_index: u32,
+        transaction: &'a [u8],
+    },
+    TransactionExecuteFromAccountWithSolanaCall {
+        treasury_index: u32,
+    },
+    OperatorBalanceCreate {
+        address: Address,
+        chain_id: u64,
+    },
+    OperatorBalanceDelete,
+    OperatorBalanceWithdraw,
+    OperatorRegistryAdd {
+        operator: Pubkey,
+    },
+    OperatorRegistryRemove,
+    OperatorRegistrySuspend {
+        suspended: bool,
+    },
+    ConfigGetChainCount,
+    ConfigGetChainInfo {
+        index: usize,
+    },
+    ConfigGetEnvironment,
+    ConfigGetPropertyCount,
+    ConfigGetPropertyByIndex {
+        index: usize,
+    },
+    ConfigGetPropertyByName {
+        name: &'a str,
+    },
+    ConfigGetStatus,
+    ConfigGetVersion,
+    ConfigGetOperatorStatus {
+        operator: Pubkey,
+    },
+}
+
+fn array<const N: usize>(data: &[u8], offset: usize) -> Result<[u8; N], ProgramError> {
+    data.get(offset..offset + N)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or(ProgramError::InvalidInstructionData)
+}
+
+fn tail(data: &[u8], offset: usize) -> Result<&[u8], ProgramError> {
+    data.get(offset..)
+        .ok_or(ProgramError::InvalidInstructionData)
+}
+
+fn utf8(data: &[u8]) -> Result<&str, ProgramError> {
+    std::str::from_utf8(data).map_err(|_| ProgramError::InvalidInstructionData)
+}
+
+fn u32_at(data: &[u8], offset: usize) -> Result<u32, ProgramError> {
+    array(data, offset).map(u32::from_le_bytes)
+}
+
+fn u64_at(data: &[u8], offset: usize) -> Result<u64, ProgramError> {
+    array(data, offset).map(u64::from_le_bytes)
+}
+
+fn usize_at(data: &[u8], offset: usize) -> Result<usize, ProgramError> {
+    array(data, offset).map(usize::from_le_bytes)
+}
+
+impl<'a> ParsedInstruction<'a> {
+    /// Decode the instruction data of the `evm_loader` program
+    ///
+    /// # Errors
+    /// Will return `ProgramError::InvalidInstructionData` if the tag is unknown
+    /// or the data is shorter than the instruction layout
+    pub fn parse(instruction: &'a [u8]) -> Result<Self, ProgramError> {
+        let (tag, data) = instruction
+            .split_first()
+            .ok_or(ProgramError::InvalidInstructionData)?;
+
+        Ok(match *tag {
+            tag::COLLECT_TREASURE => Self::CollectTreasure {
+                treasury_index: u32_at(data, 0)?,
+            },
+            tag::HOLDER_CREATE => {
+                let seed_len = usize_at(data, 0)?;
+                let seed = data
+                    .get(8..8_usize.saturating_add(seed_len))
+                    .ok_or(ProgramError::InvalidInstructionData)?;
+
+                Self::HolderCreate { seed: utf8(seed)? }
+            }
+            tag::HOLDER_DELETE => Self::HolderDelete,
+            tag::HOLDER_WRITE => Self::HolderWrite {
+                transaction_hash: array(data, 0)?,
+                offset: usize_at(data, 32)?,
+                data: tail(data, 40)?,
+            },
+            tag::CREATE_MAIN_TREASURY => Self::CreateMainTreasury,
+            tag::ACCOUNT_CREATE_BALANCE => Self::AccountCreateBalance {
+                address: Address::from(array::<20>(data, 0)?),
+                chain_id: u64_at(data, 20)?,
+            },
+            tag::DEPOSIT => Self::Deposit {
+                address: Address::from(array::<20>(data, 0)?),
+                chain_id: u64_at(data, 20)?,
+            },
+            tag::TRANSACTION_EXECUTE_FROM_INSTRUCTION => Self::TransactionExecuteFromInstruction {
+                treasury_index: u32_at(data, 0)?,
+                transaction: tail(data, 4)?,
+            },
+            tag::TRANSACTION_EXECUTE_FROM_ACCOUNT => Self::TransactionExecuteFromAccount {
+                treasury_index: u32_at(data, 0)?,
+            },
+            tag::TRANSACTION_STEP_FROM_INSTRUCTION => Self::TransactionStepFromInstruction {
+                treasury_index: u32_at(data, 0)?,
+                step_count: u32_at(data, 4)?,
+                unique_index: u32_at(data, 8)?,
+                transaction: tail(data, 12)?,
+            },
+            tag::TRANSACTION_STEP_FROM_ACCOUNT => Self::TransactionStepFromAccount {
+                treasury_index: u32_at(data, 0)?,
+                step_count: u32_at(data, 4)?,
+                unique_index: u32_at(data, 8)?,
+            },
+            tag::TRANSACTION_STEP_FROM_ACCOUNT_NO_CHAINID => {
+                Self::TransactionStepFromAccountNoChainId {
+                    treasury_index: u32_at(data, 0)?,
+                    step_count: u32_at(data, 4)?,
+                    unique_index: u32_at(data, 8)?,
+                }
+            }
+            tag::CANCEL => Self::Cancel {
+                transaction_hash: array(data, 0)?,
+            },
+            tag::TRANSACTION_EXECUTE_FROM_INSTRUCTION_WITH_SOLANA_CALL => {
+                Self::TransactionExecuteFromInstructionWithSolanaCall {
+                    treasury_index: u32_at(data, 0)?,
+                    transaction: tail(data, 4)?,
+                }
+            }
+            tag::TRANSACTION_EXECUTE_FROM_ACCOUNT_WITH_SOLANA_CALL => {
+                Self::TransactionExecuteFromAccountWithSolanaCall {
+                    treasury_index: u32_at(data, 0)?,
+                }
+            }
+            tag::OPERATOR_BALANCE_CREATE => Self::OperatorBalanceCreate {
+                address: Address::from(array::<20>(data, 0)?),
+                chain_id: u64_at(data, 20)?,
+            },
+            tag::OPERATOR_BALANCE_DELETE => Self::OperatorBalanceDelete,
+            tag::OPERATOR_BALANCE_WITHDRAW => Self::OperatorBalanceWithdraw,
+            tag::OPERATOR_REGISTRY_ADD => Self::OperatorRegistryAdd {
+                operator: Pubkey::new_from_array(array(data, 0)?),
+            },
+            tag::OPERATOR_REGISTRY_REMOVE => Self::OperatorRegistryRemove,
+            tag::OPERATOR_REGISTRY_SUSPEND => match array::<1>(data, 0)? {
+                [0] => Self::OperatorRegistrySuspend { suspended: false },
+                [1] => Self::OperatorRegistrySuspend { suspended: true },
+                _ => return Err(ProgramError::InvalidInstructionData),
+            },
+            tag::CONFIG_GET_CHAIN_COUNT => Self::ConfigGetChainCount,
+            tag::CONFIG_GET_CHAIN_INFO => Self::ConfigGetChainInfo {
+                index: usize_at(data, 0)?,
+            },
+            tag::CONFIG_GET_ENVIRONMENT => Self::ConfigGetEnvironment,
+            tag::CONFIG_GET_PROPERTY_COUNT => Self::ConfigGetPropertyCount,
+            tag::CONFIG_GET_PROPERTY_BY_INDEX => Self::ConfigGetPropertyByIndex {
+                index: usize_at(data, 0)?,
+            },
+            tag::CONFIG_GET_PROPERTY_BY_NAME => Self::ConfigGetPropertyByName { name: utf8(data)? },
+            tag::CONFIG_GET_STATUS => Self::ConfigGetStatus,
+            tag::CONFIG_GET_VERSION => Self::ConfigGetVersion,
+            tag::CONFIG_GET_OPERATOR_STATUS => Self::ConfigGetOperatorStatus {
+                operator: Pubkey::new_from_array(array(data, 0)?),
+            },
+            _ => return Err(ProgramError::InvalidInstructionData),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_program::instruction::AccountMeta;
+
+    use super::super::*;
+
+    #[test]
+    fn test_parse_builders() {
+        let program_id = Pubkey::new_unique();
+        let operator = Pubkey::new_unique();
+        let holder = Pubkey::new_unique();
+        let address = Address::from([0x11; 20]);
+        let hash = [0x22; 32];
+        let touched = [AccountMeta::new(Pubkey::new_unique(), false)];
+        let accounts = ExecuteAccounts {
+            operator,
+            operator_balance: Pubkey::new_unique(),
+            treasury_index: 7,
+            accounts: &touched,
+        };
+
+        let cases = [
+            (
+                holder_create(&program_id, &holder, &operator, "holder"),
+                ParsedInstruction::HolderCreate { seed: "holder" },
+            ),
+            (
+                holder_write(&program_id, &holder, &operator, &hash, 16, &[1, 2, 3]),
+                ParsedInstruction::HolderWrite {
+                    transaction_hash: hash,
+                    offset: 16,
+                    data: &[1, 2, 3],
+                },
+            ),
+            (
+                deposit(
+                    &program_id,
+                    &Pubkey::new_unique(),
+                    &spl_token_2022::id(),
+                    &Pubkey::new_unique(),
+                    address,
+                    245_022_934,
+                    &operator,
+                ),
+                ParsedInstruction::Deposit {
+                    address,
+                    chain_id: 245_022_934,
+                },
+            ),
+            (
+                transaction_step_from_instruction(&program_id, &holder, &accounts, 500, 3, &[4, 5]),
+                ParsedInstruction::TransactionStepFromInstruction {
+                    treasury_index: 7,
+                    step_count: 500,
+                    unique_index: 3,
+                    transaction: &[4, 5],
+                },
+            ),
+            (
+                transaction_step_from_account(&program_id, &holder, &accounts, 500, 3),
+                ParsedInstruction::TransactionStepFromAccount {
+                    treasury_index: 7,
+                    step_count: 500,
+                    unique_index: 3,
+                },
+            ),
+            (
+                transaction_step_from_account_no_chain_id(&program_id, &holder, &accounts, 500, 4),
+                ParsedInstruction::TransactionStepFromAccountNoChainId {
+                    treasury_index: 7,
+                    step_count: 500,
+                    unique_index: 4,
+                },
+            ),
+            (
+                cancel(&program_id, &holder, &operator, &holder, &hash, &touched),
+                ParsedInstruction::Cancel {
+                    transaction_hash: hash,
+                },
+            ),
+            (
+                operator_registry_suspend(&program_id, &operator, &operator, true),
+                ParsedInstruction::OperatorRegistrySuspend { suspended: true },
+            ),
+            (
+                config_get_property_by_name(&program_id, "NEON_TOKEN_MINT"),
+                ParsedInstruction::ConfigGetPropertyByName {
+                    name: "NEON_TOKEN_MINT",
+                },
+            ),
+        ];
+
+        for (instruction, expected) in &cases {
+            assert_eq!(instruction.program_id, program_id);
+            assert_eq!(
+                ParsedInstruction::parse(&instruction.data),
+                Ok(expected.clone())
+            );
+        }
+    }
+
+    #[test]
+    fn test_step_accounts() {
+        let program_id = Pubkey::new_unique();
+        let operator = Pubkey::new_unique();
+        let holder = Pubkey::new_unique();
+        let touched = [AccountMeta::new_readonly(Pubkey::new_unique(), false)];
+        let accounts = ExecuteAccounts {
+            operator,
+            operator_balance: Pubkey::new_unique(),
+            treasury_index: 0,
+            accounts: &touched,
+        };
+
+        let instruction = transaction_step_from_account(&program_id, &holder, &accounts, 1, 0);
+        let keys: Vec<Pubkey> = instruction.accounts.iter().map(|a| a.pubkey).collect();
+
+        assert_eq!(
+            keys,
+            vec![
+                holder,
+                operator,
+                Treasury::address(&program_id, 0).0,
+                accounts.operator_balance,
+                system_program::id(),
+                OperatorRecord::address(&program_id, &operator).0,
+                touched[0].pubkey,
+            ]
+        );
+        assert!(instruction.accounts[1].is_signer);
+
+        let instruction = cancel(
+            &program_id,
+            &holder,
+            &operator,
+            &accounts.operator_balance,
+            &[0; 32],
+            &touched,
+        );
+        let keys: Vec<Pubkey> = instruction.accounts.iter().map(|a| a.pubkey).collect();
+
+        assert_eq!(
+            keys,
+            vec![
+                holder,
+                operator,
+                accounts.operator_balance,
+                OperatorRecord::address(&program_id, &operator).0,
+                touched[0].pubkey,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert_eq!(
+            ParsedInstruction::parse(&[]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+        assert_eq!(
+            ParsedInstruction::parse(&[0xFF]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+        assert_eq!(
+            ParsedInstruction::parse(&[tag::CANCEL, 1, 2, 3]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+        assert_eq!(
+            ParsedInstruction::parse(&[tag::OPERATOR_REGISTRY_SUSPEND, 2]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+    }
+}
@@ -29,6 +29,7 @@ pub mod external_programs;
 pub mod gasometer;
 #[cfg(target_os = "solana")]
 pub mod instruction;
+pub mod instruction_builder;
 pub mod types;
 
 // Export current solana-sdk types for downstream users who may also be building with a different
@@ -6,7 +6,7 @@ use std::convert::{From, TryInto};
 use std::fmt::{Debug, Display};
 use std::str::FromStr;
 
-use crate::account::{Operator, ACCOUNT_SEED_VERSION};
+use crate::account::ACCOUNT_SEED_VERSION;
 use crate::error::Error;
 
 #[repr(transparent)]
@@ -72,13 +72,13 @@ impl Address {
         &self,
         program_id: &Pubkey,
         chain_id: u64,
-        operator: &Operator,
+        operator: &Pubkey,
     ) -> (Pubkey, u8) {
         let chain_id = U256::from(chain_id);
 
         let seeds: &[&[u8]] = &[
             &[ACCOUNT_SEED_VERSION],
-            operator.key.as_ref(),
+            operator.as_ref(),
             &self.0,
             &chain_id.to_be_bytes(),
         ];
@@ -6,13 +6,15 @@ use jsonrpsee_http_client::{HttpClient, HttpClientBuilder};
 use neon_lib::LibMethod;
 use neon_lib::{
     commands::{
-        emulate::EmulateResponse, get_balance::GetBalanceResponse, get_config::GetConfigResponse,
-        get_contract::GetContractResponse, get_holder::GetHolderResponse,
-        get_storage_at::GetStorageAtReturn,
+        emulate::EmulateResponse, emulate_batch::EmulateBatchResponse,
+        estimate_gas::EstimateGasResponse, get_balance::GetBalanceResponse,
+        get_config::GetConfigResponse, get_contract::GetContractResponse,
+        get_holder::GetHolderResponse, get_storage_at::GetStorageAtReturn,
+        simulate_solana::SimulateSolanaResponse,
     },
     types::{
-        EmulateApiRequest, GetBalanceRequest, GetContractRequest, GetHolderRequest,
-        GetStorageAtRequest,
+        EmulateApiRequest, EmulateBatchApiRequest, EstimateGasApiRequest, GetBalanceRequest,
+        GetContractRequest, GetHolderRequest, GetStorageAtRequest, SimulateSolanaRequest,
     },
 };
 use serde::de::DeserializeOwned;
@@ -58,6 +60,20 @@ impl NeonRpcClient for NeonRpcHttpClient {
         self.request(LibMethod::Emulate, params).await
     }
 
+    async fn emulate_batch(
+        &self,
+        params: EmulateBatchApiRequest,
+    ) -> NeonRpcClientResult<EmulateBatchResponse> {
+        self.request(LibMethod::EmulateBatch, params).await
+    }
+
+    async fn estimate_gas(
+        &self,
+        params: EstimateGasApiRequest,
+    ) -> NeonRpcClientResult<EstimateGasResponse> {
+        self.request(LibMethod::EstimateGas, params).await
+    }
+
     async fn balance(
         &self,
         params: GetBalanceRequest,
@@ -90,6 +106,13 @@ impl NeonRpcClient for NeonRpcHttpClient {
     async fn trace(&self, params: EmulateApiRequest) -> NeonRpcClientResult<serde_json::Value> {
         self.request(LibMethod::Trace, params).await
     }
+
+    async fn simulate_solana(
+        &self,
+        params: SimulateSolanaRequest,
+    ) -> NeonRpcClientResult<SimulateSolanaResponse> {
+        self.request(LibMethod::SimulateSolana, params).await
+    }
 }
 
 impl NeonRpcHttpClient {
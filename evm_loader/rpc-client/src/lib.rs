@@ -11,13 +11,15 @@ pub use error::NeonRpcClientError;
 use async_trait::async_trait;
 use neon_lib::{
     commands::{
-        emulate::EmulateResponse, get_balance::GetBalanceResponse, get_config::GetConfigResponse,
-        get_contract::GetContractResponse, get_holder::GetHolderResponse,
-        get_storage_at::GetStorageAtReturn,
+        emulate::EmulateResponse, emulate_batch::EmulateBatchResponse,
+        estimate_gas::EstimateGasResponse, get_balance::GetBalanceResponse,
+        get_config::GetConfigResponse, get_contract::GetContractResponse,
+        get_holder::GetHolderResponse, get_storage_at::GetStorageAtReturn,
+        simulate_solana::SimulateSolanaResponse,
     },
     types::{
-        EmulateApiRequest, GetBalanceRequest, GetContractRequest, GetHolderRequest,
-        GetStorageAtRequest,
+        EmulateApiRequest, EmulateBatchApiRequest, EstimateGasApiRequest, GetBalanceRequest,
+        GetContractRequest, GetHolderRequest, GetStorageAtRequest, SimulateSolanaRequest,
     },
 };
 
@@ -26,6 +28,14 @@ type NeonRpcClientResult<T> = Result<T, NeonRpcClientError>;
 #[async_trait(?Send)]
 pub trait NeonRpcClient {
     async fn emulate(&self, params: EmulateApiRequest) -> NeonRpcClientResult<EmulateResponse>;
+    async fn emulate_batch(
+        &self,
+        params: EmulateBatchApiRequest,
+    ) -> NeonRpcClientResult<EmulateBatchResponse>;
+    async fn estimate_gas(
+        &self,
+        params: EstimateGasApiRequest,
+    ) -> NeonRpcClientResult<EstimateGasResponse>;
     async fn balance(
         &self,
         params: GetBalanceRequest,
@@ -41,4 +51,8 @@ pub trait NeonRpcClient {
         params: GetStorageAtRequest,
     ) -> NeonRpcClientResult<GetStorageAtReturn>;
     async fn trace(&self, params: EmulateApiRequest) -> NeonRpcClientResult<serde_json::Value>;
+    async fn simulate_solana(
+        &self,
+        params: SimulateSolanaRequest,
+    ) -> NeonRpcClientResult<SimulateSolanaResponse>;
 }
@@ -1,6 +1,6 @@
-use neon_lib_interface::NeonEVMLib_Ref;
-use std::collections::HashMap;
+use crate::libraries::Libraries;
+use std::sync::RwLock;
 
 pub struct Context {
-    pub libraries: HashMap<String, NeonEVMLib_Ref>,
+    pub libraries: RwLock<Libraries>,
 }
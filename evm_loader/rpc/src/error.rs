@@ -1,4 +1,4 @@
-use neon_lib::errors::NeonError;
+use neon_lib::errors::{ErrorKind, NeonError};
 use neon_lib_interface::NeonEVMLibLoadError;
 use std::net::AddrParseError;
 
@@ -22,6 +22,20 @@ pub enum NeonRPCError {
 
 impl From<NeonRPCError> for jsonrpc_v2::Error {
     fn from(value: NeonRPCError) -> Self {
-        Self::internal(value)
+        let kind = match &value {
+            NeonRPCError::NeonError(e) => e.kind(),
+            NeonRPCError::IncorrectParameters() => ErrorKind::BadRequest,
+            _ => ErrorKind::Internal,
+        };
+        let data = match &value {
+            NeonRPCError::NeonError(e) => e.data(),
+            _ => None,
+        };
+
+        Self::Full {
+            code: kind.jsonrpc_code(),
+            message: value.to_string(),
+            data: data.map(|data| Box::new(data) as _),
+        }
     }
 }
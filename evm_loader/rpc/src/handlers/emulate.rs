@@ -10,10 +10,5 @@ pub async fn handle(
     Params(params): Params<Vec<EmulateApiRequest>>,
 ) -> Result<serde_json::Value, jsonrpc_v2::Error> {
     let param = params.first().ok_or(NeonRPCError::IncorrectParameters())?;
-    invoke(
-        LibMethod::Emulate,
-        ctx,
-        Some(serde_json::value::to_value(param).unwrap()),
-    )
-    .await
+    invoke(LibMethod::Emulate, ctx, Some(param)).await
 }
@@ -0,0 +1,25 @@
+#![allow(clippy::future_not_send)]
+
+use super::invoke;
+use crate::context::Context;
+use jsonrpc_v2::Data;
+use neon_lib::{errors::ErrorKind, LibMethod};
+
+pub async fn handle(ctx: Data<Context>) -> Result<serde_json::Value, jsonrpc_v2::Error> {
+    invoke(LibMethod::Health, ctx, Option::<serde_json::Value>::None).await
+}
+
+/// Fails with the health report as error data while any dependency is unavailable
+pub async fn ready(ctx: Data<Context>) -> Result<serde_json::Value, jsonrpc_v2::Error> {
+    let health = invoke(LibMethod::Health, ctx, Option::<serde_json::Value>::None).await?;
+
+    if health["ready"].as_bool() == Some(true) {
+        return Ok(health);
+    }
+
+    Err(jsonrpc_v2::Error::Full {
+        code: ErrorKind::Upstream.jsonrpc_code(),
+        message: "Service is not ready".to_string(),
+        data: Some(Box::new(health)),
+    })
+}
@@ -0,0 +1,23 @@
+#![allow(clippy::future_not_send)]
+
+use super::invoke;
+use crate::context::Context;
+use actix_web::HttpResponse;
+use jsonrpc_v2::Data;
+use neon_lib::{metrics, LibMethod};
+use serde_json::Value;
+
+/// Metrics of neon-rpc itself followed by the metrics of the library serving requests.
+/// The library exports its metrics under the `neon_lib_` namespace, so the families don't clash.
+pub async fn handle(ctx: Data<Context>) -> HttpResponse {
+    let mut body = metrics::gather();
+
+    match invoke(LibMethod::Metrics, ctx, Option::<Value>::None).await {
+        Ok(Value::String(library_metrics)) => body.push_str(&library_metrics),
+        _ => tracing::warn!("Failed to collect library metrics"),
+    }
+
+    HttpResponse::Ok()
+        .content_type(metrics::CONTENT_TYPE)
+        .body(body)
+}
@@ -1,33 +1,35 @@
 #![allow(clippy::future_not_send)]
 
 pub mod emulate;
+pub mod emulate_batch;
+pub mod estimate_gas;
 pub mod get_balance;
 pub mod get_config;
 pub mod get_contract;
 pub mod get_holder;
 pub mod get_storage_at;
+pub mod health;
 pub mod info;
 pub mod lib_info;
+pub mod metrics;
+pub mod simulate_solana;
 pub mod trace;
 
-use crate::context::Context;
+use crate::{context::Context, libraries::LibraryRef};
 use jsonrpc_v2::Data;
-use neon_lib::LibMethod;
-use neon_lib_interface::{types::NeonEVMLibError, NeonEVMLib_Ref};
+use neon_lib::{errors::ErrorKind, metrics, LibMethod};
+use neon_lib_interface::types::NeonEVMLibError;
 use serde::Serialize;
-use serde_json::Value;
+use serde_json::{json, Value};
+use std::time::Instant;
 
-fn get_library(context: &Data<Context>) -> Result<&NeonEVMLib_Ref, jsonrpc_v2::Error> {
-    // just for testing
-    let hash = context
+fn get_library(context: &Data<Context>) -> Result<LibraryRef, jsonrpc_v2::Error> {
+    let library = context
         .libraries
-        .keys()
-        .last()
-        .ok_or_else(|| jsonrpc_v2::Error::internal("library collection is empty"));
-    let has_ref = &hash?.clone();
-    let library = context.libraries.get(has_ref).ok_or_else(|| {
-        jsonrpc_v2::Error::internal(format!("Library not found for hash  {has_ref:?}"))
-    })?;
+        .read()
+        .unwrap()
+        .latest()
+        .ok_or_else(|| jsonrpc_v2::Error::internal("library collection is empty"))?;
 
     tracing::debug!("ver {:?}", library.hash()());
 
@@ -42,40 +44,99 @@ pub async fn invoke(
     let library = get_library(&context)?;
 
     let method_str: &str = method.into();
-    let mut params_str: String = String::new();
-    if let Some(params_value) = params {
-        params_str = serde_json::to_string(&params_value).unwrap();
-    }
+    let params_str = match params {
+        Some(params_value) => {
+            serde_json::to_string(&params_value).map_err(jsonrpc_v2::Error::internal)?
+        }
+        None => String::new(),
+    };
 
-    library.invoke()(method_str.into(), params_str.as_str().into())
+    let time_start = Instant::now();
+    let result = library.invoke()(method_str.into(), params_str.as_str().into())
         .await
-        .map(|x| serde_json::from_str::<serde_json::Value>(&x).unwrap())
-        .map_err(|s| {
-            let NeonEVMLibError {
-                code,
-                message,
-                data,
-            } = serde_json::from_str(s.as_str()).unwrap();
-
-            jsonrpc_v2::Error::Full {
-                code: i64::from(code),
-                message,
-                data: Some(Box::new(
-                    data.as_ref()
-                        .and_then(Value::as_str)
-                        .unwrap_or("null")
-                        .to_string(),
-                )),
-            }
-        })
-        .into()
+        .into_result();
+    metrics::observe_request(method_str, time_start.elapsed());
+
+    match result {
+        Ok(value) => serde_json::from_str::<serde_json::Value>(&value)
+            .map_err(|e| jsonrpc_v2::Error::internal(format!("Malformed library response: {e}"))),
+        Err(error) => Err(library_error(&error)),
+    }
+}
+
+fn library_error(error: &str) -> jsonrpc_v2::Error {
+    let Ok(NeonEVMLibError {
+        code,
+        kind,
+        message,
+        data,
+    }) = serde_json::from_str(error)
+    else {
+        return jsonrpc_v2::Error::internal(format!("Malformed library error: {error}"));
+    };
+
+    metrics::observe_error(code);
+
+    let data = match (kind, data) {
+        (ErrorKind::Revert, Some(revert_data)) => revert_data,
+        _ => json!({ "error_code": code }),
+    };
+
+    jsonrpc_v2::Error::Full {
+        code: kind.jsonrpc_code(),
+        message,
+        data: Some(Box::new(data)),
+    }
 }
 
 pub async fn lib_build_info(
     context: Data<Context>,
 ) -> Result<serde_json::Value, jsonrpc_v2::Error> {
-    let library = get_library(&context)?;
-    let build_info = library.get_build_info()();
+    let libraries: Vec<LibraryRef> = context
+        .libraries
+        .read()
+        .unwrap()
+        .iter()
+        .map(|(_, library)| library.clone())
+        .collect();
 
-    Ok(serde_json::from_str::<serde_json::Value>(&build_info).unwrap())
+    let mut result = serde_json::Map::new();
+    for library in libraries {
+        let hash = library.hash()();
+        let build_info = library.get_build_info()();
+
+        result.insert(
+            hash.into_string(),
+            serde_json::from_str::<serde_json::Value>(&build_info)
+                .map_err(jsonrpc_v2::Error::internal)?,
+        );
+    }
+
+    Ok(Value::Object(result))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn jsonrpc_code(error: &serde_json::Value) -> i64 {
+        match library_error(&error.to_string()) {
+            jsonrpc_v2::Error::Full { code, .. } | jsonrpc_v2::Error::Provided { code, .. } => code,
+        }
+    }
+
+    #[test]
+    fn test_library_error() {
+        let revert =
+            json!({"code": 267, "kind": "revert", "message": "reverted", "data": "0xdead"});
+        assert_eq!(jsonrpc_code(&revert), 3);
+
+        let program =
+            json!({"code": 117, "kind": "internal", "message": "invalid owner", "data": null});
+        assert_eq!(jsonrpc_code(&program), -32603);
+
+        // Library without error kinds
+        let legacy = json!({"code": 117, "message": "invalid owner", "data": null});
+        assert_eq!(jsonrpc_code(&legacy), -32603);
+    }
 }
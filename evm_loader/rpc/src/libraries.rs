@@ -0,0 +1,304 @@
+use crate::context::Context;
+use crate::NeonRPCResult;
+use jsonrpc_v2::Data;
+use neon_lib_interface::{load_library, NeonEVMLib_Ref};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime};
+use tracing::{error, info, warn};
+
+/// Reference to a loaded library. Requests keep a clone while they run,
+/// so a library removed from the directory stays usable until they finish.
+pub type LibraryRef = Arc<NeonEVMLib_Ref>;
+
+struct LibraryFile {
+    hash: String,
+    modified: Option<SystemTime>,
+}
+
+#[derive(Default)]
+pub struct Libraries {
+    files: HashMap<PathBuf, LibraryFile>,
+    active: HashMap<String, LibraryRef>,
+    /// Hashes of active libraries in the order they were loaded
+    order: Vec<String>,
+    retiring: Vec<(String, LibraryRef)>,
+}
+
+impl Libraries {
+    pub fn load(directory: &Path) -> NeonRPCResult<Self> {
+        let libraries = RwLock::new(Self::default());
+        sync(&libraries, directory)?;
+
+        Ok(libraries.into_inner().unwrap())
+    }
+
+    /// The most recently loaded library
+    pub fn latest(&self) -> Option<LibraryRef> {
+        self.order
+            .last()
+            .and_then(|hash| self.active.get(hash))
+            .cloned()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &LibraryRef)> {
+        self.order
+            .iter()
+            .filter_map(|hash| self.active.get_key_value(hash))
+    }
+
+    pub fn len(&self) -> usize {
+        self.active.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.active.is_empty()
+    }
+
+    /// Files removed from the directory and files which are new or modified
+    fn changes(
+        &self,
+        entries: &HashMap<PathBuf, Option<SystemTime>>,
+    ) -> (Vec<PathBuf>, Vec<PathBuf>) {
+        let removed = self
+            .files
+            .keys()
+            .filter(|path| !entries.contains_key(*path))
+            .cloned()
+            .collect();
+
+        let changed = entries
+            .iter()
+            .filter(|(path, modified)| {
+                self.files
+                    .get(*path)
+                    .map_or(true, |file| file.modified != **modified)
+            })
+            .map(|(path, _)| path.clone())
+            .collect();
+
+        (removed, changed)
+    }
+
+    fn remove_file(&mut self, path: &Path) {
+        if let Some(LibraryFile { hash, .. }) = self.files.remove(path) {
+            self.retire_unused(hash, path);
+        }
+    }
+
+    /// Inserts the loaded library, a previous version of the same file is retired afterwards
+    fn insert_file(
+        &mut self,
+        path: PathBuf,
+        modified: Option<SystemTime>,
+        hash: String,
+        library: NeonEVMLib_Ref,
+    ) {
+        info!("Library {} loaded: {}", hash, path.display());
+
+        if !self.active.contains_key(&hash) {
+            self.active.insert(hash.clone(), Arc::new(library));
+            self.order.push(hash.clone());
+        }
+
+        let previous = self
+            .files
+            .insert(path.clone(), LibraryFile { hash, modified });
+        if let Some(LibraryFile { hash, .. }) = previous {
+            self.retire_unused(hash, &path);
+        }
+    }
+
+    fn retire_unused(&mut self, hash: String, path: &Path) {
+        if self.files.values().any(|file| file.hash == hash) {
+            return;
+        }
+
+        self.order.retain(|h| h != &hash);
+        if let Some(library) = self.active.remove(&hash) {
+            info!("Library {} removed: {}", hash, path.display());
+            self.retiring.push((hash, library));
+        }
+    }
+
+    fn collect_retired(&mut self) {
+        self.retiring.retain(|(hash, library)| {
+            let in_use = Arc::strong_count(library) > 1;
+            if !in_use {
+                info!("Library {} retired", hash);
+            }
+            in_use
+        });
+    }
+}
+
+fn is_library(path: &Path) -> bool {
+    path.is_file() && path.extension().map_or(false, |ext| ext == "so")
+}
+
+fn scan(directory: &Path) -> NeonRPCResult<HashMap<PathBuf, Option<SystemTime>>> {
+    let mut entries = HashMap::new();
+    for entry in std::fs::read_dir(directory)? {
+        let path = entry?.path();
+        if !is_library(&path) {
+            continue;
+        }
+
+        let modified = std::fs::metadata(&path)?.modified().ok();
+        entries.insert(path, modified);
+    }
+
+    Ok(entries)
+}
+
+/// Brings the set of loaded libraries in line with the content of the directory.
+/// Libraries are loaded without holding the lock, so requests are not blocked meanwhile.
+/// A modified library is replaced only after its new version is loaded, a file that fails
+/// to load (e.g. it is still being copied) keeps its previous version and is retried on the next sync.
+pub fn sync(libraries: &RwLock<Libraries>, directory: &Path) -> NeonRPCResult<()> {
+    let entries = scan(directory)?;
+    let (removed, changed) = libraries.read().unwrap().changes(&entries);
+
+    let mut loaded = Vec::with_capacity(changed.len());
+    for path in changed {
+        match load_library(&path) {
+            Ok((hash, library)) => loaded.push((path, hash, library)),
+            Err(e) => warn!("Failed to load library {}: {e}", path.display()),
+        }
+    }
+
+    let mut libraries = libraries.write().unwrap();
+    for path in removed {
+        libraries.remove_file(&path);
+    }
+    for (path, hash, library) in loaded {
+        let modified = entries.get(&path).copied().flatten();
+        libraries.insert_file(path, modified, hash, library);
+    }
+    libraries.collect_retired();
+    drop(libraries);
+
+    Ok(())
+}
+
+pub async fn watch(context: Data<Context>, directory: PathBuf, period: Duration) {
+    info!(
+        "Watching library dir {} every {} sec",
+        directory.display(),
+        period.as_secs()
+    );
+
+    let mut interval = tokio::time::interval(period);
+    interval.tick().await;
+
+    loop {
+        interval.tick().await;
+
+        if let Err(e) = sync(&context.libraries, &directory) {
+            error!(
+                "Failed to reload libraries from {}: {e}",
+                directory.display()
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Two builds of neon-lib with different revisions, separated by `:`
+    const BUILDS_VAR: &str = "NEON_LIB_TEST_BUILDS";
+
+    fn builds() -> (PathBuf, PathBuf) {
+        let builds = std::env::var(BUILDS_VAR).unwrap();
+        let (first, second) = builds.split_once(':').unwrap();
+
+        (PathBuf::from(first), PathBuf::from(second))
+    }
+
+    fn directory(name: &str) -> PathBuf {
+        let directory =
+            std::env::temp_dir().join(format!("neon-rpc-libraries-{}-{name}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&directory);
+        std::fs::create_dir_all(&directory).unwrap();
+        directory
+    }
+
+    /// Modification time may not change between quick writes, forget it to force the reload
+    fn touch(libraries: &RwLock<Libraries>, path: &Path) {
+        libraries
+            .write()
+            .unwrap()
+            .files
+            .get_mut(path)
+            .unwrap()
+            .modified = None;
+    }
+
+    fn hashes(libraries: &RwLock<Libraries>) -> Vec<String> {
+        libraries
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(hash, library)| {
+                assert_eq!(&library.hash()().into_string(), hash);
+                hash.clone()
+            })
+            .collect()
+    }
+
+    #[test]
+    #[ignore = "requires NEON_LIB_TEST_BUILDS with two neon-lib builds"]
+    fn test_reload() {
+        let (first, second) = builds();
+        let (first_hash, _) = load_library(&first).unwrap();
+        let (second_hash, _) = load_library(&second).unwrap();
+        assert_ne!(first_hash, second_hash);
+
+        let directory = directory("reload");
+        let path = directory.join("lib.so");
+        let libraries = RwLock::new(Libraries::default());
+
+        std::fs::copy(&first, &path).unwrap();
+        std::fs::write(directory.join("readme.txt"), "not a library").unwrap();
+        sync(&libraries, &directory).unwrap();
+        assert_eq!(hashes(&libraries), [first_hash.clone()]);
+        let running = libraries.read().unwrap().latest().unwrap();
+
+        // Failed load keeps the previous version
+        std::fs::write(&path, "broken").unwrap();
+        touch(&libraries, &path);
+        sync(&libraries, &directory).unwrap();
+        assert_eq!(hashes(&libraries), [first_hash.clone()]);
+
+        // Modified file is served with the new revision,
+        // the replaced library stays alive while a request uses it
+        std::fs::copy(&second, &path).unwrap();
+        touch(&libraries, &path);
+        sync(&libraries, &directory).unwrap();
+        assert_eq!(hashes(&libraries), [second_hash.clone()]);
+        assert_eq!(libraries.read().unwrap().retiring.len(), 1);
+        assert_eq!(running.hash()().into_string(), first_hash);
+
+        drop(running);
+        sync(&libraries, &directory).unwrap();
+        assert!(libraries.read().unwrap().retiring.is_empty());
+
+        // Both revisions are served side by side
+        std::fs::copy(&first, directory.join("old.so")).unwrap();
+        sync(&libraries, &directory).unwrap();
+        assert_eq!(hashes(&libraries), [second_hash, first_hash.clone()]);
+        let latest = libraries.read().unwrap().latest().unwrap();
+        assert_eq!(latest.hash()().into_string(), first_hash);
+        drop(latest);
+
+        std::fs::remove_file(&path).unwrap();
+        std::fs::remove_file(directory.join("old.so")).unwrap();
+        sync(&libraries, &directory).unwrap();
+        assert!(libraries.read().unwrap().is_empty());
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+}
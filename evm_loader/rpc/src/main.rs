@@ -7,14 +7,17 @@ mod build_info;
 mod context;
 mod error;
 mod handlers;
+mod libraries;
 mod options;
 mod rpc;
 
 use crate::build_info::get_build_info;
 use context::Context;
 use error::NeonRPCError;
+use jsonrpc_v2::Data;
+use libraries::Libraries;
 use neon_lib::config;
-use std::{env, net::SocketAddr, str::FromStr};
+use std::{env, net::SocketAddr, path::PathBuf, str::FromStr, sync::RwLock, time::Duration};
 use tracing::info;
 use tracing_appender::non_blocking::NonBlockingBuilder;
 
@@ -31,19 +34,19 @@ async fn main() -> NeonRPCResult<()> {
 
     tracing_subscriber::fmt().with_writer(non_blocking).init();
 
-    let lib_dir = matches.value_of("LIB-DIR").unwrap();
-    let libraries = neon_lib_interface::load_libraries(lib_dir)?;
+    let lib_dir = PathBuf::from(matches.value_of("LIB-DIR").unwrap());
+    let libraries = Libraries::load(&lib_dir)?;
 
     info!("BUILD INFO: {}", get_build_info());
     info!(
         "LIBRARY DIR: {}, count: {}",
-        lib_dir,
-        libraries.keys().len(),
+        lib_dir.display(),
+        libraries.len(),
     );
 
-    if libraries.keys().len() > 0 {
+    if !libraries.is_empty() {
         info!("=== LIBRARY VERSIONS: =================================================================");
-        for library_ver in libraries.keys() {
+        for (library_ver, _) in libraries.iter() {
             info!("Lib version: {}", library_ver);
         }
         info!("=== END LIBRARY VERSIONS ==============================================================");
@@ -52,8 +55,23 @@ async fn main() -> NeonRPCResult<()> {
     // check configs
     let _api_config = config::load_api_config_from_environment();
 
-    let ctx = Context { libraries };
-    let rpc = rpc::build_rpc(ctx);
+    let ctx = Data::new(Context {
+        libraries: RwLock::new(libraries),
+    });
+
+    let reload_interval: u64 = matches
+        .value_of("LIB-RELOAD-INTERVAL")
+        .map_or(Ok(0), str::parse)
+        .map_err(|_| NeonRPCError::IncorrectParameters())?;
+    if reload_interval > 0 {
+        actix_web::rt::spawn(libraries::watch(
+            ctx.clone(),
+            lib_dir,
+            Duration::from_secs(reload_interval),
+        ));
+    }
+
+    let rpc = rpc::build_rpc(ctx.clone());
 
     let listener_addr = matches
         .value_of("host")
@@ -67,11 +85,17 @@ async fn main() -> NeonRPCResult<()> {
 
     actix_web::HttpServer::new(move || {
         let rpc = rpc.clone();
-        actix_web::App::new().service(
-            actix_web::web::service("/")
-                .guard(actix_web::guard::Post())
-                .finish(rpc.into_web_service()),
-        )
+        let ctx = ctx.clone();
+        actix_web::App::new()
+            .route(
+                "/metrics",
+                actix_web::web::get().to(move || handlers::metrics::handle(ctx.clone())),
+            )
+            .service(
+                actix_web::web::service("/")
+                    .guard(actix_web::guard::Post())
+                    .finish(rpc.into_web_service()),
+            )
     })
     .bind(addr)?
     .run()
@@ -22,5 +22,14 @@ pub fn parse<'a>() -> ArgMatches<'a> {
                 .required(false)
                 .index(2),
         )
+        .arg(
+            clap::Arg::with_name("LIB-RELOAD-INTERVAL")
+                .long("lib-reload-interval")
+                .env("NEON_LIB_RELOAD_INTERVAL")
+                .value_name("SECONDS")
+                .default_value("10")
+                .help("Period of checking LIB-DIR for added or removed libraries, 0 disables reloading")
+                .required(false),
+        )
         .get_matches()
 }
@@ -1,24 +1,32 @@
 use crate::context::Context;
 use crate::handlers::{
-    emulate, get_balance, get_config, get_contract, get_holder, get_storage_at, info, lib_info,
-    trace,
+    emulate, emulate_batch, estimate_gas, get_balance, get_config, get_contract, get_holder,
+    get_storage_at, health, info, lib_info, simulate_solana, trace,
 };
 
 use jsonrpc_v2::{Data, MapRouter, Server};
 use neon_lib::LibMethod;
 use std::sync::Arc;
 
-pub fn build_rpc(ctx: Context) -> Arc<Server<MapRouter>> {
+pub fn build_rpc(ctx: Data<Context>) -> Arc<Server<MapRouter>> {
     Server::new()
-        .with_data(Data::new(ctx))
+        .with_data(ctx)
         .with_method("build_info", info::handle)
         .with_method("lib_build_info", lib_info::handle)
         .with_method(LibMethod::GetStorageAt.to_string(), get_storage_at::handle)
         .with_method(LibMethod::Trace.to_string(), trace::handle)
         .with_method(LibMethod::Emulate.to_string(), emulate::handle)
+        .with_method(LibMethod::EmulateBatch.to_string(), emulate_batch::handle)
+        .with_method(LibMethod::EstimateGas.to_string(), estimate_gas::handle)
         .with_method(LibMethod::GetBalance.to_string(), get_balance::handle)
         .with_method(LibMethod::GetConfig.to_string(), get_config::handle)
         .with_method(LibMethod::GetHolder.to_string(), get_holder::handle)
         .with_method(LibMethod::GetContract.to_string(), get_contract::handle)
+        .with_method(
+            LibMethod::SimulateSolana.to_string(),
+            simulate_solana::handle,
+        )
+        .with_method(LibMethod::Health.to_string(), health::handle)
+        .with_method("ready", health::ready)
         .finish()
 }